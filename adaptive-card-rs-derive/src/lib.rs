@@ -0,0 +1,323 @@
+//! Derive macros for [`adaptive-card-rs`](https://docs.rs/adaptive-card-rs).
+//!
+//! Use them through the `derive` feature of `adaptive-card-rs`, which re-exports them from
+//! its `form` module.
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Data, DeriveInput, Fields, LitBool, LitStr, Meta, Token, parse_macro_input};
+
+/// Generates `AdaptiveForm` for a struct with named fields.
+///
+/// Each field becomes the input chosen by its type's `FormField` implementation. Fields are
+/// required unless they are an `Option` or marked `#[adaptive(required = false)]`.
+///
+/// Struct attributes: `#[adaptive(submit = "Send")]` sets the submit button title.
+///
+/// Field attributes: `label = ".."`, `placeholder = ".."`, `id = ".."`,
+/// `required = true|false` and `skip`. Ids follow `#[serde(rename)]` and
+/// `#[serde(rename_all)]` so submissions deserialize back into the struct.
+#[proc_macro_derive(AdaptiveForm, attributes(adaptive))]
+pub fn derive_adaptive_form(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_form(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generates `AdaptiveChoices` and `FormField` for an enum of unit variants, so that fields
+/// of the enum's type become a choice set.
+///
+/// Variant attributes: `title = ".."` and `value = ".."`. Values follow `#[serde(rename)]`
+/// and `#[serde(rename_all)]`.
+#[proc_macro_derive(AdaptiveChoices, attributes(adaptive))]
+pub fn derive_adaptive_choices(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_choices(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_form(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "AdaptiveForm can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "AdaptiveForm requires a struct with named fields",
+        ));
+    };
+
+    let container = Options::parse(&input.attrs)?;
+    let rename_all = serde_rename_all(&input.attrs)?;
+    let submit = container.submit.unwrap_or_else(|| "Submit".to_string());
+
+    let mut inputs = Vec::new();
+    for field in &fields.named {
+        let options = Options::parse(&field.attrs)?;
+        if options.skip {
+            continue;
+        }
+        let name = field.ident.as_ref().expect("named field").to_string();
+        let name = name.strip_prefix("r#").unwrap_or(&name);
+        let id = match options.id.or_else(|| serde_rename(&field.attrs)) {
+            Some(id) => id,
+            None => rename_all.apply(name),
+        };
+        let label = options.label.unwrap_or_else(|| sentence_case(name));
+        let placeholder = match options.placeholder {
+            Some(placeholder) => quote!(::core::option::Option::Some(#placeholder.to_string())),
+            None => quote!(::core::option::Option::None),
+        };
+        let required = options.required.unwrap_or(true);
+        let ty = &field.ty;
+        inputs.push(quote! {
+            <#ty as ::adaptive_card_rs::form::FormField>::input(::adaptive_card_rs::form::Field {
+                id: #id.to_string(),
+                label: #label.to_string(),
+                placeholder: #placeholder,
+                required: #required,
+            })
+        });
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::adaptive_card_rs::form::AdaptiveForm for #name #ty_generics #where_clause {
+            fn form_elements() -> ::std::vec::Vec<::adaptive_card_rs::card::CardElement> {
+                ::std::vec![#(#inputs),*]
+            }
+
+            fn submit_action() -> ::adaptive_card_rs::actions::Action {
+                ::adaptive_card_rs::actions::SubmitAction::builder()
+                    .title(#submit)
+                    .into()
+            }
+        }
+    })
+}
+
+fn expand_choices(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "AdaptiveChoices can only be derived for enums",
+        ));
+    };
+
+    let rename_all = serde_rename_all(&input.attrs)?;
+    let mut choices = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "AdaptiveChoices requires unit variants",
+            ));
+        }
+        let options = Options::parse(&variant.attrs)?;
+        if options.skip {
+            continue;
+        }
+        let name = variant.ident.to_string();
+        let value = match options.value.or_else(|| serde_rename(&variant.attrs)) {
+            Some(value) => value,
+            None => rename_all.apply(&name),
+        };
+        let title = options.title.unwrap_or_else(|| sentence_case(&name));
+        choices.push(quote!(::adaptive_card_rs::inputs::InputChoice::new(#title, #value)));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::adaptive_card_rs::form::AdaptiveChoices for #name #ty_generics #where_clause {
+            fn choices() -> ::std::vec::Vec<::adaptive_card_rs::inputs::InputChoice> {
+                ::std::vec![#(#choices),*]
+            }
+        }
+
+        impl #impl_generics ::adaptive_card_rs::form::FormField for #name #ty_generics #where_clause {
+            fn input(field: ::adaptive_card_rs::form::Field) -> ::adaptive_card_rs::card::CardElement {
+                ::adaptive_card_rs::form::choice_set::<Self>(field)
+            }
+        }
+    })
+}
+
+/// The contents of `#[adaptive(..)]` attributes.
+#[derive(Default)]
+struct Options {
+    submit: Option<String>,
+    id: Option<String>,
+    label: Option<String>,
+    placeholder: Option<String>,
+    required: Option<bool>,
+    title: Option<String>,
+    value: Option<String>,
+    skip: bool,
+}
+
+impl Options {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut options = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("adaptive")) {
+            attr.parse_nested_meta(|meta| {
+                let string = |meta: &syn::meta::ParseNestedMeta| -> syn::Result<Option<String>> {
+                    Ok(Some(meta.value()?.parse::<LitStr>()?.value()))
+                };
+                if meta.path.is_ident("submit") {
+                    options.submit = string(&meta)?;
+                } else if meta.path.is_ident("id") {
+                    options.id = string(&meta)?;
+                } else if meta.path.is_ident("label") {
+                    options.label = string(&meta)?;
+                } else if meta.path.is_ident("placeholder") {
+                    options.placeholder = string(&meta)?;
+                } else if meta.path.is_ident("title") {
+                    options.title = string(&meta)?;
+                } else if meta.path.is_ident("value") {
+                    options.value = string(&meta)?;
+                } else if meta.path.is_ident("required") {
+                    options.required = Some(if meta.input.peek(Token![=]) {
+                        meta.value()?.parse::<LitBool>()?.value
+                    } else {
+                        true
+                    });
+                } else if meta.path.is_ident("skip") {
+                    options.skip = true;
+                } else {
+                    return Err(meta.error("unknown adaptive attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+/// Returns the value of `#[serde(rename = "..")]`, if present.
+fn serde_rename(attrs: &[Attribute]) -> Option<String> {
+    serde_name_value(attrs, "rename").ok().flatten()
+}
+
+fn serde_rename_all(attrs: &[Attribute]) -> syn::Result<RenameAll> {
+    let Some(rule) = serde_name_value(attrs, "rename_all")? else {
+        return Ok(RenameAll::None);
+    };
+    Ok(match rule.as_str() {
+        "lowercase" => RenameAll::Lower,
+        "UPPERCASE" => RenameAll::Upper,
+        "PascalCase" => RenameAll::Pascal,
+        "camelCase" => RenameAll::Camel,
+        "snake_case" => RenameAll::Snake,
+        "SCREAMING_SNAKE_CASE" => RenameAll::ScreamingSnake,
+        "kebab-case" => RenameAll::Kebab,
+        "SCREAMING-KEBAB-CASE" => RenameAll::ScreamingKebab,
+        // Serde reports unknown rules itself.
+        _ => RenameAll::None,
+    })
+}
+
+fn serde_name_value(attrs: &[Attribute], name: &str) -> syn::Result<Option<String>> {
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        let metas = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        for meta in metas {
+            if let Meta::NameValue(meta) = meta
+                && meta.path.is_ident(name)
+                && let syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(value),
+                    ..
+                }) = &meta.value
+            {
+                return Ok(Some(value.value()));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// The subset of serde's `rename_all` rules, applied to a field or variant name.
+enum RenameAll {
+    None,
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameAll {
+    fn apply(&self, name: &str) -> String {
+        let words = words(name);
+        let capitalized = || {
+            words
+                .iter()
+                .map(|word| capitalize(word))
+                .collect::<String>()
+        };
+        match self {
+            Self::None => name.to_string(),
+            Self::Lower => name.to_lowercase(),
+            Self::Upper => name.to_uppercase(),
+            Self::Pascal => capitalized(),
+            Self::Camel => {
+                let pascal = capitalized();
+                let mut chars = pascal.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_lowercase().chain(chars).collect())
+                    .unwrap_or_default()
+            }
+            Self::Snake => words.join("_"),
+            Self::ScreamingSnake => words.join("_").to_uppercase(),
+            Self::Kebab => words.join("-"),
+            Self::ScreamingKebab => words.join("-").to_uppercase(),
+        }
+    }
+}
+
+/// Splits a `snake_case` or `PascalCase` name into lowercase words.
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for c in name.chars() {
+        if c == '_' {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+        } else if c.is_uppercase() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            current.extend(c.to_lowercase());
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Turns `first_name` or `FirstName` into `First name`.
+fn sentence_case(name: &str) -> String {
+    capitalize(&words(name).join(" "))
+}
@@ -0,0 +1,12 @@
+use adaptive_card_rs::form::AdaptiveChoices;
+
+#[derive(AdaptiveChoices)]
+enum Priority {
+    Low,
+    Custom(u8),
+}
+
+#[derive(AdaptiveChoices)]
+struct High;
+
+fn main() {}
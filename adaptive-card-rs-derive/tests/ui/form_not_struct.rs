@@ -0,0 +1,11 @@
+use adaptive_card_rs::form::AdaptiveForm;
+
+#[derive(AdaptiveForm)]
+enum Ticket {
+    Bug,
+}
+
+#[derive(AdaptiveForm)]
+struct Tuple(String);
+
+fn main() {}
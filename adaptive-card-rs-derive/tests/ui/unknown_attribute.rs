@@ -0,0 +1,9 @@
+use adaptive_card_rs::form::AdaptiveForm;
+
+#[derive(AdaptiveForm)]
+struct Ticket {
+    #[adaptive(hint = "What went wrong?")]
+    summary: String,
+}
+
+fn main() {}
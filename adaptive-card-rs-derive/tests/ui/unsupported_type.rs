@@ -0,0 +1,13 @@
+use adaptive_card_rs::form::AdaptiveForm;
+
+struct Owner {
+    name: String,
+}
+
+#[derive(AdaptiveForm)]
+struct Ticket {
+    summary: String,
+    owner: Owner,
+}
+
+fn main() {}
@@ -2,7 +2,7 @@ use crate::common::{ActionMode, ActionStyle, AssociatedInputs};
 use serde::{Deserialize, Serialize};
 
 // ActionSet element
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ActionSet {
     pub actions: Vec<Action>,
@@ -20,10 +20,47 @@ pub enum Action {
     ShowCard(ShowCardAction),
     #[serde(rename = "Action.ToggleVisibility")]
     ToggleVisibility(ToggleVisibilityAction),
+    #[serde(rename = "Action.Execute")]
+    Execute(ExecuteAction),
+}
+
+impl Action {
+    /// Returns the label of the button or link that represents the action.
+    pub fn title(&self) -> Option<&str> {
+        match self {
+            Action::OpenUrl(a) => a.title.as_deref(),
+            Action::Submit(a) => a.title.as_deref(),
+            Action::ShowCard(a) => a.title.as_deref(),
+            Action::ToggleVisibility(a) => a.title.as_deref(),
+            Action::Execute(a) => a.title.as_deref(),
+        }
+    }
+
+    /// Returns the unique identifier of the action, if it has one.
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            Action::OpenUrl(a) => a.id.as_deref(),
+            Action::Submit(a) => a.id.as_deref(),
+            Action::ShowCard(a) => a.id.as_deref(),
+            Action::ToggleVisibility(a) => a.id.as_deref(),
+            Action::Execute(a) => a.id.as_deref(),
+        }
+    }
+
+    /// Returns the style of the action, if set.
+    pub fn style(&self) -> Option<&ActionStyle> {
+        match self {
+            Action::OpenUrl(a) => a.style.as_ref(),
+            Action::Submit(a) => a.style.as_ref(),
+            Action::ShowCard(a) => a.style.as_ref(),
+            Action::ToggleVisibility(a) => a.style.as_ref(),
+            Action::Execute(a) => a.style.as_ref(),
+        }
+    }
 }
 
 /// Opens a URL when the action is invoked.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenUrlAction {
     /// Label for button or link that represents this action.
@@ -52,7 +89,7 @@ pub struct OpenUrlAction {
 }
 
 /// Gathers input fields, merges with optional data field, and sends an event to the client.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct SubmitAction {
     /// Label for button or link that represents this action.
@@ -85,7 +122,7 @@ pub struct SubmitAction {
 }
 
 /// Shows a card when the action is invoked. Note: AdaptiveCard is forward-declared.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ShowCardAction {
     /// Label for button or link that represents this action.
@@ -114,7 +151,7 @@ pub struct ShowCardAction {
 }
 
 /// Toggles the visibility of associated elements.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ToggleVisibilityAction {
     /// Label for button or link that represents this action.
@@ -141,3 +178,40 @@ pub struct ToggleVisibilityAction {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mode: Option<ActionMode>,
 }
+
+/// Gathers input fields, merges with optional data field, and sends an event to the client.
+/// Unlike Action.Submit, the event is routed to the bot as a Universal Action invoke.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecuteAction {
+    /// Label for button or link that represents this action.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The card author-defined verb associated with this action.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verb: Option<String>,
+    /// Initial data that input fields will be combined with. These are essentially 'hidden' properties.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+    /// Controls which inputs are associated with the action.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub associated_inputs: Option<AssociatedInputs>,
+    /// A unique identifier associated with this action.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Optional icon to be shown on the action in conjunction with the title.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+    /// Controls the style of an action.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<ActionStyle>,
+    /// Defines text that should be displayed to the end user as they hover the mouse over the action.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tooltip: Option<String>,
+    /// Determines whether the action should be enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_enabled: Option<bool>,
+    /// Determines whether the action should be displayed as a button or in the overflow menu.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<ActionMode>,
+}
@@ -0,0 +1,462 @@
+//! Bot Framework activities, attachments and invoke responses.
+//!
+//! Bots send cards as attachments of a message activity, and receive `Action.Execute` clicks
+//! as `adaptiveCard/action` invoke activities, which they answer with an [`InvokeResponse`].
+//!
+//! ```
+//! use adaptive_card_rs::bot::{Activity, InvokeResponse};
+//! use adaptive_card_rs::card::AdaptiveCard;
+//!
+//! let card = AdaptiveCard::builder()
+//!     .text("Approve the deploy?")
+//!     .execute(|a| a.title("Approve").verb("approve"))
+//!     .build();
+//! let message = Activity::from(card);
+//! assert_eq!(
+//!     serde_json::to_value(&message).unwrap()["attachments"][0]["contentType"],
+//!     "application/vnd.microsoft.card.adaptive"
+//! );
+//!
+//! // Later, when the button is clicked:
+//! let invoke: Activity = serde_json::from_value(serde_json::json!({
+//!     "type": "invoke",
+//!     "name": "adaptiveCard/action",
+//!     "value": { "action": { "type": "Action.Execute", "verb": "approve" }, "trigger": "manual" }
+//! }))
+//! .unwrap();
+//! let response = match invoke.adaptive_card_action() {
+//!     Some(Ok(invoke)) if invoke.action.verb.as_deref() == Some("approve") => {
+//!         InvokeResponse::card(AdaptiveCard::builder().text("Approved").build())
+//!     }
+//!     _ => InvokeResponse::error(400, "BadRequest", "Unknown action"),
+//! };
+//! assert_eq!(response.status, 200);
+//! ```
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::actions::ExecuteAction;
+use crate::builder::builder;
+use crate::card::AdaptiveCard;
+use crate::teams::Entity;
+
+/// The content type of Adaptive Card attachments and invoke responses.
+pub const ADAPTIVE_CARD_CONTENT_TYPE: &str = "application/vnd.microsoft.card.adaptive";
+/// The content type of invoke responses that show a message.
+pub const MESSAGE_CONTENT_TYPE: &str = "application/vnd.microsoft.activity.message";
+/// The content type of invoke responses that report an error.
+pub const ERROR_CONTENT_TYPE: &str = "application/vnd.microsoft.error";
+/// The name of the invoke activities sent for `Action.Execute`.
+pub const ADAPTIVE_CARD_ACTION: &str = "adaptiveCard/action";
+
+/// A Bot Framework activity, such as a message sent by a bot or an invoke it receives.
+///
+/// Only the properties used with cards are modelled; others are ignored when deserializing.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Activity {
+    /// The type of activity.
+    #[serde(rename = "type")]
+    pub kind: ActivityType,
+    /// The id of the activity, assigned by the channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The id of the activity this one replies to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_to_id: Option<String>,
+    /// The text of a message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// How `text` is formatted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_format: Option<TextFormat>,
+    /// Text shown where the attachments can't be, such as notifications.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// Cards and files attached to a message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<Vec<Attachment>>,
+    /// How multiple attachments are laid out.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachment_layout: Option<AttachmentLayout>,
+    /// Entities referenced by `text`, such as Teams mentions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entities: Option<Vec<Entity>>,
+    /// The name of an invoke or event activity, e.g. `adaptiveCard/action`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The payload of an invoke or event activity.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+}
+
+impl From<AdaptiveCard> for Activity {
+    /// Creates a message with the card as its only attachment.
+    fn from(card: AdaptiveCard) -> Self {
+        Activity {
+            attachments: Some(vec![card.into()]),
+            ..Default::default()
+        }
+    }
+}
+
+impl Activity {
+    /// Returns the `Action.Execute` invoke carried by this activity, if it is one.
+    pub fn adaptive_card_action(&self) -> Option<Result<AdaptiveCardInvoke, serde_json::Error>> {
+        if self.kind != ActivityType::Invoke || self.name.as_deref() != Some(ADAPTIVE_CARD_ACTION) {
+            return None;
+        }
+        let value = self.value.clone().unwrap_or_default();
+        Some(serde_json::from_value(value))
+    }
+
+    /// Returns the cards attached to this activity, skipping other attachments.
+    pub fn cards(&self) -> impl Iterator<Item = Result<AdaptiveCard, serde_json::Error>> + '_ {
+        self.attachments
+            .iter()
+            .flatten()
+            .filter_map(Attachment::card)
+    }
+}
+
+builder! {
+    ActivityBuilder => Activity {
+        new();
+        set { kind: ActivityType }
+        optional {
+            id: String,
+            reply_to_id: String,
+            text: String,
+            text_format: TextFormat,
+            summary: String,
+            attachment_layout: AttachmentLayout,
+            entities: Vec<Entity>,
+            name: String,
+            value: Value,
+        }
+    }
+}
+
+impl ActivityBuilder {
+    /// Appends an [`Attachment`].
+    pub fn attachment(mut self, attachment: impl Into<Attachment>) -> Self {
+        self.inner
+            .attachments
+            .get_or_insert_with(Vec::new)
+            .push(attachment.into());
+        self
+    }
+
+    /// Appends `card` as an attachment.
+    pub fn card(self, card: impl Into<AdaptiveCard>) -> Self {
+        self.attachment(card.into())
+    }
+}
+
+/// The type of an [`Activity`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ActivityType {
+    #[default]
+    Message,
+    Typing,
+    Invoke,
+    InvokeResponse,
+    Event,
+    ConversationUpdate,
+    MessageReaction,
+    MessageUpdate,
+    MessageDelete,
+}
+
+/// How the text of a message is formatted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TextFormat {
+    Markdown,
+    Plain,
+    Xml,
+}
+
+/// How multiple attachments are laid out.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AttachmentLayout {
+    /// One below the other.
+    List,
+    /// Side by side, scrolling horizontally.
+    Carousel,
+}
+
+/// Content attached to a message, such as a card.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Attachment {
+    /// The type of the content, e.g. [`ADAPTIVE_CARD_CONTENT_TYPE`].
+    pub content_type: String,
+    /// The content itself, for cards.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<Value>,
+    /// Where the content can be downloaded, for files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_url: Option<String>,
+    /// The name of the attachment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// A thumbnail shown for the attachment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail_url: Option<String>,
+}
+
+/// This can't fail. Serializing to a [`Value`] only fails for maps whose keys aren't
+/// strings, for internally tagged enum variants that don't hold a struct, and for
+/// `Serialize` impls that return errors. A card's maps are keyed by `String`, its tagged
+/// variants all hold structs, and every type in it derives `Serialize`.
+impl From<AdaptiveCard> for Attachment {
+    fn from(card: AdaptiveCard) -> Self {
+        Attachment {
+            content_type: ADAPTIVE_CARD_CONTENT_TYPE.to_string(),
+            content: Some(serde_json::to_value(card).expect("cards serialize to JSON")),
+            content_url: None,
+            name: None,
+            thumbnail_url: None,
+        }
+    }
+}
+
+impl Attachment {
+    /// Returns the card in this attachment, or `None` if it holds something else.
+    pub fn card(&self) -> Option<Result<AdaptiveCard, serde_json::Error>> {
+        if self.content_type != ADAPTIVE_CARD_CONTENT_TYPE {
+            return None;
+        }
+        let content = self.content.clone().unwrap_or_default();
+        Some(serde_json::from_value(content))
+    }
+}
+
+/// The value of an `adaptiveCard/action` invoke, sent when a user clicks `Action.Execute`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdaptiveCardInvoke {
+    /// The clicked action. Its `data` holds the action's data merged with the card's inputs.
+    pub action: ExecuteAction,
+    /// Whether the user clicked the action or the card refreshed itself.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger: Option<InvokeTrigger>,
+    /// The verification code from a sign-in flow, if the card asked the user to sign in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// The token obtained by single sign-on, if the card uses it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authentication: Option<Value>,
+}
+
+impl AdaptiveCardInvoke {
+    /// Returns the submitted data, to be passed to
+    /// [`AdaptiveCard::parse_submission`](crate::card::AdaptiveCard::parse_submission).
+    pub fn data(&self) -> &Value {
+        static EMPTY: Value = Value::Null;
+        self.action.data.as_ref().unwrap_or(&EMPTY)
+    }
+}
+
+/// What caused an `adaptiveCard/action` invoke.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InvokeTrigger {
+    /// The user clicked the action.
+    Manual,
+    /// The card's `refresh` action ran when the card was shown.
+    Automatic,
+}
+
+/// The HTTP response to an invoke activity.
+///
+/// Bots return `body` with the status code `status`. For `adaptiveCard/action` invokes the
+/// body is an [`AdaptiveCardInvokeResponse`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvokeResponse<T = AdaptiveCardInvokeResponse> {
+    /// The HTTP status code.
+    pub status: u16,
+    /// The response body.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<T>,
+}
+
+impl InvokeResponse {
+    /// Replaces the card the user clicked with `card`.
+    pub fn card(card: impl Into<AdaptiveCard>) -> Self {
+        Self::ok(InvokeResponseValue::Card(Box::new(card.into())))
+    }
+
+    /// Shows `message` to the user, leaving the card unchanged.
+    pub fn message(message: impl Into<String>) -> Self {
+        Self::ok(InvokeResponseValue::Message(message.into()))
+    }
+
+    /// Reports an error to the user. `status_code` is an HTTP status code, e.g. 400.
+    pub fn error(status_code: u16, code: impl Into<String>, message: impl Into<String>) -> Self {
+        InvokeResponse {
+            status: 200,
+            body: Some(AdaptiveCardInvokeResponse {
+                status_code,
+                value: InvokeResponseValue::Error(InvokeError {
+                    code: code.into(),
+                    message: message.into(),
+                }),
+            }),
+        }
+    }
+
+    fn ok(value: InvokeResponseValue) -> Self {
+        InvokeResponse {
+            status: 200,
+            body: Some(AdaptiveCardInvokeResponse {
+                status_code: 200,
+                value,
+            }),
+        }
+    }
+}
+
+/// The body of the response to an `adaptiveCard/action` invoke.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdaptiveCardInvokeResponse {
+    /// The status of the action, as an HTTP status code.
+    pub status_code: u16,
+    /// What the client should do, serialized as `type` and `value`.
+    #[serde(flatten)]
+    pub value: InvokeResponseValue,
+}
+
+/// What the client does after an `adaptiveCard/action` invoke.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum InvokeResponseValue {
+    /// Replace the card.
+    #[serde(rename = "application/vnd.microsoft.card.adaptive")]
+    Card(Box<AdaptiveCard>),
+    /// Show a message.
+    #[serde(rename = "application/vnd.microsoft.activity.message")]
+    Message(String),
+    /// Show an error.
+    #[serde(rename = "application/vnd.microsoft.error")]
+    Error(InvokeError),
+}
+
+/// An error returned from an `adaptiveCard/action` invoke.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvokeError {
+    pub code: String,
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expect_test::expect;
+
+    #[test]
+    fn test_message_activity() {
+        let card = AdaptiveCard::builder().text("Hello").build();
+        let activity = Activity::builder()
+            .summary("Hello")
+            .attachment_layout(AttachmentLayout::Carousel)
+            .card(card.clone())
+            .card(card)
+            .build();
+
+        let expected = expect![[r#"
+            {
+              "type": "message",
+              "summary": "Hello",
+              "attachments": [
+                {
+                  "contentType": "application/vnd.microsoft.card.adaptive",
+                  "content": {
+                    "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                    "body": [
+                      {
+                        "text": "Hello",
+                        "type": "TextBlock"
+                      }
+                    ],
+                    "type": "AdaptiveCard",
+                    "version": "1.2"
+                  }
+                },
+                {
+                  "contentType": "application/vnd.microsoft.card.adaptive",
+                  "content": {
+                    "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                    "body": [
+                      {
+                        "text": "Hello",
+                        "type": "TextBlock"
+                      }
+                    ],
+                    "type": "AdaptiveCard",
+                    "version": "1.2"
+                  }
+                }
+              ],
+              "attachmentLayout": "carousel"
+            }"#]];
+        expected.assert_eq(&serde_json::to_string_pretty(&activity).unwrap());
+        assert_eq!(activity.cards().filter(Result::is_ok).count(), 2);
+    }
+
+    #[test]
+    fn test_adaptive_card_action() {
+        let invoke: Activity = serde_json::from_value(serde_json::json!({
+            "type": "invoke",
+            "name": "adaptiveCard/action",
+            "id": "f:123",
+            "from": { "id": "29:1abc" },
+            "value": {
+                "action": {
+                    "type": "Action.Execute",
+                    "id": "approve",
+                    "verb": "approve",
+                    "data": { "comment": "LGTM" }
+                },
+                "trigger": "manual"
+            }
+        }))
+        .unwrap();
+
+        let action = invoke.adaptive_card_action().unwrap().unwrap();
+        assert_eq!(action.action.verb.as_deref(), Some("approve"));
+        assert_eq!(action.trigger, Some(InvokeTrigger::Manual));
+        assert_eq!(action.data()["comment"], "LGTM");
+        assert!(Activity::default().adaptive_card_action().is_none());
+    }
+
+    #[test]
+    fn test_invoke_responses() {
+        let responses = [
+            InvokeResponse::card(AdaptiveCard::builder().text("Approved").build()),
+            InvokeResponse::message("Saved"),
+            InvokeResponse::error(403, "Forbidden", "Only owners can approve"),
+        ];
+        let json: Vec<_> = responses
+            .iter()
+            .map(|response| serde_json::to_string(response).unwrap())
+            .collect();
+
+        let expected = expect![[r#"
+            [
+                "{\"status\":200,\"body\":{\"statusCode\":200,\"type\":\"application/vnd.microsoft.card.adaptive\",\"value\":{\"type\":\"AdaptiveCard\",\"$schema\":\"http://adaptivecards.io/schemas/adaptive-card.json\",\"version\":\"1.2\",\"body\":[{\"type\":\"TextBlock\",\"text\":\"Approved\"}]}}}",
+                "{\"status\":200,\"body\":{\"statusCode\":200,\"type\":\"application/vnd.microsoft.activity.message\",\"value\":\"Saved\"}}",
+                "{\"status\":200,\"body\":{\"statusCode\":403,\"type\":\"application/vnd.microsoft.error\",\"value\":{\"code\":\"Forbidden\",\"message\":\"Only owners can approve\"}}}",
+            ]
+        "#]];
+        expected.assert_debug_eq(&json);
+        for json in &json {
+            let response: InvokeResponse = serde_json::from_str(json).unwrap();
+            assert_eq!(&serde_json::to_string(&response).unwrap(), json);
+        }
+    }
+}
@@ -0,0 +1,1430 @@
+//! Fluent builders for cards, elements and actions.
+//!
+//! Every type gets a `builder` function whose arguments are the type's required fields, so a
+//! card can't be built without them. Optional fields are set with methods of the same name,
+//! and builders that hold elements or actions have methods that append them.
+//!
+//! ```
+//! use adaptive_card_rs::card::{AdaptiveCard, TextWeight, Version};
+//!
+//! let card = AdaptiveCard::builder()
+//!     .version(Version::V1_5)
+//!     .text("Hi")
+//!     .text_block("Tell us about yourself", |t| t.weight(TextWeight::Bolder).wrap(true))
+//!     .input_text("name", |i| i.label("Name").required())
+//!     .input_choice_set("colour", |i| i.choice("Red", "red").choice("Blue", "blue"))
+//!     .submit(|a| a.title("Send"))
+//!     .build();
+//!
+//! assert_eq!(card.body.len(), 4);
+//! assert_eq!(card.actions.map(|actions| actions.len()), Some(1));
+//! ```
+use serde_json::Value;
+
+use crate::actions::{
+    Action, ActionSet, ExecuteAction, OpenUrlAction, ShowCardAction, SubmitAction,
+    ToggleVisibilityAction,
+};
+use crate::card::{
+    AdaptiveCard, AuthCardButton, Authentication, BackgroundImage, BackgroundImageDetails,
+    CaptionSource, CardElement, Column, ColumnSet, ColumnWidth, Container, ContainerStyle, Fact,
+    FactSet, Image, ImageFillMode, ImageSet, ImageSize, Inline, Media, MediaSource, MsTeams,
+    MsTeamsWidth, Refresh, RichTextBlock, Spacing, Table, TableCell, TableColumnDefinition,
+    TableRow, TextBlock, TextRun, TextSize, TextWeight, TokenExchangeResource, Version,
+};
+use crate::common::{
+    ActionMode, ActionStyle, AssociatedInputs, ChoiceInputStyle, Color, FontType, Height,
+    HorizontalAlignment, TextBlockStyle, TextInputStyle, VerticalContentAlignment,
+};
+use crate::inputs::{
+    InputChoice, InputChoiceSet, InputDate, InputNumber, InputText, InputTime, InputToggle,
+};
+use crate::teams::Entity;
+
+/// Declares a builder for a type that implements `Default`.
+///
+/// Fields listed in `new` become arguments of the type's `builder` function. Fields under
+/// `set` are assigned as is, `optional` fields are wrapped in `Some` and `boxed` fields are
+/// wrapped in `Some(Box::new(..))`.
+macro_rules! builder {
+    (
+        $builder:ident => $target:ident {
+            new($($required:ident: $required_ty:ty),* $(,)?);
+            $(set { $($set:ident: $set_ty:ty),* $(,)? })?
+            optional { $($optional:ident: $optional_ty:ty),* $(,)? }
+            $(boxed { $($boxed:ident: $boxed_ty:ty),* $(,)? })?
+        }
+    ) => {
+        #[doc = concat!("Builds a [`", stringify!($target), "`].")]
+        #[derive(Debug, Clone)]
+        #[must_use]
+        pub struct $builder {
+            // Visible to the modules that extend builders, e.g. `teams`.
+            pub(crate) inner: $target,
+        }
+
+        impl $target {
+            #[doc = concat!("Starts building a [`", stringify!($target), "`].")]
+            pub fn builder($($required: impl Into<$required_ty>),*) -> $builder {
+                $builder {
+                    inner: $target {
+                        $($required: $required.into(),)*
+                        ..Default::default()
+                    },
+                }
+            }
+        }
+
+        impl $builder {
+            $($(
+                #[doc = concat!("Sets [`", stringify!($target), "::", stringify!($set), "`].")]
+                pub fn $set(mut self, $set: impl Into<$set_ty>) -> Self {
+                    self.inner.$set = $set.into();
+                    self
+                }
+            )*)?
+
+            $(
+                #[doc = concat!("Sets [`", stringify!($target), "::", stringify!($optional), "`].")]
+                pub fn $optional(mut self, $optional: impl Into<$optional_ty>) -> Self {
+                    self.inner.$optional = Some($optional.into());
+                    self
+                }
+            )*
+
+            $($(
+                #[doc = concat!("Sets [`", stringify!($target), "::", stringify!($boxed), "`].")]
+                pub fn $boxed(mut self, $boxed: impl Into<$boxed_ty>) -> Self {
+                    self.inner.$boxed = Some(Box::new($boxed.into()));
+                    self
+                }
+            )*)?
+
+            #[doc = concat!("Returns the finished [`", stringify!($target), "`].")]
+            pub fn build(self) -> $target {
+                self.inner
+            }
+        }
+
+        impl From<$builder> for $target {
+            fn from(builder: $builder) -> Self {
+                builder.inner
+            }
+        }
+    };
+}
+
+pub(crate) use builder;
+
+/// Implements `From` for an enum variant and for the builder of the variant's type.
+macro_rules! into_variant {
+    ($enum:ident { $($variant:ident($target:ident, $builder:ident)),* $(,)? }) => {
+        $(
+            impl From<$target> for $enum {
+                fn from(value: $target) -> Self {
+                    $enum::$variant(value)
+                }
+            }
+
+            impl From<$builder> for $enum {
+                fn from(builder: $builder) -> Self {
+                    $enum::$variant(builder.inner)
+                }
+            }
+        )*
+    };
+}
+
+/// Adds methods that append elements to a builder with a `push_element` method.
+macro_rules! element_methods {
+    ($builder:ident) => {
+        impl $builder {
+            /// Appends an element.
+            pub fn element(mut self, element: impl Into<CardElement>) -> Self {
+                self.push_element(element.into());
+                self
+            }
+
+            /// Appends a [`TextBlock`] with the default style.
+            pub fn text(self, text: impl Into<String>) -> Self {
+                self.element(TextBlock::builder(text))
+            }
+
+            /// Appends a [`TextBlock`] configured by `f`.
+            pub fn text_block(
+                self,
+                text: impl Into<String>,
+                f: impl FnOnce(TextBlockBuilder) -> TextBlockBuilder,
+            ) -> Self {
+                self.element(f(TextBlock::builder(text)))
+            }
+
+            /// Appends an [`Image`] configured by `f`.
+            pub fn image(
+                self,
+                url: impl Into<String>,
+                f: impl FnOnce(ImageBuilder) -> ImageBuilder,
+            ) -> Self {
+                self.element(f(Image::builder(url)))
+            }
+
+            /// Appends a [`Container`] configured by `f`.
+            pub fn container(self, f: impl FnOnce(ContainerBuilder) -> ContainerBuilder) -> Self {
+                self.element(f(Container::builder()))
+            }
+
+            /// Appends a [`ColumnSet`] configured by `f`.
+            pub fn column_set(self, f: impl FnOnce(ColumnSetBuilder) -> ColumnSetBuilder) -> Self {
+                self.element(f(ColumnSet::builder()))
+            }
+
+            /// Appends a [`FactSet`] configured by `f`.
+            pub fn fact_set(self, f: impl FnOnce(FactSetBuilder) -> FactSetBuilder) -> Self {
+                self.element(f(FactSet::builder()))
+            }
+
+            /// Appends an [`ImageSet`] configured by `f`.
+            pub fn image_set(self, f: impl FnOnce(ImageSetBuilder) -> ImageSetBuilder) -> Self {
+                self.element(f(ImageSet::builder()))
+            }
+
+            /// Appends a [`RichTextBlock`] configured by `f`.
+            pub fn rich_text_block(
+                self,
+                f: impl FnOnce(RichTextBlockBuilder) -> RichTextBlockBuilder,
+            ) -> Self {
+                self.element(f(RichTextBlock::builder()))
+            }
+
+            /// Appends a [`Table`] configured by `f`.
+            pub fn table(self, f: impl FnOnce(TableBuilder) -> TableBuilder) -> Self {
+                self.element(f(Table::builder()))
+            }
+
+            /// Appends a [`Media`] element configured by `f`.
+            pub fn media(self, f: impl FnOnce(MediaBuilder) -> MediaBuilder) -> Self {
+                self.element(f(Media::builder()))
+            }
+
+            /// Appends an [`ActionSet`] configured by `f`.
+            pub fn action_set(self, f: impl FnOnce(ActionSetBuilder) -> ActionSetBuilder) -> Self {
+                self.element(f(ActionSet::builder()))
+            }
+
+            /// Appends an [`InputText`] configured by `f`.
+            pub fn input_text(
+                self,
+                id: impl Into<String>,
+                f: impl FnOnce(InputTextBuilder) -> InputTextBuilder,
+            ) -> Self {
+                self.element(f(InputText::builder(id)))
+            }
+
+            /// Appends an [`InputNumber`] configured by `f`.
+            pub fn input_number(
+                self,
+                id: impl Into<String>,
+                f: impl FnOnce(InputNumberBuilder) -> InputNumberBuilder,
+            ) -> Self {
+                self.element(f(InputNumber::builder(id)))
+            }
+
+            /// Appends an [`InputDate`] configured by `f`.
+            pub fn input_date(
+                self,
+                id: impl Into<String>,
+                f: impl FnOnce(InputDateBuilder) -> InputDateBuilder,
+            ) -> Self {
+                self.element(f(InputDate::builder(id)))
+            }
+
+            /// Appends an [`InputTime`] configured by `f`.
+            pub fn input_time(
+                self,
+                id: impl Into<String>,
+                f: impl FnOnce(InputTimeBuilder) -> InputTimeBuilder,
+            ) -> Self {
+                self.element(f(InputTime::builder(id)))
+            }
+
+            /// Appends an [`InputToggle`] configured by `f`.
+            pub fn input_toggle(
+                self,
+                id: impl Into<String>,
+                title: impl Into<String>,
+                f: impl FnOnce(InputToggleBuilder) -> InputToggleBuilder,
+            ) -> Self {
+                self.element(f(InputToggle::builder(id, title)))
+            }
+
+            /// Appends an [`InputChoiceSet`] configured by `f`.
+            pub fn input_choice_set(
+                self,
+                id: impl Into<String>,
+                f: impl FnOnce(InputChoiceSetBuilder) -> InputChoiceSetBuilder,
+            ) -> Self {
+                self.element(f(InputChoiceSet::builder(id)))
+            }
+        }
+    };
+}
+
+/// Adds methods that append actions to a builder with a `push_action` method.
+macro_rules! action_methods {
+    ($builder:ident) => {
+        impl $builder {
+            /// Appends an action.
+            pub fn action(mut self, action: impl Into<Action>) -> Self {
+                self.push_action(action.into());
+                self
+            }
+
+            /// Appends an [`OpenUrlAction`] configured by `f`.
+            pub fn open_url(
+                self,
+                url: impl Into<String>,
+                f: impl FnOnce(OpenUrlActionBuilder) -> OpenUrlActionBuilder,
+            ) -> Self {
+                self.action(f(OpenUrlAction::builder(url)))
+            }
+
+            /// Appends a [`SubmitAction`] configured by `f`.
+            pub fn submit(
+                self,
+                f: impl FnOnce(SubmitActionBuilder) -> SubmitActionBuilder,
+            ) -> Self {
+                self.action(f(SubmitAction::builder()))
+            }
+
+            /// Appends a [`ShowCardAction`] that reveals `card`, configured by `f`.
+            pub fn show_card(
+                self,
+                card: impl Into<AdaptiveCard>,
+                f: impl FnOnce(ShowCardActionBuilder) -> ShowCardActionBuilder,
+            ) -> Self {
+                self.action(f(ShowCardAction::builder(card.into())))
+            }
+
+            /// Appends a [`ToggleVisibilityAction`] configured by `f`.
+            pub fn toggle_visibility(
+                self,
+                f: impl FnOnce(ToggleVisibilityActionBuilder) -> ToggleVisibilityActionBuilder,
+            ) -> Self {
+                self.action(f(ToggleVisibilityAction::builder()))
+            }
+
+            /// Appends an [`ExecuteAction`] configured by `f`.
+            pub fn execute(
+                self,
+                f: impl FnOnce(ExecuteActionBuilder) -> ExecuteActionBuilder,
+            ) -> Self {
+                self.action(f(ExecuteAction::builder()))
+            }
+        }
+    };
+}
+
+/// Adds `required`, a shorthand for `is_required(true)`, to input builders.
+macro_rules! required_method {
+    ($($builder:ident),*) => {
+        $(
+            impl $builder {
+                /// Marks the input as required.
+                pub fn required(self) -> Self {
+                    self.is_required(true)
+                }
+            }
+        )*
+    };
+}
+
+builder! {
+    AdaptiveCardBuilder => AdaptiveCard {
+        new();
+        set { schema: String, version: Version }
+        optional {
+            msteams: MsTeams,
+            fallback_text: String,
+            min_height: String,
+            vertical_content_alignment: VerticalContentAlignment,
+            rtl: bool,
+            lang: String,
+            refresh: Refresh,
+            authentication: Authentication,
+            background_image: BackgroundImage,
+        }
+        boxed { select_action: Action }
+    }
+}
+
+impl AdaptiveCardBuilder {
+    fn push_element(&mut self, element: CardElement) {
+        self.inner.body.push(element);
+    }
+
+    fn push_action(&mut self, action: Action) {
+        self.inner.actions.get_or_insert_with(Vec::new).push(action);
+    }
+}
+
+element_methods!(AdaptiveCardBuilder);
+action_methods!(AdaptiveCardBuilder);
+
+builder! {
+    TextBlockBuilder => TextBlock {
+        new(text: String);
+        optional {
+            size: TextSize,
+            weight: TextWeight,
+            wrap: bool,
+            is_subtle: bool,
+            color: Color,
+            horizontal_alignment: HorizontalAlignment,
+            max_lines: u32,
+            font_type: FontType,
+            style: TextBlockStyle,
+            id: String,
+            separator: bool,
+            spacing: Spacing,
+            height: Height,
+            is_visible: bool,
+        }
+    }
+}
+
+builder! {
+    ContainerBuilder => Container {
+        new();
+        optional {
+            style: ContainerStyle,
+            spacing: Spacing,
+            vertical_content_alignment: VerticalContentAlignment,
+            bleed: bool,
+            min_height: String,
+            background_image: BackgroundImage,
+            id: String,
+            separator: bool,
+            height: Height,
+            is_visible: bool,
+        }
+        boxed { select_action: Action }
+    }
+}
+
+impl ContainerBuilder {
+    fn push_element(&mut self, element: CardElement) {
+        self.inner.items.push(element);
+    }
+}
+
+element_methods!(ContainerBuilder);
+
+builder! {
+    ColumnSetBuilder => ColumnSet {
+        new();
+        optional {
+            style: ContainerStyle,
+            bleed: bool,
+            min_height: String,
+            horizontal_alignment: HorizontalAlignment,
+            id: String,
+            separator: bool,
+            spacing: Spacing,
+            height: Height,
+            is_visible: bool,
+        }
+        boxed { select_action: Action }
+    }
+}
+
+impl ColumnSetBuilder {
+    /// Appends a [`Column`] configured by `f`.
+    pub fn column(mut self, f: impl FnOnce(ColumnBuilder) -> ColumnBuilder) -> Self {
+        self.inner.columns.push(f(Column::builder()).build());
+        self
+    }
+}
+
+builder! {
+    ColumnBuilder => Column {
+        new();
+        optional {
+            width: ColumnWidth,
+            background_image: BackgroundImage,
+            style: ContainerStyle,
+            vertical_content_alignment: VerticalContentAlignment,
+            bleed: bool,
+            min_height: String,
+            rtl: bool,
+            id: String,
+            separator: bool,
+            spacing: Spacing,
+            is_visible: bool,
+        }
+        boxed { select_action: Action }
+    }
+}
+
+impl ColumnBuilder {
+    fn push_element(&mut self, element: CardElement) {
+        self.inner.items.push(element);
+    }
+}
+
+element_methods!(ColumnBuilder);
+
+builder! {
+    ImageBuilder => Image {
+        new(url: String);
+        optional {
+            size: ImageSize,
+            alt_text: String,
+            background_color: String,
+            horizontal_alignment: HorizontalAlignment,
+            width: String,
+            id: String,
+            separator: bool,
+            spacing: Spacing,
+            height: Height,
+            is_visible: bool,
+        }
+        boxed { select_action: Action }
+    }
+}
+
+builder! {
+    ImageSetBuilder => ImageSet {
+        new();
+        optional {
+            image_size: ImageSize,
+            id: String,
+            separator: bool,
+            spacing: Spacing,
+            height: Height,
+            is_visible: bool,
+        }
+    }
+}
+
+impl ImageSetBuilder {
+    /// Appends an [`Image`] configured by `f`.
+    pub fn image(
+        mut self,
+        url: impl Into<String>,
+        f: impl FnOnce(ImageBuilder) -> ImageBuilder,
+    ) -> Self {
+        self.inner.images.push(f(Image::builder(url)).build());
+        self
+    }
+}
+
+builder! {
+    MediaBuilder => Media {
+        new();
+        optional {
+            poster: String,
+            alt_text: String,
+            id: String,
+            separator: bool,
+            spacing: Spacing,
+            height: Height,
+            is_visible: bool,
+        }
+    }
+}
+
+impl MediaBuilder {
+    /// Appends a [`MediaSource`] configured by `f`.
+    pub fn source(
+        mut self,
+        url: impl Into<String>,
+        f: impl FnOnce(MediaSourceBuilder) -> MediaSourceBuilder,
+    ) -> Self {
+        self.inner
+            .sources
+            .push(f(MediaSource::builder(url)).build());
+        self
+    }
+
+    /// Appends a [`CaptionSource`].
+    pub fn caption_source(mut self, caption_source: CaptionSource) -> Self {
+        self.inner
+            .caption_sources
+            .get_or_insert_with(Vec::new)
+            .push(caption_source);
+        self
+    }
+}
+
+builder! {
+    MediaSourceBuilder => MediaSource {
+        new(url: String);
+        optional { mime_type: String }
+    }
+}
+
+impl CaptionSource {
+    /// Creates a caption source.
+    pub fn new(
+        mime_type: impl Into<String>,
+        url: impl Into<String>,
+        label: impl Into<String>,
+    ) -> Self {
+        Self {
+            mime_type: mime_type.into(),
+            url: url.into(),
+            label: label.into(),
+        }
+    }
+}
+
+builder! {
+    BackgroundImageDetailsBuilder => BackgroundImageDetails {
+        new(url: String);
+        optional {
+            fill_mode: ImageFillMode,
+            horizontal_alignment: HorizontalAlignment,
+            vertical_alignment: VerticalContentAlignment,
+        }
+    }
+}
+
+builder! {
+    FactSetBuilder => FactSet {
+        new();
+        optional {}
+    }
+}
+
+impl FactSetBuilder {
+    /// Appends a [`Fact`].
+    pub fn fact(mut self, title: impl Into<String>, value: impl Into<String>) -> Self {
+        self.inner.facts.push(Fact::new(title, value));
+        self
+    }
+}
+
+impl Fact {
+    /// Creates a fact.
+    pub fn new(title: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            value: value.into(),
+        }
+    }
+}
+
+builder! {
+    RichTextBlockBuilder => RichTextBlock {
+        new();
+        optional {
+            horizontal_alignment: HorizontalAlignment,
+            id: String,
+            separator: bool,
+            spacing: Spacing,
+            height: Height,
+            is_visible: bool,
+        }
+    }
+}
+
+impl RichTextBlockBuilder {
+    /// Appends an inline.
+    pub fn inline(mut self, inline: impl Into<Inline>) -> Self {
+        self.inner.inlines.push(inline.into());
+        self
+    }
+
+    /// Appends unformatted text.
+    pub fn text(self, text: impl Into<String>) -> Self {
+        self.inline(Inline::Text(text.into()))
+    }
+
+    /// Appends a [`TextRun`] configured by `f`.
+    pub fn text_run(
+        self,
+        text: impl Into<String>,
+        f: impl FnOnce(TextRunBuilder) -> TextRunBuilder,
+    ) -> Self {
+        self.inline(f(TextRun::builder(text)))
+    }
+}
+
+builder! {
+    TextRunBuilder => TextRun {
+        new(text: String);
+        optional {
+            color: Color,
+            font_type: FontType,
+            highlight: bool,
+            is_subtle: bool,
+            italic: bool,
+            size: TextSize,
+            strikethrough: bool,
+            weight: TextWeight,
+        }
+    }
+}
+
+into_variant!(Inline {
+    TextRun(TextRun, TextRunBuilder),
+});
+
+builder! {
+    TableBuilder => Table {
+        new();
+        optional {
+            first_row_as_headers: bool,
+            show_grid_lines: bool,
+            grid_style: ContainerStyle,
+            horizontal_cell_content_alignment: HorizontalAlignment,
+            vertical_cell_content_alignment: VerticalContentAlignment,
+            id: String,
+            separator: bool,
+            spacing: Spacing,
+            height: Height,
+            is_visible: bool,
+        }
+    }
+}
+
+impl TableBuilder {
+    /// Appends a [`TableColumnDefinition`] configured by `f`.
+    pub fn column(
+        mut self,
+        f: impl FnOnce(TableColumnDefinitionBuilder) -> TableColumnDefinitionBuilder,
+    ) -> Self {
+        self.inner
+            .columns
+            .get_or_insert_with(Vec::new)
+            .push(f(TableColumnDefinition::builder()).build());
+        self
+    }
+
+    /// Appends a [`TableRow`] configured by `f`.
+    pub fn row(mut self, f: impl FnOnce(TableRowBuilder) -> TableRowBuilder) -> Self {
+        self.inner
+            .rows
+            .get_or_insert_with(Vec::new)
+            .push(f(TableRow::builder()).build());
+        self
+    }
+}
+
+builder! {
+    TableColumnDefinitionBuilder => TableColumnDefinition {
+        new();
+        optional {
+            width: ColumnWidth,
+            horizontal_cell_content_alignment: HorizontalAlignment,
+            vertical_cell_content_alignment: VerticalContentAlignment,
+        }
+    }
+}
+
+builder! {
+    TableRowBuilder => TableRow {
+        new();
+        optional {
+            style: ContainerStyle,
+            horizontal_cell_content_alignment: HorizontalAlignment,
+            vertical_cell_content_alignment: VerticalContentAlignment,
+        }
+    }
+}
+
+impl TableRowBuilder {
+    /// Appends a [`TableCell`] configured by `f`.
+    pub fn cell(mut self, f: impl FnOnce(TableCellBuilder) -> TableCellBuilder) -> Self {
+        self.inner.cells.push(f(TableCell::builder()).build());
+        self
+    }
+}
+
+builder! {
+    TableCellBuilder => TableCell {
+        new();
+        optional {
+            style: ContainerStyle,
+            vertical_content_alignment: VerticalContentAlignment,
+            bleed: bool,
+            min_height: String,
+            rtl: bool,
+        }
+        boxed { select_action: Action }
+    }
+}
+
+impl TableCellBuilder {
+    fn push_element(&mut self, element: CardElement) {
+        self.inner.items.push(element);
+    }
+}
+
+element_methods!(TableCellBuilder);
+
+builder! {
+    MsTeamsBuilder => MsTeams {
+        new();
+        optional { width: MsTeamsWidth, entities: Vec<Entity> }
+    }
+}
+
+/// Builds a [`Refresh`].
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct RefreshBuilder {
+    inner: Refresh,
+}
+
+impl Refresh {
+    /// Starts building a [`Refresh`] that invokes `action`.
+    pub fn builder(action: impl Into<Action>) -> RefreshBuilder {
+        RefreshBuilder {
+            inner: Refresh {
+                action: action.into(),
+                user_ids: None,
+                expires: None,
+            },
+        }
+    }
+}
+
+impl RefreshBuilder {
+    /// Appends a user to [`Refresh::user_ids`].
+    pub fn user_id(mut self, user_id: impl Into<String>) -> Self {
+        self.inner
+            .user_ids
+            .get_or_insert_with(Vec::new)
+            .push(user_id.into());
+        self
+    }
+
+    /// Sets [`Refresh::expires`].
+    pub fn expires(mut self, expires: impl Into<String>) -> Self {
+        self.inner.expires = Some(expires.into());
+        self
+    }
+
+    /// Returns the finished [`Refresh`].
+    pub fn build(self) -> Refresh {
+        self.inner
+    }
+}
+
+impl From<RefreshBuilder> for Refresh {
+    fn from(builder: RefreshBuilder) -> Self {
+        builder.inner
+    }
+}
+
+builder! {
+    AuthenticationBuilder => Authentication {
+        new();
+        optional {
+            text: String,
+            connection_name: String,
+            token_exchange_resource: TokenExchangeResource,
+        }
+    }
+}
+
+impl AuthenticationBuilder {
+    /// Appends a sign-in button.
+    pub fn button(mut self, button: impl Into<AuthCardButton>) -> Self {
+        self.inner
+            .buttons
+            .get_or_insert_with(Vec::new)
+            .push(button.into());
+        self
+    }
+}
+
+impl TokenExchangeResource {
+    /// Creates a token exchange resource.
+    pub fn new(
+        id: impl Into<String>,
+        uri: impl Into<String>,
+        provider_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            uri: uri.into(),
+            provider_id: provider_id.into(),
+        }
+    }
+}
+
+builder! {
+    AuthCardButtonBuilder => AuthCardButton {
+        new(type_field: String, value: String);
+        optional { title: String, image: String }
+    }
+}
+
+builder! {
+    InputTextBuilder => InputText {
+        new(id: String);
+        optional {
+            is_multiline: bool,
+            max_length: u32,
+            placeholder: String,
+            regex: String,
+            style: TextInputStyle,
+            value: String,
+            label: String,
+            is_required: bool,
+            error_message: String,
+            separator: bool,
+            spacing: Spacing,
+            height: Height,
+            is_visible: bool,
+        }
+    }
+}
+
+builder! {
+    InputNumberBuilder => InputNumber {
+        new(id: String);
+        optional {
+            min: f64,
+            max: f64,
+            placeholder: String,
+            value: f64,
+            label: String,
+            is_required: bool,
+            error_message: String,
+            separator: bool,
+            spacing: Spacing,
+            height: Height,
+            is_visible: bool,
+        }
+    }
+}
+
+builder! {
+    InputDateBuilder => InputDate {
+        new(id: String);
+        optional {
+            min: String,
+            max: String,
+            placeholder: String,
+            value: String,
+            label: String,
+            is_required: bool,
+            error_message: String,
+            separator: bool,
+            spacing: Spacing,
+            height: Height,
+            is_visible: bool,
+        }
+    }
+}
+
+builder! {
+    InputTimeBuilder => InputTime {
+        new(id: String);
+        optional {
+            min: String,
+            max: String,
+            placeholder: String,
+            value: String,
+            label: String,
+            is_required: bool,
+            error_message: String,
+            separator: bool,
+            spacing: Spacing,
+            height: Height,
+            is_visible: bool,
+        }
+    }
+}
+
+builder! {
+    InputToggleBuilder => InputToggle {
+        new(id: String, title: String);
+        optional {
+            value: String,
+            value_on: String,
+            value_off: String,
+            wrap: bool,
+            label: String,
+            is_required: bool,
+            error_message: String,
+            separator: bool,
+            spacing: Spacing,
+            height: Height,
+            is_visible: bool,
+        }
+    }
+}
+
+builder! {
+    InputChoiceSetBuilder => InputChoiceSet {
+        new(id: String);
+        optional {
+            is_multi_select: bool,
+            style: ChoiceInputStyle,
+            value: String,
+            placeholder: String,
+            wrap: bool,
+            label: String,
+            is_required: bool,
+            error_message: String,
+            separator: bool,
+            spacing: Spacing,
+            height: Height,
+            is_visible: bool,
+        }
+    }
+}
+
+impl InputChoiceSetBuilder {
+    /// Appends a choice.
+    pub fn choice(mut self, title: impl Into<String>, value: impl Into<String>) -> Self {
+        self.inner
+            .choices
+            .get_or_insert_with(Vec::new)
+            .push(InputChoice::new(title, value));
+        self
+    }
+}
+
+impl InputChoice {
+    /// Creates a choice.
+    pub fn new(title: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            value: value.into(),
+        }
+    }
+}
+
+required_method!(
+    InputTextBuilder,
+    InputNumberBuilder,
+    InputDateBuilder,
+    InputTimeBuilder,
+    InputToggleBuilder,
+    InputChoiceSetBuilder
+);
+
+builder! {
+    ActionSetBuilder => ActionSet {
+        new();
+        optional {}
+    }
+}
+
+impl ActionSetBuilder {
+    fn push_action(&mut self, action: Action) {
+        self.inner.actions.push(action);
+    }
+}
+
+action_methods!(ActionSetBuilder);
+
+builder! {
+    OpenUrlActionBuilder => OpenUrlAction {
+        new(url: String);
+        optional {
+            title: String,
+            id: String,
+            icon_url: String,
+            style: ActionStyle,
+            tooltip: String,
+            is_enabled: bool,
+            mode: ActionMode,
+        }
+    }
+}
+
+builder! {
+    SubmitActionBuilder => SubmitAction {
+        new();
+        optional {
+            title: String,
+            data: Value,
+            associated_inputs: AssociatedInputs,
+            id: String,
+            icon_url: String,
+            style: ActionStyle,
+            tooltip: String,
+            is_enabled: bool,
+            mode: ActionMode,
+        }
+    }
+}
+
+builder! {
+    ShowCardActionBuilder => ShowCardAction {
+        new(card: Box<AdaptiveCard>);
+        optional {
+            title: String,
+            id: String,
+            icon_url: String,
+            style: ActionStyle,
+            tooltip: String,
+            is_enabled: bool,
+            mode: ActionMode,
+        }
+    }
+}
+
+builder! {
+    ToggleVisibilityActionBuilder => ToggleVisibilityAction {
+        new();
+        optional {
+            title: String,
+            id: String,
+            icon_url: String,
+            style: ActionStyle,
+            tooltip: String,
+            is_enabled: bool,
+            mode: ActionMode,
+        }
+    }
+}
+
+impl ToggleVisibilityActionBuilder {
+    /// Appends the id of an element whose visibility is toggled.
+    pub fn target(mut self, element_id: impl Into<String>) -> Self {
+        self.inner.target_elements.push(element_id.into());
+        self
+    }
+}
+
+builder! {
+    ExecuteActionBuilder => ExecuteAction {
+        new();
+        optional {
+            title: String,
+            verb: String,
+            data: Value,
+            associated_inputs: AssociatedInputs,
+            id: String,
+            icon_url: String,
+            style: ActionStyle,
+            tooltip: String,
+            is_enabled: bool,
+            mode: ActionMode,
+        }
+    }
+}
+
+into_variant!(CardElement {
+    TextBlock(TextBlock, TextBlockBuilder),
+    Container(Container, ContainerBuilder),
+    ColumnSet(ColumnSet, ColumnSetBuilder),
+    Image(Image, ImageBuilder),
+    ActionSet(ActionSet, ActionSetBuilder),
+    FactSet(FactSet, FactSetBuilder),
+    RichTextBlock(RichTextBlock, RichTextBlockBuilder),
+    Table(Table, TableBuilder),
+    Media(Media, MediaBuilder),
+    ImageSet(ImageSet, ImageSetBuilder),
+    InputText(InputText, InputTextBuilder),
+    InputNumber(InputNumber, InputNumberBuilder),
+    InputDate(InputDate, InputDateBuilder),
+    InputTime(InputTime, InputTimeBuilder),
+    InputToggle(InputToggle, InputToggleBuilder),
+    InputChoiceSet(InputChoiceSet, InputChoiceSetBuilder),
+});
+
+into_variant!(Action {
+    OpenUrl(OpenUrlAction, OpenUrlActionBuilder),
+    Submit(SubmitAction, SubmitActionBuilder),
+    ShowCard(ShowCardAction, ShowCardActionBuilder),
+    ToggleVisibility(ToggleVisibilityAction, ToggleVisibilityActionBuilder),
+    Execute(ExecuteAction, ExecuteActionBuilder),
+});
+
+into_variant!(BackgroundImage {
+    Image(BackgroundImageDetails, BackgroundImageDetailsBuilder),
+});
+
+impl From<String> for BackgroundImage {
+    fn from(url: String) -> Self {
+        BackgroundImage::Url(url)
+    }
+}
+
+impl From<&str> for BackgroundImage {
+    fn from(url: &str) -> Self {
+        BackgroundImage::Url(url.to_string())
+    }
+}
+
+impl From<String> for Inline {
+    fn from(text: String) -> Self {
+        Inline::Text(text)
+    }
+}
+
+impl From<&str> for Inline {
+    fn from(text: &str) -> Self {
+        Inline::Text(text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::ColumnSizing;
+    use expect_test::expect;
+    use serde_json::json;
+
+    #[test]
+    fn test_builder_serialization() {
+        let card = AdaptiveCard::builder()
+            .version(Version::V1_5)
+            .fallback_text("Please update your client")
+            .text_block("Expense report", |t| {
+                t.size(TextSize::Large).weight(TextWeight::Bolder)
+            })
+            .column_set(|c| {
+                c.column(|c| c.width(ColumnWidth::auto()).text("Amount"))
+                    .column(|c| c.width(ColumnWidth::stretch()).text("€42"))
+            })
+            .fact_set(|f| f.fact("Submitted by", "Ada"))
+            .input_number("amount", |i| i.label("Amount").min(0).required())
+            .input_choice_set("category", |i| {
+                i.label("Category")
+                    .choice("Travel", "travel")
+                    .choice("Food", "food")
+            })
+            .submit(|a| {
+                a.title("Approve")
+                    .data(serde_json::json!({ "approved": true }))
+            })
+            .show_card(
+                AdaptiveCard::builder().input_text("comment", |i| i.is_multiline(true)),
+                |a| a.title("Comment"),
+            )
+            .select_action(OpenUrlAction::builder("https://example.com"))
+            .build();
+
+        let expected = expect![[r#"
+            {
+              "type": "AdaptiveCard",
+              "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+              "version": "1.5",
+              "body": [
+                {
+                  "type": "TextBlock",
+                  "text": "Expense report",
+                  "size": "large",
+                  "weight": "bolder"
+                },
+                {
+                  "type": "ColumnSet",
+                  "columns": [
+                    {
+                      "type": "Column",
+                      "width": "auto",
+                      "items": [
+                        {
+                          "type": "TextBlock",
+                          "text": "Amount"
+                        }
+                      ]
+                    },
+                    {
+                      "type": "Column",
+                      "width": "stretch",
+                      "items": [
+                        {
+                          "type": "TextBlock",
+                          "text": "€42"
+                        }
+                      ]
+                    }
+                  ]
+                },
+                {
+                  "type": "FactSet",
+                  "facts": [
+                    {
+                      "title": "Submitted by",
+                      "value": "Ada"
+                    }
+                  ]
+                },
+                {
+                  "type": "Input.Number",
+                  "id": "amount",
+                  "min": 0.0,
+                  "label": "Amount",
+                  "isRequired": true
+                },
+                {
+                  "type": "Input.ChoiceSet",
+                  "id": "category",
+                  "choices": [
+                    {
+                      "title": "Travel",
+                      "value": "travel"
+                    },
+                    {
+                      "title": "Food",
+                      "value": "food"
+                    }
+                  ],
+                  "label": "Category"
+                }
+              ],
+              "actions": [
+                {
+                  "type": "Action.Submit",
+                  "title": "Approve",
+                  "data": {
+                    "approved": true
+                  }
+                },
+                {
+                  "type": "Action.ShowCard",
+                  "title": "Comment",
+                  "card": {
+                    "type": "AdaptiveCard",
+                    "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                    "version": "1.2",
+                    "body": [
+                      {
+                        "type": "Input.Text",
+                        "id": "comment",
+                        "isMultiline": true
+                      }
+                    ]
+                  }
+                }
+              ],
+              "selectAction": {
+                "type": "Action.OpenUrl",
+                "url": "https://example.com"
+              },
+              "fallbackText": "Please update your client"
+            }"#]];
+        expected.assert_eq(&serde_json::to_string_pretty(&card).unwrap());
+    }
+
+    #[test]
+    fn test_nested_closures() {
+        let card = AdaptiveCard::builder()
+            .container(|c| {
+                c.id("outer").column_set(|c| {
+                    c.column(|c| {
+                        c.container(|c| c.style(ContainerStyle::Good).text("Deep"))
+                            .table(|t| {
+                                t.row(|r| {
+                                    r.cell(|c| c.column_set(|c| c.column(|c| c.text("Deepest"))))
+                                })
+                            })
+                    })
+                    .column(|c| c.image_set(|i| i.image("https://a.png", |i| i)))
+                })
+            })
+            .build();
+
+        let value = serde_json::to_value(&card).unwrap();
+        let columns = &value["body"][0]["items"][0]["columns"];
+        assert_eq!(columns[0]["items"][0]["style"], "good");
+        assert_eq!(columns[0]["items"][0]["items"][0]["text"], "Deep");
+        assert_eq!(
+            columns[0]["items"][1]["rows"][0]["cells"][0]["items"][0]["columns"][0]["items"][0]["text"],
+            "Deepest"
+        );
+        assert_eq!(columns[1]["items"][0]["images"][0]["url"], "https://a.png");
+    }
+
+    #[test]
+    fn test_column_widths() {
+        let column_set = ColumnSet::builder()
+            .column(|c| c.width(ColumnWidth::auto()))
+            .column(|c| c.width(ColumnWidth::stretch()))
+            .column(|c| c.width(ColumnWidth::pixels(80)))
+            .column(|c| c.width(ColumnWidth::weight(2)))
+            .column(|c| c)
+            .build();
+
+        let value = serde_json::to_value(&column_set).unwrap();
+        let widths: Vec<_> = value["columns"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|column| column["width"].clone())
+            .collect();
+        assert_eq!(
+            widths,
+            [
+                json!("auto"),
+                json!("stretch"),
+                json!("80px"),
+                json!(2),
+                Value::Null
+            ]
+        );
+        let sizing: Vec<_> = column_set
+            .columns
+            .iter()
+            .filter_map(|column| column.width.as_ref().map(ColumnWidth::sizing))
+            .collect();
+        assert_eq!(
+            sizing,
+            [
+                ColumnSizing::Auto,
+                ColumnSizing::Stretch,
+                ColumnSizing::Pixels(80),
+                ColumnSizing::Weight(2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_actions() {
+        let card = AdaptiveCard::builder()
+            .action_set(|a| {
+                a.open_url("https://example.com", |a| {
+                    a.title("Open").style(ActionStyle::Positive)
+                })
+                .toggle_visibility(|a| a.title("Details").target("a").target("b"))
+            })
+            .execute(|a| {
+                a.title("Approve")
+                    .verb("approve")
+                    .associated_inputs(AssociatedInputs::Auto)
+                    .mode(ActionMode::Secondary)
+            })
+            .show_card(
+                AdaptiveCard::builder().submit(|a| a.title("Send").is_enabled(false)),
+                |a| a.title("Reply").tooltip("Write a reply"),
+            )
+            .action(OpenUrlAction::builder("https://docs.example.com").title("Docs"))
+            .build();
+
+        let value = serde_json::to_value(&card).unwrap();
+        assert_eq!(
+            value["body"][0]["actions"],
+            json!([
+                {
+                    "type": "Action.OpenUrl",
+                    "title": "Open",
+                    "url": "https://example.com",
+                    "style": "positive"
+                },
+                {
+                    "type": "Action.ToggleVisibility",
+                    "title": "Details",
+                    "targetElements": ["a", "b"]
+                },
+            ])
+        );
+        assert_eq!(value["actions"][0]["verb"], "approve");
+        assert_eq!(value["actions"][0]["mode"], "secondary");
+        assert_eq!(value["actions"][1]["tooltip"], "Write a reply");
+        assert_eq!(
+            value["actions"][1]["card"]["actions"][0]["isEnabled"],
+            false
+        );
+        assert_eq!(value["actions"][2]["url"], "https://docs.example.com");
+    }
+}
@@ -1,9 +1,14 @@
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
 use crate::actions::{Action, ActionSet};
-use crate::common::{Color, FontType, Height, HorizontalAlignment, VerticalContentAlignment};
+use crate::common::{
+    Color, FontType, Height, HorizontalAlignment, TextBlockStyle, VerticalContentAlignment,
+};
+use crate::teams::Entity;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Version {
     #[serde(rename = "1.0")]
     V1_0,
@@ -21,6 +26,27 @@ pub enum Version {
     V1_6,
 }
 
+impl Version {
+    /// Returns the version as it appears in the card's `version` property, e.g. "1.5".
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Version::V1_0 => "1.0",
+            Version::V1_1 => "1.1",
+            Version::V1_2 => "1.2",
+            Version::V1_3 => "1.3",
+            Version::V1_4 => "1.4",
+            Version::V1_5 => "1.5",
+            Version::V1_6 => "1.6",
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Represents an Adaptive Card, which is a container for card elements and actions.
 /// Adaptive Cards are designed to be rendered in the Microsoft Adaptive Card ecosystem.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,6 +83,15 @@ pub struct AdaptiveCard {
     /// The 2-letter ISO-639-1 language used in the card. Used to localize any date/time functions.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lang: Option<String>,
+    /// Defines how the card can be refreshed by making a request to the target Bot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh: Option<Refresh>,
+    /// Defines authentication information to enable on-behalf-of single sign on or just-in-time OAuth.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authentication: Option<Authentication>,
+    /// Specifies the background image of the card.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_image: Option<BackgroundImage>,
 }
 
 impl Default for AdaptiveCard {
@@ -73,6 +108,9 @@ impl Default for AdaptiveCard {
             vertical_content_alignment: None,
             rtl: None,
             lang: None,
+            refresh: None,
+            authentication: None,
+            background_image: None,
         }
     }
 }
@@ -95,6 +133,12 @@ pub enum CardElement {
     FactSet(FactSet),
     /// A rich text block element that displays formatted text with inline elements.
     RichTextBlock(RichTextBlock),
+    /// A table element that displays data in a tabular form.
+    Table(Table),
+    /// A media element that displays a video or audio clip.
+    Media(Media),
+    /// An image set element that displays a collection of images.
+    ImageSet(ImageSet),
     /// An input element that allows text entry.
     #[serde(rename = "Input.Text")]
     InputText(crate::inputs::InputText),
@@ -115,6 +159,111 @@ pub enum CardElement {
     InputChoiceSet(crate::inputs::InputChoiceSet),
 }
 
+impl CardElement {
+    /// Returns the unique identifier of the element, if it has one.
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            CardElement::TextBlock(e) => e.id.as_deref(),
+            CardElement::Container(e) => e.id.as_deref(),
+            CardElement::ColumnSet(e) => e.id.as_deref(),
+            CardElement::Image(e) => e.id.as_deref(),
+            CardElement::ActionSet(_) => None,
+            CardElement::FactSet(_) => None,
+            CardElement::RichTextBlock(e) => e.id.as_deref(),
+            CardElement::Table(e) => e.id.as_deref(),
+            CardElement::Media(e) => e.id.as_deref(),
+            CardElement::ImageSet(e) => e.id.as_deref(),
+            CardElement::InputText(e) => Some(&e.id),
+            CardElement::InputNumber(e) => Some(&e.id),
+            CardElement::InputDate(e) => Some(&e.id),
+            CardElement::InputTime(e) => Some(&e.id),
+            CardElement::InputToggle(e) => Some(&e.id),
+            CardElement::InputChoiceSet(e) => Some(&e.id),
+        }
+    }
+
+    /// Returns true if the element is an input whose value is collected on submit.
+    pub fn is_input(&self) -> bool {
+        matches!(
+            self,
+            CardElement::InputText(_)
+                | CardElement::InputNumber(_)
+                | CardElement::InputDate(_)
+                | CardElement::InputTime(_)
+                | CardElement::InputToggle(_)
+                | CardElement::InputChoiceSet(_)
+        )
+    }
+
+    /// Returns the spacing between the element and the preceding element, if set.
+    pub fn spacing(&self) -> Option<&Spacing> {
+        match self {
+            CardElement::TextBlock(e) => e.spacing.as_ref(),
+            CardElement::Container(e) => e.spacing.as_ref(),
+            CardElement::ColumnSet(e) => e.spacing.as_ref(),
+            CardElement::Image(e) => e.spacing.as_ref(),
+            CardElement::ActionSet(_) => None,
+            CardElement::FactSet(_) => None,
+            CardElement::RichTextBlock(e) => e.spacing.as_ref(),
+            CardElement::Table(e) => e.spacing.as_ref(),
+            CardElement::Media(e) => e.spacing.as_ref(),
+            CardElement::ImageSet(e) => e.spacing.as_ref(),
+            CardElement::InputText(e) => e.spacing.as_ref(),
+            CardElement::InputNumber(e) => e.spacing.as_ref(),
+            CardElement::InputDate(e) => e.spacing.as_ref(),
+            CardElement::InputTime(e) => e.spacing.as_ref(),
+            CardElement::InputToggle(e) => e.spacing.as_ref(),
+            CardElement::InputChoiceSet(e) => e.spacing.as_ref(),
+        }
+    }
+
+    /// Returns true if a separating line is drawn above the element.
+    pub fn separator(&self) -> bool {
+        let separator = match self {
+            CardElement::TextBlock(e) => e.separator,
+            CardElement::Container(e) => e.separator,
+            CardElement::ColumnSet(e) => e.separator,
+            CardElement::Image(e) => e.separator,
+            CardElement::ActionSet(_) => None,
+            CardElement::FactSet(_) => None,
+            CardElement::RichTextBlock(e) => e.separator,
+            CardElement::Table(e) => e.separator,
+            CardElement::Media(e) => e.separator,
+            CardElement::ImageSet(e) => e.separator,
+            CardElement::InputText(e) => e.separator,
+            CardElement::InputNumber(e) => e.separator,
+            CardElement::InputDate(e) => e.separator,
+            CardElement::InputTime(e) => e.separator,
+            CardElement::InputToggle(e) => e.separator,
+            CardElement::InputChoiceSet(e) => e.separator,
+        };
+        separator == Some(true)
+    }
+
+    /// Returns false if the element is hidden until shown by `Action.ToggleVisibility`.
+    pub fn is_visible(&self) -> bool {
+        let is_visible = match self {
+            CardElement::TextBlock(e) => e.is_visible,
+            CardElement::Container(e) => e.is_visible,
+            CardElement::ColumnSet(e) => e.is_visible,
+            CardElement::Image(e) => e.is_visible,
+            CardElement::ActionSet(_) => None,
+            CardElement::FactSet(_) => None,
+            CardElement::RichTextBlock(e) => e.is_visible,
+            CardElement::Table(e) => e.is_visible,
+            CardElement::Media(e) => e.is_visible,
+            CardElement::ImageSet(e) => e.is_visible,
+            CardElement::InputText(e) => e.is_visible,
+            CardElement::InputNumber(e) => e.is_visible,
+            CardElement::InputDate(e) => e.is_visible,
+            CardElement::InputTime(e) => e.is_visible,
+            CardElement::InputToggle(e) => e.is_visible,
+            CardElement::InputChoiceSet(e) => e.is_visible,
+        };
+        is_visible != Some(false)
+    }
+}
+
 /// Represents a text block element in an Adaptive Card.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -145,6 +294,9 @@ pub struct TextBlock {
     /// Type of font to use for rendering.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub font_type: Option<FontType>,
+    /// The style of the text, e.g. a heading.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<TextBlockStyle>,
     /// A unique identifier associated with the item.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
@@ -186,6 +338,9 @@ pub struct Container {
     /// Specifies the minimum height of the container.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub min_height: Option<String>,
+    /// Specifies the background image of the container.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_image: Option<BackgroundImage>,
     /// A unique identifier associated with the item.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
@@ -208,27 +363,160 @@ pub enum MsTeamsWidth {
     Full,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct MsTeams {
     /// The width of the card in Microsoft Teams (currently only supports "full").
     #[serde(skip_serializing_if = "Option::is_none")]
     pub width: Option<MsTeamsWidth>,
+    /// Entities referenced by the card's text, such as `<at>` mentions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entities: Option<Vec<Entity>>,
 }
 
-// ColumnSet element
+/// Defines how a card can be refreshed by making a request to the target Bot.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "type", rename_all = "camelCase")]
+#[serde(rename_all = "camelCase")]
+pub struct Refresh {
+    /// The action to perform to obtain an updated card. Must be an Action.Execute.
+    pub action: Action,
+    /// A list of user Ids informing the client for which users should the refresh action be
+    /// run automatically.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_ids: Option<Vec<String>>,
+    /// A timestamp in ISO-8601 format after which the card is considered stale.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+}
+
+/// Defines authentication information associated with a card.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Authentication {
+    /// Text that can be displayed to the end user when prompting them to authenticate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// The identifier for registered OAuth connection setting information.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection_name: Option<String>,
+    /// Provides information required to enable on-behalf-of single sign-on user authentication.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_exchange_resource: Option<TokenExchangeResource>,
+    /// Buttons that should be displayed to the user when prompting for authentication.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buttons: Option<Vec<AuthCardButton>>,
+}
+
+/// Defines information required to enable on-behalf-of single sign-on user authentication.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenExchangeResource {
+    /// The unique identified of this token exchange instance.
+    pub id: String,
+    /// An application ID or resource identifier with which to exchange a token on behalf of.
+    pub uri: String,
+    /// An identifier for the identity provider with which to attempt a token exchange.
+    pub provider_id: String,
+}
+
+/// Defines a button as displayed when prompting a user to authenticate.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthCardButton {
+    /// The type of the button, e.g. "signin".
+    #[serde(rename = "type")]
+    pub type_field: String,
+    /// The value associated with the button. The meaning of value depends on the button's type.
+    pub value: String,
+    /// The caption of the button.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// A URL to an image to display alongside the button's caption.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+/// Represents a column set element that divides a region into columns, allowing elements to sit
+/// side-by-side.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
 pub struct ColumnSet {
+    /// The array of columns to divide the region into.
     pub columns: Vec<Column>,
+    /// The style of the column set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<ContainerStyle>,
+    /// An Action that will be invoked when the column set is tapped or selected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub select_action: Option<Box<Action>>,
+    /// Determines whether the element should bleed through its parent's padding.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bleed: Option<bool>,
+    /// Specifies the minimum height of the column set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_height: Option<String>,
+    /// Controls the horizontal alignment of the column set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub horizontal_alignment: Option<HorizontalAlignment>,
+    /// A unique identifier associated with the item.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// When true, draw a separating line at the top of the element.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub separator: Option<bool>,
+    /// Controls the amount of spacing between this element and the preceding element.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spacing: Option<Spacing>,
+    /// Specifies the height of the element.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<Height>,
+    /// If false, this item will be removed from the visual tree.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_visible: Option<bool>,
 }
 
-// Column element
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Represents a column within a ColumnSet element.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub struct Column {
-    pub width: ColumnWidth,
+    /// The width of the column. When omitted the column is sized as if it were "stretch".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<ColumnWidth>,
+    /// The card elements to render inside the column.
     pub items: Vec<CardElement>,
+    /// Specifies the background image of the column.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_image: Option<BackgroundImage>,
+    /// The style of the column.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<ContainerStyle>,
+    /// An Action that will be invoked when the column is tapped or selected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub select_action: Option<Box<Action>>,
+    /// Defines how the content should be aligned vertically within the column.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vertical_content_alignment: Option<VerticalContentAlignment>,
+    /// Determines whether the element should bleed through its parent's padding.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bleed: Option<bool>,
+    /// Specifies the minimum height of the column.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_height: Option<String>,
+    /// When true, content in this column should be presented right to left.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rtl: Option<bool>,
+    /// A unique identifier associated with the item.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// When true, draw a separating line at the top of the element.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub separator: Option<bool>,
+    /// Controls the amount of spacing between this element and the preceding element.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spacing: Option<Spacing>,
+    /// If false, this item will be removed from the visual tree.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_visible: Option<bool>,
 }
 
 /// Represents an image element in an Adaptive Card.
@@ -272,9 +560,127 @@ pub struct Image {
     pub is_visible: Option<bool>,
 }
 
+/// Displays a collection of Images similar to a gallery.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageSet {
+    /// The array of Image elements to show.
+    pub images: Vec<Image>,
+    /// Controls the approximate size of each image.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_size: Option<ImageSize>,
+    /// A unique identifier associated with the item.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// When true, draw a separating line at the top of the element.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub separator: Option<bool>,
+    /// Controls the amount of spacing between this element and the preceding element.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spacing: Option<Spacing>,
+    /// Specifies the height of the element.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<Height>,
+    /// If false, this item will be removed from the visual tree.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_visible: Option<bool>,
+}
+
+/// Displays a media player for audio or video content.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Media {
+    /// Array of media sources to attempt to play.
+    pub sources: Vec<MediaSource>,
+    /// URL of an image to display before playing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub poster: Option<String>,
+    /// Alternate text describing the audio or video.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alt_text: Option<String>,
+    /// Array of captions sources for the media element to provide.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caption_sources: Option<Vec<CaptionSource>>,
+    /// A unique identifier associated with the item.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// When true, draw a separating line at the top of the element.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub separator: Option<bool>,
+    /// Controls the amount of spacing between this element and the preceding element.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spacing: Option<Spacing>,
+    /// Specifies the height of the element.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<Height>,
+    /// If false, this item will be removed from the visual tree.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_visible: Option<bool>,
+}
+
+/// Defines a source for a Media element.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaSource {
+    /// URL to media. Supports data URI in version 1.2+.
+    pub url: String,
+    /// Mime type of associated media (e.g. "video/mp4"). Optional for YouTube, Vimeo and Dailymotion URLs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+/// Defines a source for captions.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptionSource {
+    /// Mime type of associated caption file (e.g. "vtt").
+    pub mime_type: String,
+    /// URL to caption file.
+    pub url: String,
+    /// Label of this caption to show to the user.
+    pub label: String,
+}
+
+/// Specifies a background image, either as a URL or with full positioning details.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BackgroundImage {
+    /// The URL (or data url) of the image, stretched to cover the element.
+    Url(String),
+    /// A background image with explicit fill mode and alignment.
+    Image(BackgroundImageDetails),
+}
+
+/// Specifies a background image with explicit fill mode and alignment.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BackgroundImageDetails {
+    /// The URL (or data url) of the image. Acceptable formats are PNG, JPEG, and GIF.
+    pub url: String,
+    /// Describes how the image should fill the area.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fill_mode: Option<ImageFillMode>,
+    /// Describes how the image should be aligned if it must be cropped or if using repeat fill mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub horizontal_alignment: Option<HorizontalAlignment>,
+    /// Describes how the image should be aligned if it must be cropped or if using repeat fill mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vertical_alignment: Option<VerticalContentAlignment>,
+}
+
+/// Describes how a background image should fill the area.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ImageFillMode {
+    Cover,
+    RepeatHorizontally,
+    RepeatVertically,
+    Repeat,
+}
+
 /// Represents a fact set element in an Adaptive Card.
 /// A FactSet contains a collection of facts, which are key-value pairs that provide additional information.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct FactSet {
     /// The collection of facts in the fact set.
@@ -283,7 +689,7 @@ pub struct FactSet {
 
 /// Represents an individual fact in a FactSet element.
 /// Each fact has a title and a value, which are displayed as a key-value pair.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Fact {
     /// The title of the fact, typically displayed as the key.
@@ -293,7 +699,7 @@ pub struct Fact {
 }
 
 /// Defines an array of inlines, allowing for inline text formatting.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct RichTextBlock {
     /// The array of inline elements.
@@ -363,8 +769,125 @@ pub struct TextRun {
     pub weight: Option<TextWeight>,
 }
 
+impl Default for TextRun {
+    fn default() -> Self {
+        Self {
+            type_field: "TextRun".to_string(),
+            text: String::new(),
+            color: None,
+            font_type: None,
+            highlight: None,
+            is_subtle: None,
+            italic: None,
+            size: None,
+            strikethrough: None,
+            weight: None,
+        }
+    }
+}
+
+/// Provides a way to display data in a tabular form.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Table {
+    /// Defines the number of columns in the table, their sizes, and more.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub columns: Option<Vec<TableColumnDefinition>>,
+    /// Defines the rows of the table.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rows: Option<Vec<TableRow>>,
+    /// Specifies whether the first row of the table should be treated as a header row.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_row_as_headers: Option<bool>,
+    /// Specifies whether grid lines should be displayed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_grid_lines: Option<bool>,
+    /// Defines the style of the grid. This property currently only controls the grid's color.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grid_style: Option<ContainerStyle>,
+    /// Controls how the content of all cells is horizontally aligned by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub horizontal_cell_content_alignment: Option<HorizontalAlignment>,
+    /// Controls how the content of all cells is vertically aligned by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vertical_cell_content_alignment: Option<VerticalContentAlignment>,
+    /// A unique identifier associated with the item.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// When true, draw a separating line at the top of the element.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub separator: Option<bool>,
+    /// Controls the amount of spacing between this element and the preceding element.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spacing: Option<Spacing>,
+    /// Specifies the height of the element.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<Height>,
+    /// If false, this item will be removed from the visual tree.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_visible: Option<bool>,
+}
+
+/// Defines the characteristics of a column in a Table element.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct TableColumnDefinition {
+    /// Specifies the width of the column, either as a relative weight or in pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<ColumnWidth>,
+    /// Controls how the content of all cells in the column is horizontally aligned by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub horizontal_cell_content_alignment: Option<HorizontalAlignment>,
+    /// Controls how the content of all cells in the column is vertically aligned by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vertical_cell_content_alignment: Option<VerticalContentAlignment>,
+}
+
+/// Represents a row of cells within a Table element.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct TableRow {
+    /// The cells in this row.
+    pub cells: Vec<TableCell>,
+    /// Defines the style of the entire row.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<ContainerStyle>,
+    /// Controls how the content of all cells in the row is horizontally aligned by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub horizontal_cell_content_alignment: Option<HorizontalAlignment>,
+    /// Controls how the content of all cells in the row is vertically aligned by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vertical_cell_content_alignment: Option<VerticalContentAlignment>,
+}
+
+/// Represents a cell within a row of a Table element.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub struct TableCell {
+    /// The card elements to render inside the cell.
+    pub items: Vec<CardElement>,
+    /// The style of the cell.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<ContainerStyle>,
+    /// An Action that will be invoked when the cell is tapped or selected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub select_action: Option<Box<Action>>,
+    /// Defines how the content should be aligned vertically within the cell.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vertical_content_alignment: Option<VerticalContentAlignment>,
+    /// Determines whether the cell should bleed through its parent's padding.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bleed: Option<bool>,
+    /// Specifies the minimum height of the cell.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_height: Option<String>,
+    /// When true, content in this cell should be presented right to left.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rtl: Option<bool>,
+}
+
 /// Represents the size of the text in a TextBlock element.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum TextSize {
     Small,
@@ -374,7 +897,7 @@ pub enum TextSize {
     ExtraLarge,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum TextWeight {
     Lighter,
@@ -382,7 +905,7 @@ pub enum TextWeight {
     Bolder,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum ContainerStyle {
     Default,
@@ -393,7 +916,7 @@ pub enum ContainerStyle {
     Accent,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum Spacing {
     None,
@@ -447,6 +970,38 @@ impl ColumnWidth {
             kind: ColumnWidthKind::Relative(w),
         }
     }
+
+    /// Returns how the column is sized, however its width was written.
+    pub fn sizing(&self) -> ColumnSizing {
+        let value = match &self.kind {
+            ColumnWidthKind::Relative(w) => return ColumnSizing::Weight(*w),
+            ColumnWidthKind::Auto(value)
+            | ColumnWidthKind::Stretch(value)
+            | ColumnWidthKind::Pixel(value) => value,
+        };
+        if value == "auto" {
+            ColumnSizing::Auto
+        } else if let Some(px) = value.strip_suffix("px").and_then(|px| px.parse().ok()) {
+            ColumnSizing::Pixels(px)
+        } else if let Ok(weight) = value.parse() {
+            ColumnSizing::Weight(weight)
+        } else {
+            ColumnSizing::Stretch
+        }
+    }
+}
+
+/// How a column is sized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnSizing {
+    /// As narrow as its content.
+    Auto,
+    /// Shares the remaining space equally with other stretched columns.
+    Stretch,
+    /// A fixed width in pixels.
+    Pixels(u32),
+    /// Shares the remaining space in proportion to its weight.
+    Weight(u32),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -461,11 +1016,11 @@ pub enum ImageSize {
 
 #[cfg(test)]
 mod tests {
-    use crate::actions::{Action, OpenUrlAction};
+    use crate::actions::{Action, ExecuteAction, OpenUrlAction};
 
     use super::*;
+    use crate::common::AssociatedInputs;
     use expect_test::expect;
-    use serde_json::{self, Value};
 
     #[test]
     fn test_adaptive_card_serialization() {
@@ -572,6 +1127,7 @@ mod tests {
             version: Version::V1_6,
             msteams: Some(MsTeams {
                 width: Some(MsTeamsWidth::Full),
+                ..Default::default()
             }),
             ..Default::default()
         };
@@ -599,7 +1155,7 @@ mod tests {
             body: vec![CardElement::ColumnSet(ColumnSet {
                 columns: vec![
                     Column {
-                        width: ColumnWidth::auto(),
+                        width: Some(ColumnWidth::auto()),
                         items: vec![CardElement::TextBlock(TextBlock {
                             size: Some(TextSize::Default),
                             weight: Some(TextWeight::Default),
@@ -608,9 +1164,10 @@ mod tests {
                             is_subtle: Some(false),
                             ..Default::default()
                         })],
+                        ..Default::default()
                     },
                     Column {
-                        width: ColumnWidth::stretch(),
+                        width: Some(ColumnWidth::stretch()),
                         items: vec![CardElement::TextBlock(TextBlock {
                             size: Some(TextSize::Default),
                             weight: Some(TextWeight::Default),
@@ -619,9 +1176,10 @@ mod tests {
                             is_subtle: Some(false),
                             ..Default::default()
                         })],
+                        ..Default::default()
                     },
                     Column {
-                        width: ColumnWidth::pixels(200),
+                        width: Some(ColumnWidth::pixels(200)),
                         items: vec![CardElement::TextBlock(TextBlock {
                             size: Some(TextSize::Default),
                             weight: Some(TextWeight::Default),
@@ -630,9 +1188,10 @@ mod tests {
                             is_subtle: Some(false),
                             ..Default::default()
                         })],
+                        ..Default::default()
                     },
                     Column {
-                        width: ColumnWidth::weight(2),
+                        width: Some(ColumnWidth::weight(2)),
                         items: vec![CardElement::TextBlock(TextBlock {
                             size: Some(TextSize::Default),
                             weight: Some(TextWeight::Default),
@@ -641,8 +1200,10 @@ mod tests {
                             is_subtle: Some(false),
                             ..Default::default()
                         })],
+                        ..Default::default()
                     },
                 ],
+                ..Default::default()
             })],
             ..Default::default()
         };
@@ -654,7 +1215,6 @@ mod tests {
               "version": "1.2",
               "body": [
                 {
-                  "type": "ColumnSet",
                   "type": "ColumnSet",
                   "columns": [
                     {
@@ -723,22 +1283,435 @@ mod tests {
         validate_card_against_schema(&card);
     }
 
+    #[test]
+    fn test_table_serialization() {
+        let cell = |text: &str| TableCell {
+            items: vec![CardElement::TextBlock(TextBlock {
+                text: text.to_string(),
+                wrap: Some(true),
+                ..Default::default()
+            })],
+            ..Default::default()
+        };
+
+        let card = AdaptiveCard {
+            version: Version::V1_5,
+            body: vec![CardElement::Table(Table {
+                columns: Some(vec![
+                    TableColumnDefinition {
+                        width: Some(ColumnWidth::weight(1)),
+                        ..Default::default()
+                    },
+                    TableColumnDefinition {
+                        width: Some(ColumnWidth::weight(2)),
+                        horizontal_cell_content_alignment: Some(HorizontalAlignment::Right),
+                        ..Default::default()
+                    },
+                ]),
+                rows: Some(vec![
+                    TableRow {
+                        cells: vec![cell("Service"), cell("Latency")],
+                        style: Some(ContainerStyle::Accent),
+                        ..Default::default()
+                    },
+                    TableRow {
+                        cells: vec![cell("api"), cell("42ms")],
+                        ..Default::default()
+                    },
+                ]),
+                first_row_as_headers: Some(true),
+                show_grid_lines: Some(false),
+                grid_style: Some(ContainerStyle::Accent),
+                ..Default::default()
+            })],
+            ..Default::default()
+        };
+
+        let expected = expect![[r#"
+            {
+              "type": "AdaptiveCard",
+              "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+              "version": "1.5",
+              "body": [
+                {
+                  "type": "Table",
+                  "columns": [
+                    {
+                      "type": "TableColumnDefinition",
+                      "width": 1
+                    },
+                    {
+                      "type": "TableColumnDefinition",
+                      "width": 2,
+                      "horizontalCellContentAlignment": "right"
+                    }
+                  ],
+                  "rows": [
+                    {
+                      "type": "TableRow",
+                      "cells": [
+                        {
+                          "type": "TableCell",
+                          "items": [
+                            {
+                              "type": "TextBlock",
+                              "text": "Service",
+                              "wrap": true
+                            }
+                          ]
+                        },
+                        {
+                          "type": "TableCell",
+                          "items": [
+                            {
+                              "type": "TextBlock",
+                              "text": "Latency",
+                              "wrap": true
+                            }
+                          ]
+                        }
+                      ],
+                      "style": "accent"
+                    },
+                    {
+                      "type": "TableRow",
+                      "cells": [
+                        {
+                          "type": "TableCell",
+                          "items": [
+                            {
+                              "type": "TextBlock",
+                              "text": "api",
+                              "wrap": true
+                            }
+                          ]
+                        },
+                        {
+                          "type": "TableCell",
+                          "items": [
+                            {
+                              "type": "TextBlock",
+                              "text": "42ms",
+                              "wrap": true
+                            }
+                          ]
+                        }
+                      ]
+                    }
+                  ],
+                  "firstRowAsHeaders": true,
+                  "showGridLines": false,
+                  "gridStyle": "accent"
+                }
+              ]
+            }"#]];
+
+        expected.assert_eq(&serde_json::to_string_pretty(&card).unwrap());
+    }
+
+    #[test]
+    fn test_universal_action_serialization() {
+        let card = AdaptiveCard {
+            version: Version::V1_4,
+            body: vec![CardElement::TextBlock(TextBlock {
+                text: "Approval pending".to_string(),
+                ..Default::default()
+            })],
+            actions: Some(vec![Action::Execute(ExecuteAction {
+                title: Some("Approve".to_string()),
+                verb: Some("approve".to_string()),
+                data: Some(serde_json::json!({ "requestId": 7 })),
+                associated_inputs: Some(AssociatedInputs::Auto),
+                ..Default::default()
+            })]),
+            refresh: Some(Refresh {
+                action: Action::Execute(ExecuteAction {
+                    verb: Some("refresh".to_string()),
+                    ..Default::default()
+                }),
+                user_ids: Some(vec!["29:abc".to_string()]),
+                expires: Some("2025-01-01T00:00:00Z".to_string()),
+            }),
+            authentication: Some(Authentication {
+                text: Some("Please sign in".to_string()),
+                connection_name: Some("oauth".to_string()),
+                token_exchange_resource: Some(TokenExchangeResource {
+                    id: "resource".to_string(),
+                    uri: "api://example".to_string(),
+                    provider_id: "aad".to_string(),
+                }),
+                buttons: Some(vec![AuthCardButton {
+                    type_field: "signin".to_string(),
+                    value: "https://example.com/login".to_string(),
+                    title: Some("Sign in".to_string()),
+                    image: None,
+                }]),
+            }),
+            ..Default::default()
+        };
+
+        let expected = expect![[r#"
+            {
+              "type": "AdaptiveCard",
+              "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+              "version": "1.4",
+              "body": [
+                {
+                  "type": "TextBlock",
+                  "text": "Approval pending"
+                }
+              ],
+              "actions": [
+                {
+                  "type": "Action.Execute",
+                  "title": "Approve",
+                  "verb": "approve",
+                  "data": {
+                    "requestId": 7
+                  },
+                  "associatedInputs": "Auto"
+                }
+              ],
+              "refresh": {
+                "action": {
+                  "type": "Action.Execute",
+                  "verb": "refresh"
+                },
+                "userIds": [
+                  "29:abc"
+                ],
+                "expires": "2025-01-01T00:00:00Z"
+              },
+              "authentication": {
+                "text": "Please sign in",
+                "connectionName": "oauth",
+                "tokenExchangeResource": {
+                  "id": "resource",
+                  "uri": "api://example",
+                  "providerId": "aad"
+                },
+                "buttons": [
+                  {
+                    "type": "signin",
+                    "value": "https://example.com/login",
+                    "title": "Sign in"
+                  }
+                ]
+              }
+            }"#]];
+
+        let json = serde_json::to_string_pretty(&card).unwrap();
+        expected.assert_eq(&json);
+
+        let parsed: AdaptiveCard = serde_json::from_str(&json).unwrap();
+        assert!(matches!(parsed.refresh.unwrap().action, Action::Execute(_)));
+    }
+
+    #[test]
+    fn test_media_and_background_image_serialization() {
+        let card = AdaptiveCard {
+            version: Version::V1_2,
+            background_image: Some(BackgroundImage::Url(
+                "https://example.com/background.png".to_string(),
+            )),
+            body: vec![
+                CardElement::Media(Media {
+                    sources: vec![MediaSource {
+                        url: "https://example.com/intro.mp4".to_string(),
+                        mime_type: Some("video/mp4".to_string()),
+                    }],
+                    poster: Some("https://example.com/poster.png".to_string()),
+                    caption_sources: Some(vec![CaptionSource {
+                        mime_type: "vtt".to_string(),
+                        url: "https://example.com/intro.vtt".to_string(),
+                        label: "English".to_string(),
+                    }]),
+                    ..Default::default()
+                }),
+                CardElement::Container(Container {
+                    background_image: Some(BackgroundImage::Image(BackgroundImageDetails {
+                        url: "https://example.com/tile.png".to_string(),
+                        fill_mode: Some(ImageFillMode::RepeatHorizontally),
+                        vertical_alignment: Some(VerticalContentAlignment::Bottom),
+                        ..Default::default()
+                    })),
+                    items: vec![CardElement::ImageSet(ImageSet {
+                        images: vec![
+                            Image {
+                                url: "https://example.com/1.png".to_string(),
+                                ..Default::default()
+                            },
+                            Image {
+                                url: "https://example.com/2.png".to_string(),
+                                ..Default::default()
+                            },
+                        ],
+                        image_size: Some(ImageSize::Small),
+                        ..Default::default()
+                    })],
+                    ..Default::default()
+                }),
+            ],
+            ..Default::default()
+        };
+
+        let expected = expect![[r#"
+            {
+              "type": "AdaptiveCard",
+              "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+              "version": "1.2",
+              "body": [
+                {
+                  "type": "Media",
+                  "sources": [
+                    {
+                      "url": "https://example.com/intro.mp4",
+                      "mimeType": "video/mp4"
+                    }
+                  ],
+                  "poster": "https://example.com/poster.png",
+                  "captionSources": [
+                    {
+                      "mimeType": "vtt",
+                      "url": "https://example.com/intro.vtt",
+                      "label": "English"
+                    }
+                  ]
+                },
+                {
+                  "type": "Container",
+                  "items": [
+                    {
+                      "type": "ImageSet",
+                      "images": [
+                        {
+                          "url": "https://example.com/1.png"
+                        },
+                        {
+                          "url": "https://example.com/2.png"
+                        }
+                      ],
+                      "imageSize": "small"
+                    }
+                  ],
+                  "backgroundImage": {
+                    "url": "https://example.com/tile.png",
+                    "fillMode": "repeatHorizontally",
+                    "verticalAlignment": "bottom"
+                  }
+                }
+              ],
+              "backgroundImage": "https://example.com/background.png"
+            }"#]];
+
+        let json = serde_json::to_string_pretty(&card).unwrap();
+        expected.assert_eq(&json);
+
+        let parsed: AdaptiveCard = serde_json::from_str(&json).unwrap();
+        assert!(matches!(
+            parsed.background_image,
+            Some(BackgroundImage::Url(_))
+        ));
+        match &parsed.body[1] {
+            CardElement::Container(container) => assert!(matches!(
+                container.background_image,
+                Some(BackgroundImage::Image(_))
+            )),
+            other => panic!("expected container, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_column_set_properties_serialization() {
+        use crate::actions::ToggleVisibilityAction;
+
+        let card = AdaptiveCard {
+            body: vec![CardElement::ColumnSet(ColumnSet {
+                columns: vec![
+                    Column {
+                        items: vec![],
+                        style: Some(ContainerStyle::Good),
+                        vertical_content_alignment: Some(VerticalContentAlignment::Center),
+                        select_action: Some(Box::new(Action::ToggleVisibility(
+                            ToggleVisibilityAction {
+                                title: None,
+                                target_elements: vec!["details".to_string()],
+                                id: None,
+                                icon_url: None,
+                                style: None,
+                                tooltip: None,
+                                is_enabled: None,
+                                mode: None,
+                            },
+                        ))),
+                        ..Default::default()
+                    },
+                    Column {
+                        id: Some("details".to_string()),
+                        is_visible: Some(false),
+                        spacing: Some(Spacing::Small),
+                        separator: Some(true),
+                        items: vec![],
+                        ..Default::default()
+                    },
+                ],
+                style: Some(ContainerStyle::Emphasis),
+                bleed: Some(true),
+                min_height: Some("50px".to_string()),
+                horizontal_alignment: Some(HorizontalAlignment::Center),
+                ..Default::default()
+            })],
+            ..Default::default()
+        };
+
+        let expected = expect![[r#"
+            {
+              "type": "AdaptiveCard",
+              "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+              "version": "1.2",
+              "body": [
+                {
+                  "type": "ColumnSet",
+                  "columns": [
+                    {
+                      "type": "Column",
+                      "items": [],
+                      "style": "good",
+                      "selectAction": {
+                        "type": "Action.ToggleVisibility",
+                        "targetElements": [
+                          "details"
+                        ]
+                      },
+                      "verticalContentAlignment": "center"
+                    },
+                    {
+                      "type": "Column",
+                      "items": [],
+                      "id": "details",
+                      "separator": true,
+                      "spacing": "small",
+                      "isVisible": false
+                    }
+                  ],
+                  "style": "emphasis",
+                  "bleed": true,
+                  "minHeight": "50px",
+                  "horizontalAlignment": "center"
+                }
+              ]
+            }"#]];
+
+        expected.assert_eq(&serde_json::to_string_pretty(&card).unwrap());
+    }
+
+    #[cfg(feature = "schema-validation")]
     fn validate_card_against_schema(card: &AdaptiveCard) {
-        use std::io::Read;
-        use std::sync::OnceLock;
-
-        static SCHEMA_CONTENT: OnceLock<Value> = OnceLock::new();
-
-        let schema = SCHEMA_CONTENT.get_or_init(|| {
-            let mut resp =
-                reqwest::blocking::get("http://adaptivecards.io/schemas/adaptive-card.json")
-                    .expect("Failed to fetch schema");
-            let mut content = String::new();
-            resp.read_to_string(&mut content)
-                .expect("Failed to read schema response");
-            serde_json::from_str(&content).expect("Failed to parse schema JSON")
-        });
-        let validator = jsonschema::validator_for(schema).unwrap();
-        assert!(validator.is_valid(&serde_json::to_value(card).unwrap()));
+        if let Err(errors) = card.validate_schema() {
+            panic!("card does not match the schema: {errors:?}");
+        }
     }
+
+    #[cfg(not(feature = "schema-validation"))]
+    fn validate_card_against_schema(_card: &AdaptiveCard) {}
 }
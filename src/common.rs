@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 /// Controls the color of text elements.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum Color {
     Default,
@@ -42,7 +42,7 @@ pub enum Height {
 }
 
 /// Type of font to use for rendering text.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum FontType {
     Default,
@@ -0,0 +1,42 @@
+//! Conversions between Adaptive Cards and other card formats.
+//!
+//! - [`message_card`] converts legacy Office 365 connector cards into Adaptive Cards.
+//! - [`slack`] exports Adaptive Cards as Slack Block Kit messages.
+//!
+//! The formats do not map one-to-one, so each conversion returns a [`Conversion`] that
+//! lists the parts of the input it could not carry over.
+use std::fmt;
+
+pub mod message_card;
+pub mod slack;
+
+/// A part of the input that has no equivalent in the output format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Loss {
+    /// JSON pointer to the part in the input.
+    pub path: String,
+    /// What was dropped, e.g. `themeColor` or `HttpPOST action`.
+    pub feature: String,
+}
+
+impl fmt::Display for Loss {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} was not converted", self.path, self.feature)
+    }
+}
+
+/// The result of a conversion.
+#[derive(Debug, Clone)]
+pub struct Conversion<T> {
+    /// The converted card.
+    pub output: T,
+    /// Every part of the input that was dropped.
+    pub losses: Vec<Loss>,
+}
+
+impl<T> Conversion<T> {
+    /// Returns true if nothing was dropped.
+    pub fn is_lossless(&self) -> bool {
+        self.losses.is_empty()
+    }
+}
@@ -0,0 +1,810 @@
+//! Converts legacy Office 365 connector cards (`MessageCard`) into Adaptive Cards.
+//!
+//! Each section becomes a `Container` holding its activity, hero image, text, facts and
+//! images, and each action becomes the closest Adaptive Card action:
+//!
+//! - `OpenUri` becomes `Action.OpenUrl`, using the `default` target,
+//! - `ActionCard` becomes `Action.ShowCard` holding its inputs and actions.
+//!
+//! `HttpPOST` and `InvokeAddInCommand` need the connector service to run, so they are
+//! dropped, as are `themeColor` and any property the card format does not model. The
+//! converted card declares version 1.3, which has input labels.
+//!
+//! ```
+//! use adaptive_card_rs::convert::message_card::MessageCard;
+//!
+//! let message_card: MessageCard = serde_json::from_str(
+//!     r#"{
+//!         "@type": "MessageCard",
+//!         "summary": "Build failed",
+//!         "themeColor": "d70000",
+//!         "sections": [{
+//!             "activityTitle": "Build #42 failed",
+//!             "facts": [{ "name": "Branch", "value": "main" }]
+//!         }],
+//!         "potentialAction": [{
+//!             "@type": "OpenUri",
+//!             "name": "View build",
+//!             "targets": [{ "os": "default", "uri": "https://ci.example.com/42" }]
+//!         }]
+//!     }"#,
+//! )
+//! .unwrap();
+//!
+//! let conversion = message_card.to_adaptive_card();
+//! assert_eq!(conversion.output.fallback_text.as_deref(), Some("Build failed"));
+//! assert_eq!(conversion.losses.len(), 1);
+//! assert_eq!(conversion.losses[0].to_string(), "/themeColor: themeColor was not converted");
+//! ```
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::actions::{Action, OpenUrlAction, ShowCardAction};
+use crate::card::{
+    self, AdaptiveCard, CardElement, ColumnWidth, Container, ImageSize, TextSize, TextWeight,
+    Version,
+};
+use crate::common::ChoiceInputStyle;
+use crate::convert::{Conversion, Loss};
+use crate::inputs::{InputChoice, InputChoiceSet, InputDate, InputText};
+
+/// An Office 365 connector card.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageCard {
+    /// Always `MessageCard`.
+    #[serde(rename = "@type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// The schema context, e.g. `https://schema.org/extensions`.
+    #[serde(rename = "@context", skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+    /// The text shown in notifications.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// The title of the card.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The main text of the card, in Markdown.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// The hex color of the card's accent bar.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme_color: Option<String>,
+    /// The sections of the card.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sections: Option<Vec<Section>>,
+    /// The actions shown at the bottom of the card.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub potential_action: Option<Vec<PotentialAction>>,
+    /// Any other properties, such as `correlationId` or `hideOriginalBody`.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// A section of a [`MessageCard`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Section {
+    /// The title of the section.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The text of the section, in Markdown.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// The title of the activity, usually who did what.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activity_title: Option<String>,
+    /// The subtitle of the activity, usually when it happened.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activity_subtitle: Option<String>,
+    /// The text of the activity.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activity_text: Option<String>,
+    /// The URL of the activity's avatar.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activity_image: Option<String>,
+    /// A large image shown across the section.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hero_image: Option<Image>,
+    /// Name and value pairs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facts: Option<Vec<Fact>>,
+    /// A gallery of images.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub images: Option<Vec<Image>>,
+    /// The actions shown at the bottom of the section.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub potential_action: Option<Vec<PotentialAction>>,
+    /// Whether a line separates the section from the one before it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_group: Option<bool>,
+}
+
+/// A name and value pair in a [`Section`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Fact {
+    /// The name of the fact.
+    pub name: String,
+    /// The value of the fact, in Markdown.
+    pub value: String,
+}
+
+/// An image in a [`Section`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Image {
+    /// The URL of the image.
+    pub image: String,
+    /// The alternative text of the image.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+/// An action of a [`MessageCard`] or [`Section`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "@type")]
+pub enum PotentialAction {
+    /// Opens a URL, which can differ between operating systems.
+    #[serde(rename_all = "camelCase")]
+    OpenUri {
+        /// The label of the button.
+        name: String,
+        /// The URL to open on each operating system.
+        targets: Vec<OpenUriTarget>,
+    },
+    /// Shows a card with inputs and further actions.
+    #[serde(rename_all = "camelCase")]
+    ActionCard {
+        /// The label of the button.
+        name: String,
+        /// The inputs of the card.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        inputs: Option<Vec<Input>>,
+        /// The actions of the card.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        actions: Option<Vec<PotentialAction>>,
+    },
+    /// Makes the connector service post to a URL.
+    #[serde(rename = "HttpPOST", rename_all = "camelCase")]
+    HttpPost {
+        /// The label of the button.
+        name: String,
+        /// The URL to post to.
+        target: String,
+        /// The body of the request, with `{{input.value}}` placeholders.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        body: Option<String>,
+        /// The content type of the body.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        body_content_type: Option<String>,
+    },
+    /// Opens an Outlook add-in.
+    #[serde(rename_all = "camelCase")]
+    InvokeAddInCommand {
+        /// The label of the button.
+        name: String,
+        /// The id of the add-in.
+        add_in_id: String,
+        /// The id of the add-in command.
+        desktop_command_id: String,
+    },
+    /// An action type this crate does not know.
+    #[serde(other)]
+    Unknown,
+}
+
+/// The URL an `OpenUri` action opens on one operating system.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OpenUriTarget {
+    /// `default`, `iOS`, `android` or `windows`.
+    pub os: String,
+    /// The URL to open.
+    pub uri: String,
+}
+
+/// An input of an `ActionCard`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "@type")]
+pub enum Input {
+    /// A text input.
+    #[serde(rename_all = "camelCase")]
+    TextInput {
+        /// The id of the input.
+        id: String,
+        /// The label of the input.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        /// Whether a value is required.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        is_required: Option<bool>,
+        /// The initial value.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        value: Option<String>,
+        /// Whether the input spans several lines.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        is_multiline: Option<bool>,
+        /// The maximum number of characters.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max_length: Option<u32>,
+    },
+    /// A date input.
+    #[serde(rename_all = "camelCase")]
+    DateInput {
+        /// The id of the input.
+        id: String,
+        /// The label of the input.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        /// Whether a value is required.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        is_required: Option<bool>,
+        /// The initial value.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        value: Option<String>,
+        /// Whether a time is picked as well.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        include_time: Option<bool>,
+    },
+    /// A choice between several values.
+    #[serde(rename_all = "camelCase")]
+    MultichoiceInput {
+        /// The id of the input.
+        id: String,
+        /// The label of the input.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        /// Whether a value is required.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        is_required: Option<bool>,
+        /// The initial value.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        value: Option<String>,
+        /// The choices.
+        choices: Vec<Choice>,
+        /// Whether several choices can be selected.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        is_multi_select: Option<bool>,
+        /// `normal` for a dropdown or `expanded` for radio buttons or checkboxes.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        style: Option<String>,
+    },
+    /// An input type this crate does not know.
+    #[serde(other)]
+    Unknown,
+}
+
+/// A choice of a `MultichoiceInput`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Choice {
+    /// The text shown for the choice.
+    pub display: String,
+    /// The value submitted for the choice.
+    pub value: String,
+}
+
+impl MessageCard {
+    /// Converts the card into an equivalent Adaptive Card.
+    pub fn to_adaptive_card(&self) -> Conversion<AdaptiveCard> {
+        let mut converter = Converter { losses: Vec::new() };
+        let output = converter.card(self);
+        Conversion {
+            output,
+            losses: converter.losses,
+        }
+    }
+}
+
+struct Converter {
+    losses: Vec<Loss>,
+}
+
+impl Converter {
+    fn lose(&mut self, path: String, feature: impl Into<String>) {
+        self.losses.push(Loss {
+            path,
+            feature: feature.into(),
+        });
+    }
+
+    fn card(&mut self, message_card: &MessageCard) -> AdaptiveCard {
+        let mut card = AdaptiveCard::builder().version(Version::V1_3);
+        if let Some(summary) = &message_card.summary {
+            card = card.fallback_text(summary);
+        }
+        if let Some(title) = &message_card.title {
+            card = card.text_block(title, |t| {
+                t.size(TextSize::Medium)
+                    .weight(TextWeight::Bolder)
+                    .wrap(true)
+            });
+        }
+        if let Some(text) = &message_card.text {
+            card = card.text_block(text, |t| t.wrap(true));
+        }
+        if message_card.theme_color.is_some() {
+            self.lose("/themeColor".to_string(), "themeColor");
+        }
+        for (i, section) in message_card.sections.iter().flatten().enumerate() {
+            card = card.element(self.section(section, &format!("/sections/{i}")));
+        }
+        let actions = self.actions(&message_card.potential_action, "/potentialAction");
+        for action in actions {
+            card = card.action(action);
+        }
+        for key in message_card.extra.keys() {
+            self.lose(format!("/{key}"), key);
+        }
+        card.build()
+    }
+
+    fn section(&mut self, section: &Section, path: &str) -> Container {
+        let mut container = Container::builder();
+        if section.start_group == Some(true) {
+            container = container.separator(true);
+        }
+        if let Some(title) = &section.title {
+            container = container.text_block(title, |t| t.weight(TextWeight::Bolder).wrap(true));
+        }
+        if section.activity_title.is_some()
+            || section.activity_subtitle.is_some()
+            || section.activity_text.is_some()
+            || section.activity_image.is_some()
+        {
+            container = container.column_set(|mut columns| {
+                if let Some(url) = &section.activity_image {
+                    columns = columns.column(|column| {
+                        column
+                            .width(ColumnWidth::auto())
+                            .image(url, |i| i.size(ImageSize::Small))
+                    });
+                }
+                columns.column(|mut column| {
+                    column = column.width(ColumnWidth::stretch());
+                    if let Some(title) = &section.activity_title {
+                        column =
+                            column.text_block(title, |t| t.weight(TextWeight::Bolder).wrap(true));
+                    }
+                    if let Some(subtitle) = &section.activity_subtitle {
+                        column = column.text_block(subtitle, |t| {
+                            t.is_subtle(true).spacing(card::Spacing::None).wrap(true)
+                        });
+                    }
+                    if let Some(text) = &section.activity_text {
+                        column = column.text_block(text, |t| t.wrap(true));
+                    }
+                    column
+                })
+            });
+        }
+        if let Some(hero) = &section.hero_image {
+            container = container.element(image(hero, Some(ImageSize::Stretch)));
+        }
+        if let Some(text) = &section.text {
+            container = container.text_block(text, |t| t.wrap(true));
+        }
+        if let Some(facts) = &section.facts {
+            container = container.fact_set(|set| {
+                facts
+                    .iter()
+                    .fold(set, |set, fact| set.fact(&fact.name, &fact.value))
+            });
+        }
+        if let Some(images) = &section.images {
+            container = container.element(card::ImageSet {
+                images: images.iter().map(|i| image(i, None)).collect(),
+                ..Default::default()
+            });
+        }
+        let actions = self.actions(
+            &section.potential_action,
+            &format!("{path}/potentialAction"),
+        );
+        if !actions.is_empty() {
+            container =
+                container.action_set(|set| actions.into_iter().fold(set, |set, a| set.action(a)));
+        }
+        container.build()
+    }
+
+    fn actions(&mut self, actions: &Option<Vec<PotentialAction>>, path: &str) -> Vec<Action> {
+        actions
+            .iter()
+            .flatten()
+            .enumerate()
+            .filter_map(|(i, action)| self.action(action, &format!("{path}/{i}")))
+            .collect()
+    }
+
+    fn action(&mut self, action: &PotentialAction, path: &str) -> Option<Action> {
+        match action {
+            PotentialAction::OpenUri { name, targets } => {
+                let target = targets
+                    .iter()
+                    .find(|target| target.os == "default")
+                    .or_else(|| targets.first());
+                match target {
+                    Some(target) => Some(OpenUrlAction::builder(&target.uri).title(name).into()),
+                    None => {
+                        self.lose(path.to_string(), "OpenUri action without targets");
+                        None
+                    }
+                }
+            }
+            PotentialAction::ActionCard {
+                name,
+                inputs,
+                actions,
+            } => {
+                let mut card = AdaptiveCard::builder().version(Version::V1_3);
+                for (i, input) in inputs.iter().flatten().enumerate() {
+                    if let Some(element) = self.input(input, &format!("{path}/inputs/{i}")) {
+                        card = card.element(element);
+                    }
+                }
+                for action in self.actions(actions, &format!("{path}/actions")) {
+                    card = card.action(action);
+                }
+                Some(ShowCardAction::builder(card.build()).title(name).into())
+            }
+            PotentialAction::HttpPost { .. } => {
+                self.lose(path.to_string(), "HttpPOST action");
+                None
+            }
+            PotentialAction::InvokeAddInCommand { .. } => {
+                self.lose(path.to_string(), "InvokeAddInCommand action");
+                None
+            }
+            PotentialAction::Unknown => {
+                self.lose(path.to_string(), "unknown action");
+                None
+            }
+        }
+    }
+
+    fn input(&mut self, input: &Input, path: &str) -> Option<CardElement> {
+        let element = match input {
+            Input::TextInput {
+                id,
+                title,
+                is_required,
+                value,
+                is_multiline,
+                max_length,
+            } => CardElement::InputText(InputText {
+                id: id.clone(),
+                label: title.clone(),
+                is_required: *is_required,
+                value: value.clone(),
+                is_multiline: *is_multiline,
+                max_length: *max_length,
+                ..Default::default()
+            }),
+            Input::DateInput {
+                id,
+                title,
+                is_required,
+                value,
+                include_time,
+            } => {
+                if *include_time == Some(true) {
+                    self.lose(format!("{path}/includeTime"), "DateInput.includeTime");
+                }
+                CardElement::InputDate(InputDate {
+                    id: id.clone(),
+                    label: title.clone(),
+                    is_required: *is_required,
+                    value: value.clone(),
+                    ..Default::default()
+                })
+            }
+            Input::MultichoiceInput {
+                id,
+                title,
+                is_required,
+                value,
+                choices,
+                is_multi_select,
+                style,
+            } => CardElement::InputChoiceSet(InputChoiceSet {
+                id: id.clone(),
+                label: title.clone(),
+                is_required: *is_required,
+                value: value.clone(),
+                choices: Some(
+                    choices
+                        .iter()
+                        .map(|choice| InputChoice::new(&choice.display, &choice.value))
+                        .collect(),
+                ),
+                is_multi_select: *is_multi_select,
+                style: (style.as_deref() == Some("expanded")).then_some(ChoiceInputStyle::Expanded),
+                ..Default::default()
+            }),
+            Input::Unknown => {
+                self.lose(path.to_string(), "unknown input");
+                return None;
+            }
+        };
+        Some(element)
+    }
+}
+
+fn image(image: &Image, size: Option<ImageSize>) -> card::Image {
+    card::Image {
+        url: image.image.clone(),
+        alt_text: image.title.clone(),
+        size,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expect_test::expect;
+    use serde_json::json;
+
+    fn convert(value: Value) -> Conversion<AdaptiveCard> {
+        serde_json::from_value::<MessageCard>(value)
+            .unwrap()
+            .to_adaptive_card()
+    }
+
+    #[test]
+    fn test_sections() {
+        let conversion = convert(json!({
+            "@type": "MessageCard",
+            "@context": "https://schema.org/extensions",
+            "summary": "Ada commented",
+            "title": "New comment",
+            "sections": [{
+                "activityTitle": "Ada Lovelace",
+                "activitySubtitle": "9:30 AM",
+                "activityImage": "https://example.com/ada.png",
+                "text": "Looks good",
+                "facts": [{ "name": "Issue", "value": "#12" }],
+                "images": [{ "image": "https://example.com/a.png", "title": "Diagram" }]
+            }, {
+                "startGroup": true,
+                "heroImage": { "image": "https://example.com/hero.png" },
+                "potentialAction": [{
+                    "@type": "OpenUri",
+                    "name": "Open",
+                    "targets": [
+                        { "os": "iOS", "uri": "app://issue/12" },
+                        { "os": "default", "uri": "https://example.com/12" }
+                    ]
+                }]
+            }]
+        }));
+        assert!(conversion.is_lossless());
+        expect![[r##"
+            {
+              "type": "AdaptiveCard",
+              "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+              "version": "1.3",
+              "body": [
+                {
+                  "type": "TextBlock",
+                  "text": "New comment",
+                  "size": "medium",
+                  "weight": "bolder",
+                  "wrap": true
+                },
+                {
+                  "type": "Container",
+                  "items": [
+                    {
+                      "type": "ColumnSet",
+                      "columns": [
+                        {
+                          "type": "Column",
+                          "width": "auto",
+                          "items": [
+                            {
+                              "type": "Image",
+                              "url": "https://example.com/ada.png",
+                              "size": "small"
+                            }
+                          ]
+                        },
+                        {
+                          "type": "Column",
+                          "width": "stretch",
+                          "items": [
+                            {
+                              "type": "TextBlock",
+                              "text": "Ada Lovelace",
+                              "weight": "bolder",
+                              "wrap": true
+                            },
+                            {
+                              "type": "TextBlock",
+                              "text": "9:30 AM",
+                              "wrap": true,
+                              "isSubtle": true,
+                              "spacing": "none"
+                            }
+                          ]
+                        }
+                      ]
+                    },
+                    {
+                      "type": "TextBlock",
+                      "text": "Looks good",
+                      "wrap": true
+                    },
+                    {
+                      "type": "FactSet",
+                      "facts": [
+                        {
+                          "title": "Issue",
+                          "value": "#12"
+                        }
+                      ]
+                    },
+                    {
+                      "type": "ImageSet",
+                      "images": [
+                        {
+                          "url": "https://example.com/a.png",
+                          "altText": "Diagram"
+                        }
+                      ]
+                    }
+                  ]
+                },
+                {
+                  "type": "Container",
+                  "items": [
+                    {
+                      "type": "Image",
+                      "url": "https://example.com/hero.png",
+                      "size": "stretch"
+                    },
+                    {
+                      "type": "ActionSet",
+                      "actions": [
+                        {
+                          "type": "Action.OpenUrl",
+                          "title": "Open",
+                          "url": "https://example.com/12"
+                        }
+                      ]
+                    }
+                  ],
+                  "separator": true
+                }
+              ],
+              "fallbackText": "Ada commented"
+            }"##]]
+        .assert_eq(&serde_json::to_string_pretty(&conversion.output).unwrap());
+    }
+
+    #[test]
+    fn test_action_card() {
+        let conversion = convert(json!({
+            "text": "Leave a comment",
+            "potentialAction": [{
+                "@type": "ActionCard",
+                "name": "Comment",
+                "inputs": [
+                    { "@type": "TextInput", "id": "comment", "title": "Comment", "isMultiline": true },
+                    { "@type": "DateInput", "id": "due", "title": "Due", "includeTime": true },
+                    {
+                        "@type": "MultichoiceInput",
+                        "id": "priority",
+                        "title": "Priority",
+                        "style": "expanded",
+                        "choices": [
+                            { "display": "High", "value": "1" },
+                            { "display": "Low", "value": "2" }
+                        ]
+                    }
+                ],
+                "actions": [{
+                    "@type": "HttpPOST",
+                    "name": "Save",
+                    "target": "https://example.com/comment"
+                }]
+            }]
+        }));
+        expect![[r#"
+            {
+              "type": "AdaptiveCard",
+              "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+              "version": "1.3",
+              "body": [
+                {
+                  "type": "TextBlock",
+                  "text": "Leave a comment",
+                  "wrap": true
+                }
+              ],
+              "actions": [
+                {
+                  "type": "Action.ShowCard",
+                  "title": "Comment",
+                  "card": {
+                    "type": "AdaptiveCard",
+                    "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                    "version": "1.3",
+                    "body": [
+                      {
+                        "type": "Input.Text",
+                        "id": "comment",
+                        "isMultiline": true,
+                        "label": "Comment"
+                      },
+                      {
+                        "type": "Input.Date",
+                        "id": "due",
+                        "label": "Due"
+                      },
+                      {
+                        "type": "Input.ChoiceSet",
+                        "id": "priority",
+                        "choices": [
+                          {
+                            "title": "High",
+                            "value": "1"
+                          },
+                          {
+                            "title": "Low",
+                            "value": "2"
+                          }
+                        ],
+                        "style": "expanded",
+                        "label": "Priority"
+                      }
+                    ]
+                  }
+                }
+              ]
+            }"#]]
+        .assert_eq(&serde_json::to_string_pretty(&conversion.output).unwrap());
+        expect![[r#"
+            /potentialAction/0/inputs/1/includeTime: DateInput.includeTime was not converted
+            /potentialAction/0/actions/0: HttpPOST action was not converted
+        "#]]
+        .assert_eq(
+            &conversion
+                .losses
+                .iter()
+                .map(|loss| format!("{loss}\n"))
+                .collect::<String>(),
+        );
+    }
+
+    #[test]
+    fn test_unconvertible() {
+        let conversion = convert(json!({
+            "themeColor": "0076D7",
+            "hideOriginalBody": true,
+            "sections": [{
+                "potentialAction": [
+                    { "@type": "InvokeAddInCommand", "name": "Open", "addInId": "a", "desktopCommandId": "b" },
+                    { "@type": "Hologram", "name": "Beam" }
+                ]
+            }]
+        }));
+        assert!(!conversion.is_lossless());
+        let losses: Vec<_> = conversion
+            .losses
+            .iter()
+            .map(|loss| (loss.path.as_str(), loss.feature.as_str()))
+            .collect();
+        assert_eq!(
+            losses,
+            [
+                ("/themeColor", "themeColor"),
+                ("/sections/0/potentialAction/0", "InvokeAddInCommand action"),
+                ("/sections/0/potentialAction/1", "unknown action"),
+                ("/hideOriginalBody", "hideOriginalBody"),
+            ]
+        );
+        let CardElement::Container(section) = &conversion.output.body[0] else {
+            panic!("expected a container");
+        };
+        assert!(section.items.is_empty());
+    }
+}
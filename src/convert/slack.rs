@@ -0,0 +1,1286 @@
+//! Exports Adaptive Cards as Slack Block Kit messages.
+//!
+//! Block Kit has no layout containers, so the content is flattened into a list of blocks:
+//!
+//! - headings (`TextBlockStyle::Heading` or large text) become `header` blocks and other
+//!   text becomes `section` blocks, with the Markdown rewritten as Slack `mrkdwn`,
+//! - fact sets, column sets and table rows become `section` fields,
+//! - images become `image` blocks, or the accessory of the section beside them in a column,
+//! - inputs become `input` blocks whose `block_id` and `action_id` are the input id,
+//! - `Action.OpenUrl`, `Action.Submit` and `Action.Execute` become buttons; the button of
+//!   an `Action.Execute` has the verb as its `action_id`.
+//!
+//! Hidden elements, media, `Action.ShowCard`, `Action.ToggleVisibility` and select actions
+//! cannot be expressed and are reported as losses.
+//!
+//! Slack rejects messages over its size limits, so text too long for one section is split
+//! over several, overlong fields and button labels and values are truncated and blocks past
+//! the 50th are dropped. Each is reported as a loss.
+//!
+//! ```
+//! use adaptive_card_rs::card::AdaptiveCard;
+//!
+//! let card = AdaptiveCard::builder()
+//!     .text("**Disk usage** is at 92%")
+//!     .fact_set(|f| f.fact("Host", "db-1").fact("Region", "eu-west-1"))
+//!     .open_url("https://grafana.example.com", |a| a.title("Dashboard"))
+//!     .build();
+//!
+//! let conversion = card.to_slack_message();
+//! assert!(conversion.is_lossless());
+//! let message = serde_json::to_value(&conversion.output).unwrap();
+//! assert_eq!(message["blocks"][0]["text"]["text"], "*Disk usage* is at 92%");
+//! assert_eq!(message["blocks"][1]["fields"][0]["text"], "*Host*\ndb-1");
+//! assert_eq!(message["blocks"][2]["elements"][0]["url"], "https://grafana.example.com");
+//! ```
+use serde::{Deserialize, Serialize};
+
+use crate::actions::Action;
+use crate::card::{AdaptiveCard, CardElement, Inline, TextSize, TextWeight};
+use crate::common::{ActionStyle, ChoiceInputStyle, TextBlockStyle};
+use crate::convert::{Conversion, Loss};
+use crate::inputs::InputChoice;
+use crate::render::markdown::{self, Span};
+use crate::render::text::{self, Format};
+
+/// The most fields Slack shows in one section.
+const MAX_FIELDS: usize = 10;
+/// The most elements Slack shows in one actions block.
+const MAX_ACTIONS: usize = 25;
+/// The longest text Slack accepts in a header.
+const MAX_HEADER_CHARS: usize = 150;
+/// The most blocks Slack accepts in one message.
+const MAX_BLOCKS: usize = 50;
+/// The longest text Slack accepts in a section.
+const MAX_TEXT_CHARS: usize = 3000;
+/// The longest text Slack accepts in a section field.
+const MAX_FIELD_CHARS: usize = 2000;
+/// The longest label Slack accepts on a button.
+const MAX_BUTTON_TEXT_CHARS: usize = 75;
+/// The longest value Slack accepts on a button.
+const MAX_BUTTON_VALUE_CHARS: usize = 2000;
+
+/// A Slack message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Message {
+    /// The text shown in notifications.
+    pub text: String,
+    /// The content of the message.
+    pub blocks: Vec<Block>,
+}
+
+/// A Block Kit layout block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Block {
+    /// Large, bold plain text.
+    Header {
+        /// The text of the header.
+        text: Text,
+        /// Identifies the block in interaction payloads.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        block_id: Option<String>,
+    },
+    /// Text, fields shown in two columns, or both.
+    Section {
+        /// The text of the section.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        text: Option<Text>,
+        /// Short texts shown in two columns.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        fields: Option<Vec<Text>>,
+        /// An element shown beside the text.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        accessory: Option<Element>,
+        /// Identifies the block in interaction payloads.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        block_id: Option<String>,
+    },
+    /// A horizontal line.
+    Divider {
+        /// Identifies the block in interaction payloads.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        block_id: Option<String>,
+    },
+    /// An image.
+    Image {
+        /// The URL of the image.
+        image_url: String,
+        /// The alternative text of the image.
+        alt_text: String,
+        /// Identifies the block in interaction payloads.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        block_id: Option<String>,
+    },
+    /// A row of interactive elements.
+    Actions {
+        /// The buttons and other elements.
+        elements: Vec<Element>,
+        /// Identifies the block in interaction payloads.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        block_id: Option<String>,
+    },
+    /// A labelled input.
+    Input {
+        /// The label of the input.
+        label: Text,
+        /// The input element.
+        element: Element,
+        /// Whether the input may be left empty.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        optional: Option<bool>,
+        /// Identifies the block in interaction payloads.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        block_id: Option<String>,
+    },
+}
+
+/// A Block Kit text object.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Text {
+    /// Text shown as is.
+    PlainText {
+        /// The text.
+        text: String,
+    },
+    /// Text formatted with Slack's `mrkdwn`.
+    Mrkdwn {
+        /// The text.
+        text: String,
+    },
+}
+
+impl Text {
+    /// Creates a `plain_text` object.
+    pub fn plain(text: impl Into<String>) -> Self {
+        Text::PlainText { text: text.into() }
+    }
+
+    /// Creates a `mrkdwn` object.
+    pub fn mrkdwn(text: impl Into<String>) -> Self {
+        Text::Mrkdwn { text: text.into() }
+    }
+
+    /// Returns the text.
+    pub fn text(&self) -> &str {
+        match self {
+            Text::PlainText { text } | Text::Mrkdwn { text } => text,
+        }
+    }
+}
+
+/// A Block Kit block element.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Element {
+    /// A button that opens a URL or sends an interaction payload.
+    Button {
+        /// The label of the button.
+        text: Text,
+        /// Identifies the button in interaction payloads.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        action_id: Option<String>,
+        /// The URL opened by the button.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        url: Option<String>,
+        /// The value sent in interaction payloads.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        value: Option<String>,
+        /// The color of the button.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        style: Option<ButtonStyle>,
+    },
+    /// A small image.
+    Image {
+        /// The URL of the image.
+        image_url: String,
+        /// The alternative text of the image.
+        alt_text: String,
+    },
+    /// A text input.
+    PlainTextInput {
+        /// Identifies the input in interaction payloads.
+        action_id: String,
+        /// The initial value.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        initial_value: Option<String>,
+        /// Whether the input spans several lines.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        multiline: Option<bool>,
+        /// The maximum number of characters.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max_length: Option<u32>,
+        /// The text shown when the input is empty.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        placeholder: Option<Text>,
+    },
+    /// A number input.
+    NumberInput {
+        /// Identifies the input in interaction payloads.
+        action_id: String,
+        /// Whether the number may have a fractional part.
+        is_decimal_allowed: bool,
+        /// The initial value.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        initial_value: Option<String>,
+        /// The smallest value allowed.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        min_value: Option<String>,
+        /// The largest value allowed.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max_value: Option<String>,
+        /// The text shown when the input is empty.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        placeholder: Option<Text>,
+    },
+    /// A date picker.
+    Datepicker {
+        /// Identifies the input in interaction payloads.
+        action_id: String,
+        /// The initial date, as `YYYY-MM-DD`.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        initial_date: Option<String>,
+        /// The text shown when no date is picked.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        placeholder: Option<Text>,
+    },
+    /// A time picker.
+    Timepicker {
+        /// Identifies the input in interaction payloads.
+        action_id: String,
+        /// The initial time, as `HH:mm`.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        initial_time: Option<String>,
+        /// The text shown when no time is picked.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        placeholder: Option<Text>,
+    },
+    /// A dropdown with a single selection.
+    StaticSelect {
+        /// Identifies the input in interaction payloads.
+        action_id: String,
+        /// The options.
+        options: Vec<SelectOption>,
+        /// The initially selected option.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        initial_option: Option<SelectOption>,
+        /// The text shown when nothing is selected.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        placeholder: Option<Text>,
+    },
+    /// A dropdown with several selections.
+    MultiStaticSelect {
+        /// Identifies the input in interaction payloads.
+        action_id: String,
+        /// The options.
+        options: Vec<SelectOption>,
+        /// The initially selected options.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        initial_options: Option<Vec<SelectOption>>,
+        /// The text shown when nothing is selected.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        placeholder: Option<Text>,
+    },
+    /// Radio buttons.
+    RadioButtons {
+        /// Identifies the input in interaction payloads.
+        action_id: String,
+        /// The options.
+        options: Vec<SelectOption>,
+        /// The initially selected option.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        initial_option: Option<SelectOption>,
+    },
+    /// Checkboxes.
+    Checkboxes {
+        /// Identifies the input in interaction payloads.
+        action_id: String,
+        /// The options.
+        options: Vec<SelectOption>,
+        /// The initially checked options.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        initial_options: Option<Vec<SelectOption>>,
+    },
+}
+
+/// The color of a button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ButtonStyle {
+    /// Green, for the main action.
+    Primary,
+    /// Red, for destructive actions.
+    Danger,
+}
+
+/// An option of a select, radio buttons or checkboxes element.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelectOption {
+    /// The text shown for the option.
+    pub text: Text,
+    /// The value submitted for the option.
+    pub value: String,
+}
+
+impl From<&InputChoice> for SelectOption {
+    fn from(choice: &InputChoice) -> Self {
+        Self {
+            text: Text::plain(&choice.title),
+            value: choice.value.clone(),
+        }
+    }
+}
+
+impl AdaptiveCard {
+    /// Exports the card as a Slack Block Kit message.
+    ///
+    /// The message's notification text is the card's `fallbackText`, or the card rendered
+    /// as plain text.
+    pub fn to_slack_message(&self) -> Conversion<Message> {
+        let mut exporter = Exporter {
+            blocks: Vec::new(),
+            losses: Vec::new(),
+        };
+        // The first block exported from each top-level element and the card's actions.
+        let mut origins = Vec::new();
+        for (i, element) in self.body.iter().enumerate() {
+            let path = format!("/body/{i}");
+            origins.push((exporter.blocks.len(), path.clone()));
+            exporter.item(element, &path);
+        }
+        origins.push((exporter.blocks.len(), "/actions".to_string()));
+        exporter.actions(self.actions.iter().flatten(), "/actions");
+        if self.select_action.is_some() {
+            exporter.lose("/selectAction", "selectAction");
+        }
+        if exporter.blocks.len() > MAX_BLOCKS {
+            for (k, (start, path)) in origins.iter().enumerate() {
+                let end = origins
+                    .get(k + 1)
+                    .map_or(exporter.blocks.len(), |(end, _)| *end);
+                if end > MAX_BLOCKS && end > *start {
+                    exporter.lose(path.clone(), "content past Slack's limit of 50 blocks");
+                }
+            }
+            exporter.blocks.truncate(MAX_BLOCKS);
+        }
+
+        let text = match &self.fallback_text {
+            Some(text) => text.clone(),
+            None => text::render(self, Format::Plain),
+        };
+        Conversion {
+            output: Message {
+                text,
+                blocks: exporter.blocks,
+            },
+            losses: exporter.losses,
+        }
+    }
+}
+
+struct Exporter {
+    blocks: Vec<Block>,
+    losses: Vec<Loss>,
+}
+
+impl Exporter {
+    fn lose(&mut self, path: impl Into<String>, feature: impl Into<String>) {
+        self.losses.push(Loss {
+            path: path.into(),
+            feature: feature.into(),
+        });
+    }
+
+    fn elements(&mut self, elements: &[CardElement], path: &str) {
+        for (i, element) in elements.iter().enumerate() {
+            self.item(element, &format!("{path}/{i}"));
+        }
+    }
+
+    /// Exports an element of a list, after a divider if it has a separator.
+    fn item(&mut self, element: &CardElement, path: &str) {
+        if !element.is_visible() {
+            self.lose(path, "hidden element");
+            return;
+        }
+        if element.separator() && !self.blocks.is_empty() {
+            self.blocks.push(Block::Divider { block_id: None });
+        }
+        self.element(element, path);
+    }
+
+    fn element(&mut self, element: &CardElement, path: &str) {
+        match element {
+            CardElement::TextBlock(text_block) if text_block.text.is_empty() => {}
+            CardElement::TextBlock(text_block) => {
+                let heading = matches!(text_block.style, Some(TextBlockStyle::Heading))
+                    || matches!(
+                        text_block.size,
+                        Some(TextSize::Large | TextSize::ExtraLarge)
+                    );
+                let plain = markdown::plain(&text_block.text);
+                if heading && plain.chars().count() <= MAX_HEADER_CHARS {
+                    self.blocks.push(Block::Header {
+                        text: Text::plain(plain),
+                        block_id: text_block.id.clone(),
+                    });
+                } else if heading {
+                    self.section(format!("*{}*", escape(&plain)), path);
+                } else {
+                    self.section(mrkdwn(&text_block.text), path);
+                }
+            }
+            CardElement::RichTextBlock(rich_text) => {
+                let text: String = rich_text
+                    .inlines
+                    .iter()
+                    .map(|inline| match inline {
+                        Inline::Text(text) => escape(text),
+                        Inline::TextRun(run) => {
+                            let mut text = escape(&run.text);
+                            if run.strikethrough == Some(true) {
+                                text = format!("~{text}~");
+                            }
+                            if run.italic == Some(true) {
+                                text = format!("_{text}_");
+                            }
+                            if matches!(run.weight, Some(TextWeight::Bolder)) {
+                                text = format!("*{text}*");
+                            }
+                            text
+                        }
+                    })
+                    .collect();
+                if !text.is_empty() {
+                    self.section(text, path);
+                }
+            }
+            CardElement::Container(container) => {
+                if container.select_action.is_some() {
+                    self.lose(format!("{path}/selectAction"), "selectAction");
+                }
+                self.elements(&container.items, &format!("{path}/items"));
+            }
+            CardElement::ColumnSet(column_set) => {
+                if column_set.select_action.is_some() {
+                    self.lose(format!("{path}/selectAction"), "selectAction");
+                }
+                let mut fields = Vec::new();
+                let mut accessory = None;
+                let mut rest = Vec::new();
+                for (i, column) in column_set.columns.iter().enumerate() {
+                    let path = format!("{path}/columns/{i}");
+                    if column.is_visible == Some(false) {
+                        self.lose(path, "hidden element");
+                        continue;
+                    }
+                    if column.select_action.is_some() {
+                        self.lose(format!("{path}/selectAction"), "selectAction");
+                    }
+                    let (text, blocks) = self.flatten(&column.items, &format!("{path}/items"));
+                    if !text.is_empty() {
+                        fields.push(text);
+                    }
+                    for block in blocks {
+                        match block {
+                            Block::Image {
+                                image_url,
+                                alt_text,
+                                ..
+                            } if accessory.is_none() => {
+                                accessory = Some(Element::Image {
+                                    image_url,
+                                    alt_text,
+                                });
+                            }
+                            block => rest.push(block),
+                        }
+                    }
+                }
+                self.fields(fields, accessory, path);
+                self.blocks.extend(rest);
+            }
+            CardElement::Image(image) => {
+                if image.select_action.is_some() {
+                    self.lose(format!("{path}/selectAction"), "selectAction");
+                }
+                self.blocks.push(Block::Image {
+                    image_url: image.url.clone(),
+                    alt_text: image
+                        .alt_text
+                        .clone()
+                        .unwrap_or_else(|| "image".to_string()),
+                    block_id: image.id.clone(),
+                });
+            }
+            CardElement::ImageSet(image_set) => {
+                for (i, image) in image_set.images.iter().enumerate() {
+                    let image = CardElement::Image(image.clone());
+                    self.element(&image, &format!("{path}/images/{i}"));
+                }
+            }
+            CardElement::FactSet(fact_set) => {
+                let fields = fact_set
+                    .facts
+                    .iter()
+                    .map(|fact| format!("*{}*\n{}", escape(&fact.title), mrkdwn(&fact.value)))
+                    .collect();
+                self.fields(fields, None, path);
+            }
+            CardElement::Table(table) => {
+                let headers = table.first_row_as_headers != Some(false);
+                for (r, row) in table.rows.iter().flatten().enumerate() {
+                    let mut fields = Vec::new();
+                    let mut rest = Vec::new();
+                    for (c, cell) in row.cells.iter().enumerate() {
+                        let path = format!("{path}/rows/{r}/cells/{c}");
+                        if cell.select_action.is_some() {
+                            self.lose(format!("{path}/selectAction"), "selectAction");
+                        }
+                        let (text, blocks) = self.flatten(&cell.items, &format!("{path}/items"));
+                        if headers && r == 0 && !text.is_empty() {
+                            fields.push(format!("*{text}*"));
+                        } else if !text.is_empty() {
+                            fields.push(text);
+                        }
+                        rest.extend(blocks);
+                    }
+                    self.fields(fields, None, &format!("{path}/rows/{r}"));
+                    self.blocks.extend(rest);
+                }
+            }
+            CardElement::Media(_) => self.lose(path, "Media"),
+            CardElement::ActionSet(action_set) => {
+                self.actions(&action_set.actions, &format!("{path}/actions"));
+            }
+            CardElement::InputText(input) => {
+                if input.regex.is_some() {
+                    self.lose(format!("{path}/regex"), "Input.Text.regex");
+                }
+                let element = Element::PlainTextInput {
+                    action_id: input.id.clone(),
+                    initial_value: input.value.clone(),
+                    multiline: input.is_multiline,
+                    max_length: input.max_length,
+                    placeholder: input.placeholder.as_deref().map(Text::plain),
+                };
+                self.input(
+                    &input.id,
+                    input.label.as_deref(),
+                    input.is_required,
+                    element,
+                );
+            }
+            CardElement::InputNumber(input) => {
+                let element = Element::NumberInput {
+                    action_id: input.id.clone(),
+                    is_decimal_allowed: true,
+                    initial_value: input.value.map(|value| value.to_string()),
+                    min_value: input.min.map(|min| min.to_string()),
+                    max_value: input.max.map(|max| max.to_string()),
+                    placeholder: input.placeholder.as_deref().map(Text::plain),
+                };
+                self.input(
+                    &input.id,
+                    input.label.as_deref(),
+                    input.is_required,
+                    element,
+                );
+            }
+            CardElement::InputDate(input) => {
+                if input.min.is_some() || input.max.is_some() {
+                    self.lose(path, "Input.Date.min and max");
+                }
+                let element = Element::Datepicker {
+                    action_id: input.id.clone(),
+                    initial_date: input.value.clone(),
+                    placeholder: input.placeholder.as_deref().map(Text::plain),
+                };
+                self.input(
+                    &input.id,
+                    input.label.as_deref(),
+                    input.is_required,
+                    element,
+                );
+            }
+            CardElement::InputTime(input) => {
+                if input.min.is_some() || input.max.is_some() {
+                    self.lose(path, "Input.Time.min and max");
+                }
+                let element = Element::Timepicker {
+                    action_id: input.id.clone(),
+                    initial_time: input.value.clone(),
+                    placeholder: input.placeholder.as_deref().map(Text::plain),
+                };
+                self.input(
+                    &input.id,
+                    input.label.as_deref(),
+                    input.is_required,
+                    element,
+                );
+            }
+            CardElement::InputToggle(input) => {
+                let value_on = input.value_on.as_deref().unwrap_or("true");
+                let option = SelectOption {
+                    text: Text::plain(&input.title),
+                    value: value_on.to_string(),
+                };
+                let checked = input.value.as_deref() == Some(value_on);
+                let element = Element::Checkboxes {
+                    action_id: input.id.clone(),
+                    options: vec![option.clone()],
+                    initial_options: checked.then(|| vec![option]),
+                };
+                let label = input.label.as_deref().unwrap_or(&input.title);
+                self.input(&input.id, Some(label), input.is_required, element);
+            }
+            CardElement::InputChoiceSet(input) => {
+                let options: Vec<SelectOption> = input
+                    .choices
+                    .iter()
+                    .flatten()
+                    .map(SelectOption::from)
+                    .collect();
+                let values: Vec<&str> = input
+                    .value
+                    .as_deref()
+                    .map(|value| value.split(',').collect())
+                    .unwrap_or_default();
+                let mut selected = options
+                    .iter()
+                    .filter(|option| values.contains(&option.value.as_str()))
+                    .cloned();
+                let action_id = input.id.clone();
+                let placeholder = input.placeholder.as_deref().map(Text::plain);
+                let expanded = matches!(input.style, Some(ChoiceInputStyle::Expanded));
+                let element = match (input.is_multi_select == Some(true), expanded) {
+                    (true, true) => Element::Checkboxes {
+                        action_id,
+                        initial_options: Some(selected.collect::<Vec<_>>())
+                            .filter(|options| !options.is_empty()),
+                        options,
+                    },
+                    (true, false) => Element::MultiStaticSelect {
+                        action_id,
+                        initial_options: Some(selected.collect::<Vec<_>>())
+                            .filter(|options| !options.is_empty()),
+                        options,
+                        placeholder,
+                    },
+                    (false, true) => Element::RadioButtons {
+                        action_id,
+                        initial_option: selected.next(),
+                        options,
+                    },
+                    (false, false) => Element::StaticSelect {
+                        action_id,
+                        initial_option: selected.next(),
+                        options,
+                        placeholder,
+                    },
+                };
+                self.input(
+                    &input.id,
+                    input.label.as_deref(),
+                    input.is_required,
+                    element,
+                );
+            }
+        }
+    }
+
+    /// Exports `elements` on their own, returning their text joined by lines and the
+    /// blocks that are not text.
+    fn flatten(&mut self, elements: &[CardElement], path: &str) -> (String, Vec<Block>) {
+        let blocks = std::mem::take(&mut self.blocks);
+        self.elements(elements, path);
+        let mut lines = Vec::new();
+        let mut rest = Vec::new();
+        for block in std::mem::replace(&mut self.blocks, blocks) {
+            match block {
+                Block::Header { text, .. } => lines.push(format!("*{}*", escape(text.text()))),
+                Block::Section {
+                    text,
+                    fields,
+                    accessory: None,
+                    ..
+                } => {
+                    lines.extend(text.iter().map(|text| text.text().to_string()));
+                    lines.extend(
+                        fields
+                            .iter()
+                            .flatten()
+                            .map(|field| field.text().to_string()),
+                    );
+                }
+                Block::Divider { .. } => {}
+                block => rest.push(block),
+            }
+        }
+        (lines.join("\n"), rest)
+    }
+
+    fn section(&mut self, text: String, path: &str) {
+        self.text_section(text, None, path);
+    }
+
+    /// Pushes sections showing `text`, split over several if it is too long for one. The
+    /// accessory is shown beside the first.
+    fn text_section(&mut self, text: String, mut accessory: Option<Element>, path: &str) {
+        let chunks = split(&text, MAX_TEXT_CHARS);
+        if chunks.len() > 1 {
+            self.lose(
+                path,
+                "a single section for text over Slack's limit of 3000 characters",
+            );
+        }
+        for chunk in chunks {
+            self.blocks.push(Block::Section {
+                text: Some(Text::mrkdwn(chunk)),
+                fields: None,
+                accessory: accessory.take(),
+                block_id: None,
+            });
+        }
+    }
+
+    /// Pushes sections showing `fields`, a single one as the section's text.
+    fn fields(&mut self, mut fields: Vec<String>, accessory: Option<Element>, path: &str) {
+        if fields.len() == 1 {
+            self.text_section(fields.remove(0), accessory, path);
+            return;
+        }
+        if fields.is_empty() {
+            // A section needs text, so the accessory image is shown on its own.
+            if let Some(Element::Image {
+                image_url,
+                alt_text,
+            }) = accessory
+            {
+                self.blocks.push(Block::Image {
+                    image_url,
+                    alt_text,
+                    block_id: None,
+                });
+            }
+            return;
+        }
+        for field in &mut fields {
+            if truncate(field, MAX_FIELD_CHARS) {
+                self.lose(path, "field text past Slack's limit of 2000 characters");
+            }
+        }
+        let mut accessory = accessory;
+        for chunk in fields.chunks(MAX_FIELDS) {
+            self.blocks.push(Block::Section {
+                text: None,
+                fields: Some(chunk.iter().map(Text::mrkdwn).collect()),
+                accessory: accessory.take(),
+                block_id: None,
+            });
+        }
+    }
+
+    fn input(
+        &mut self,
+        id: &str,
+        label: Option<&str>,
+        is_required: Option<bool>,
+        element: Element,
+    ) {
+        self.blocks.push(Block::Input {
+            label: Text::plain(label.unwrap_or(id)),
+            element,
+            optional: Some(is_required != Some(true)),
+            block_id: Some(id.to_string()),
+        });
+    }
+
+    fn actions<'a>(&mut self, actions: impl IntoIterator<Item = &'a Action>, path: &str) {
+        let mut buttons = Vec::new();
+        for (i, action) in actions.into_iter().enumerate() {
+            let path = format!("{path}/{i}");
+            let (action_id, url, value) = match action {
+                Action::OpenUrl(action) => (action.id.clone(), Some(action.url.clone()), None),
+                Action::Submit(action) => (action.id.clone(), None, action.data.as_ref()),
+                Action::Execute(action) => (
+                    action.verb.clone().or_else(|| action.id.clone()),
+                    None,
+                    action.data.as_ref(),
+                ),
+                Action::ShowCard(_) => {
+                    self.lose(path, "Action.ShowCard");
+                    continue;
+                }
+                Action::ToggleVisibility(_) => {
+                    self.lose(path, "Action.ToggleVisibility");
+                    continue;
+                }
+            };
+            let mut title = action
+                .title()
+                .or(url.as_deref())
+                .unwrap_or("Submit")
+                .to_string();
+            if truncate(&mut title, MAX_BUTTON_TEXT_CHARS) {
+                self.lose(&path, "button text past Slack's limit of 75 characters");
+            }
+            let mut value = value.map(|value| value.to_string());
+            if let Some(value) = &mut value
+                && truncate(value, MAX_BUTTON_VALUE_CHARS)
+            {
+                self.lose(&path, "button value past Slack's limit of 2000 characters");
+            }
+            buttons.push(Element::Button {
+                text: Text::plain(title),
+                action_id,
+                url,
+                value,
+                style: match action.style() {
+                    Some(ActionStyle::Positive) => Some(ButtonStyle::Primary),
+                    Some(ActionStyle::Destructive) => Some(ButtonStyle::Danger),
+                    _ => None,
+                },
+            });
+        }
+        for chunk in buttons.chunks(MAX_ACTIONS) {
+            self.blocks.push(Block::Actions {
+                elements: chunk.to_vec(),
+                block_id: None,
+            });
+        }
+    }
+}
+
+/// Splits `text` into chunks of at most `max` characters, at line breaks where possible.
+fn split(text: &str, max: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut rest = text;
+    while rest.chars().count() > max {
+        let end = rest
+            .char_indices()
+            .nth(max)
+            .map_or(rest.len(), |(end, _)| end);
+        let end = match rest[..end].rfind('\n') {
+            Some(line) if line > 0 => line,
+            _ => end,
+        };
+        chunks.push(rest[..end].to_string());
+        rest = rest[end..].strip_prefix('\n').unwrap_or(&rest[end..]);
+    }
+    chunks.push(rest.to_string());
+    chunks
+}
+
+/// Shortens `text` to at most `max` characters, ending it with an ellipsis, and returns whether
+/// anything was cut.
+fn truncate(text: &mut String, max: usize) -> bool {
+    if text.chars().count() <= max {
+        return false;
+    }
+    let end = text
+        .char_indices()
+        .nth(max - 1)
+        .map_or(text.len(), |(end, _)| end);
+    text.truncate(end);
+    text.push('…');
+    true
+}
+
+/// Escapes the characters Slack treats as control characters.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Rewrites the Markdown supported in text blocks as Slack `mrkdwn`.
+fn mrkdwn(text: &str) -> String {
+    fn write(spans: &[Span], out: &mut String) {
+        for span in spans {
+            match span {
+                Span::Text(text) => out.push_str(&escape(text)),
+                Span::Bold(spans) => {
+                    out.push('*');
+                    write(spans, out);
+                    out.push('*');
+                }
+                Span::Italic(spans) => {
+                    out.push('_');
+                    write(spans, out);
+                    out.push('_');
+                }
+                Span::Link { text, url } => {
+                    out.push('<');
+                    out.push_str(&escape(url));
+                    out.push('|');
+                    write(text, out);
+                    out.push('>');
+                }
+                Span::Mention(name) => {
+                    out.push('@');
+                    out.push_str(&escape(name));
+                }
+            }
+        }
+    }
+
+    let mut out = String::new();
+    write(&markdown::parse(text), &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::{Table, TableCell, TableRow, TextBlock};
+    use crate::common::ActionStyle;
+    use expect_test::expect;
+    use serde_json::json;
+
+    #[test]
+    fn test_mrkdwn() {
+        assert_eq!(
+            mrkdwn(
+                "**Build** _failed_ on *main*, see [logs](https://ci.example.com/a_b) <at>Ada</at> & co"
+            ),
+            "*Build* _failed_ on _main_, see <https://ci.example.com/a_b|logs> @Ada &amp; co"
+        );
+    }
+
+    #[test]
+    fn test_to_slack_message() {
+        let card = AdaptiveCard::builder()
+            .fallback_text("Disk usage alert")
+            .text_block("Disk usage", |t| t.style(TextBlockStyle::Heading))
+            .column_set(|c| {
+                c.column(|c| c.image("https://example.com/disk.png", |i| i.alt_text("Disk")))
+                    .column(|c| c.text("**db-1** is at 92%").text("Threshold: 90%"))
+            })
+            .fact_set(|f| f.fact("Region", "eu-west-1").fact("Since", "10:02"))
+            .element(Table {
+                rows: Some(vec![
+                    TableRow {
+                        cells: vec![
+                            TableCell {
+                                items: vec![TextBlock::builder("Mount").build().into()],
+                                ..Default::default()
+                            },
+                            TableCell {
+                                items: vec![TextBlock::builder("Used").build().into()],
+                                ..Default::default()
+                            },
+                        ],
+                        ..Default::default()
+                    },
+                    TableRow {
+                        cells: vec![
+                            TableCell {
+                                items: vec![TextBlock::builder("/var").build().into()],
+                                ..Default::default()
+                            },
+                            TableCell {
+                                items: vec![TextBlock::builder("92%").build().into()],
+                                ..Default::default()
+                            },
+                        ],
+                        ..Default::default()
+                    },
+                ]),
+                ..Default::default()
+            })
+            .text_block("Resolved", |t| t.separator(true))
+            .execute(|a| {
+                a.title("Silence")
+                    .verb("silence")
+                    .data(json!({ "host": "db-1" }))
+                    .style(ActionStyle::Destructive)
+            })
+            .open_url("https://grafana.example.com", |a| a.title("Dashboard"))
+            .build();
+
+        let conversion = card.to_slack_message();
+        assert!(conversion.is_lossless());
+        expect![[r#"
+            {
+              "text": "Disk usage alert",
+              "blocks": [
+                {
+                  "type": "header",
+                  "text": {
+                    "type": "plain_text",
+                    "text": "Disk usage"
+                  }
+                },
+                {
+                  "type": "section",
+                  "text": {
+                    "type": "mrkdwn",
+                    "text": "*db-1* is at 92%\nThreshold: 90%"
+                  },
+                  "accessory": {
+                    "type": "image",
+                    "image_url": "https://example.com/disk.png",
+                    "alt_text": "Disk"
+                  }
+                },
+                {
+                  "type": "section",
+                  "fields": [
+                    {
+                      "type": "mrkdwn",
+                      "text": "*Region*\neu-west-1"
+                    },
+                    {
+                      "type": "mrkdwn",
+                      "text": "*Since*\n10:02"
+                    }
+                  ]
+                },
+                {
+                  "type": "section",
+                  "fields": [
+                    {
+                      "type": "mrkdwn",
+                      "text": "*Mount*"
+                    },
+                    {
+                      "type": "mrkdwn",
+                      "text": "*Used*"
+                    }
+                  ]
+                },
+                {
+                  "type": "section",
+                  "fields": [
+                    {
+                      "type": "mrkdwn",
+                      "text": "/var"
+                    },
+                    {
+                      "type": "mrkdwn",
+                      "text": "92%"
+                    }
+                  ]
+                },
+                {
+                  "type": "divider"
+                },
+                {
+                  "type": "section",
+                  "text": {
+                    "type": "mrkdwn",
+                    "text": "Resolved"
+                  }
+                },
+                {
+                  "type": "actions",
+                  "elements": [
+                    {
+                      "type": "button",
+                      "text": {
+                        "type": "plain_text",
+                        "text": "Silence"
+                      },
+                      "action_id": "silence",
+                      "value": "{\"host\":\"db-1\"}",
+                      "style": "danger"
+                    },
+                    {
+                      "type": "button",
+                      "text": {
+                        "type": "plain_text",
+                        "text": "Dashboard"
+                      },
+                      "url": "https://grafana.example.com"
+                    }
+                  ]
+                }
+              ]
+            }"#]]
+        .assert_eq(&serde_json::to_string_pretty(&conversion.output).unwrap());
+    }
+
+    #[test]
+    fn test_inputs() {
+        let card = AdaptiveCard::builder()
+            .input_text("reason", |i| {
+                i.label("Reason").is_multiline(true).regex("^.+$")
+            })
+            .input_number("minutes", |i| {
+                i.label("Minutes").min(5).value(30).required()
+            })
+            .input_toggle("notify", "Notify the team", |i| i.value("true"))
+            .input_choice_set("severity", |i| {
+                i.label("Severity")
+                    .style(ChoiceInputStyle::Expanded)
+                    .choice("High", "high")
+                    .choice("Low", "low")
+                    .value("low")
+            })
+            .show_card(AdaptiveCard::builder().text("More").build(), |a| {
+                a.title("More")
+            })
+            .build();
+
+        let conversion = card.to_slack_message();
+        let blocks: Vec<_> = conversion
+            .output
+            .blocks
+            .iter()
+            .map(|block| serde_json::to_string(block).unwrap())
+            .collect();
+        expect![[r#"
+            [
+                "{\"type\":\"input\",\"label\":{\"type\":\"plain_text\",\"text\":\"Reason\"},\"element\":{\"type\":\"plain_text_input\",\"action_id\":\"reason\",\"multiline\":true},\"optional\":true,\"block_id\":\"reason\"}",
+                "{\"type\":\"input\",\"label\":{\"type\":\"plain_text\",\"text\":\"Minutes\"},\"element\":{\"type\":\"number_input\",\"action_id\":\"minutes\",\"is_decimal_allowed\":true,\"initial_value\":\"30\",\"min_value\":\"5\"},\"optional\":false,\"block_id\":\"minutes\"}",
+                "{\"type\":\"input\",\"label\":{\"type\":\"plain_text\",\"text\":\"Notify the team\"},\"element\":{\"type\":\"checkboxes\",\"action_id\":\"notify\",\"options\":[{\"text\":{\"type\":\"plain_text\",\"text\":\"Notify the team\"},\"value\":\"true\"}],\"initial_options\":[{\"text\":{\"type\":\"plain_text\",\"text\":\"Notify the team\"},\"value\":\"true\"}]},\"optional\":true,\"block_id\":\"notify\"}",
+                "{\"type\":\"input\",\"label\":{\"type\":\"plain_text\",\"text\":\"Severity\"},\"element\":{\"type\":\"radio_buttons\",\"action_id\":\"severity\",\"options\":[{\"text\":{\"type\":\"plain_text\",\"text\":\"High\"},\"value\":\"high\"},{\"text\":{\"type\":\"plain_text\",\"text\":\"Low\"},\"value\":\"low\"}],\"initial_option\":{\"text\":{\"type\":\"plain_text\",\"text\":\"Low\"},\"value\":\"low\"}},\"optional\":true,\"block_id\":\"severity\"}",
+            ]
+        "#]]
+        .assert_debug_eq(&blocks);
+        expect![[r#"
+            /body/0/regex: Input.Text.regex was not converted
+            /actions/0: Action.ShowCard was not converted
+        "#]]
+        .assert_eq(
+            &conversion
+                .losses
+                .iter()
+                .map(|loss| format!("{loss}\n"))
+                .collect::<String>(),
+        );
+    }
+
+    #[test]
+    fn test_losses() {
+        let card = AdaptiveCard::builder()
+            .text_block("Hidden", |t| t.is_visible(false))
+            .media(|m| m.source("https://example.com/a.mp4", |s| s))
+            .toggle_visibility(|a| a.title("Details").target("details"))
+            .build();
+        let conversion = card.to_slack_message();
+        assert!(conversion.output.blocks.is_empty());
+        let losses: Vec<_> = conversion
+            .losses
+            .iter()
+            .map(|loss| (loss.path.as_str(), loss.feature.as_str()))
+            .collect();
+        assert_eq!(
+            losses,
+            [
+                ("/body/0", "hidden element"),
+                ("/body/1", "Media"),
+                ("/actions/0", "Action.ToggleVisibility"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_limits() {
+        let long_line = "a".repeat(2000);
+        let long_text = [long_line.as_str(); 2].join("\n");
+        let mut builder = AdaptiveCard::builder()
+            .text(&long_text)
+            .fact_set(|f| f.fact("Log", &long_text).fact("Host", "db-1"));
+        for i in 0..60 {
+            builder = builder.text(format!("Line {i}"));
+        }
+        let card = builder
+            .open_url("https://example.com", |a| a.title("Open"))
+            .build();
+
+        let conversion = card.to_slack_message();
+        let blocks = &conversion.output.blocks;
+        assert_eq!(blocks.len(), MAX_BLOCKS);
+        let Block::Section {
+            text: Some(text), ..
+        } = &blocks[0]
+        else {
+            panic!("expected a section, got {:?}", blocks[0]);
+        };
+        assert_eq!(text.text(), long_line);
+        let Block::Section {
+            fields: Some(fields),
+            ..
+        } = &blocks[2]
+        else {
+            panic!("expected fields, got {:?}", blocks[2]);
+        };
+        assert_eq!(fields[0].text().chars().count(), MAX_FIELD_CHARS);
+        assert!(fields[0].text().ends_with('…'));
+
+        let losses: Vec<_> = conversion
+            .losses
+            .iter()
+            .map(|loss| (loss.path.as_str(), loss.feature.as_str()))
+            .collect();
+        let mut expected = vec![
+            (
+                "/body/0",
+                "a single section for text over Slack's limit of 3000 characters",
+            ),
+            (
+                "/body/1",
+                "field text past Slack's limit of 2000 characters",
+            ),
+        ];
+        let dropped: Vec<_> = (49..62).map(|i| format!("/body/{i}")).collect();
+        expected.extend(
+            dropped
+                .iter()
+                .map(|path| (path.as_str(), "content past Slack's limit of 50 blocks")),
+        );
+        expected.push(("/actions", "content past Slack's limit of 50 blocks"));
+        assert_eq!(losses, expected);
+    }
+
+    #[test]
+    fn test_button_limits() {
+        let card = AdaptiveCard::builder()
+            .submit(|a| {
+                a.title("b".repeat(100))
+                    .data(json!({ "note": "c".repeat(3000) }))
+            })
+            .submit(|a| a.title("Send").data(json!({ "note": "short" })))
+            .build();
+
+        let conversion = card.to_slack_message();
+        let Block::Actions { elements, .. } = &conversion.output.blocks[0] else {
+            panic!("expected actions, got {:?}", conversion.output.blocks[0]);
+        };
+        let Element::Button {
+            text,
+            value: Some(value),
+            ..
+        } = &elements[0]
+        else {
+            panic!("expected a button with a value, got {:?}", elements[0]);
+        };
+        assert_eq!(text.text().chars().count(), MAX_BUTTON_TEXT_CHARS);
+        assert!(text.text().ends_with('…'));
+        assert_eq!(value.chars().count(), MAX_BUTTON_VALUE_CHARS);
+        assert!(value.ends_with('…'));
+
+        let losses: Vec<_> = conversion
+            .losses
+            .iter()
+            .map(|loss| (loss.path.as_str(), loss.feature.as_str()))
+            .collect();
+        assert_eq!(
+            losses,
+            [
+                (
+                    "/actions/0",
+                    "button text past Slack's limit of 75 characters"
+                ),
+                (
+                    "/actions/0",
+                    "button value past Slack's limit of 2000 characters"
+                ),
+            ]
+        );
+    }
+}
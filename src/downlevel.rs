@@ -0,0 +1,786 @@
+//! Rewrites a card so it can be rendered by hosts that only support an older schema
+//! [`Version`].
+//!
+//! Features with a reasonable equivalent in the target version are replaced:
+//!
+//! - input labels become a `TextBlock` above the input (before 1.3),
+//! - `RichTextBlock` becomes a markdown `TextBlock` (before 1.2),
+//! - `Table` becomes a `Container` of `ColumnSet`s, with bold text in the header row
+//!   (before 1.5),
+//! - `Action.Execute` becomes an `Action.Submit` carrying the verb in its data (before 1.4).
+//!
+//! Properties a replacement cannot express, such as a table's `gridStyle` or a verb that
+//! does not fit in non-object `data`, are reported as removed.
+//!
+//! Everything else that is newer than the target is removed, including elements hidden with
+//! `isVisible: false` when the target cannot toggle visibility. Each replacement and removal
+//! is reported so callers can decide whether the result is still acceptable.
+//!
+//! ```
+//! use adaptive_card_rs::card::{AdaptiveCard, CardElement, Version};
+//! use adaptive_card_rs::inputs::InputToggle;
+//!
+//! let card = AdaptiveCard {
+//!     version: Version::V1_5,
+//!     body: vec![CardElement::InputToggle(InputToggle {
+//!         id: "accept".to_string(),
+//!         title: "I accept".to_string(),
+//!         label: Some("Terms".to_string()),
+//!         is_required: Some(true),
+//!         ..Default::default()
+//!     })],
+//!     ..Default::default()
+//! };
+//!
+//! let downlevelled = card.downlevel(Version::V1_2).unwrap();
+//! assert_eq!(downlevelled.card.body.len(), 2);
+//! assert_eq!(downlevelled.changes.len(), 2);
+//! ```
+use std::fmt;
+
+use serde_json::{Map, Value, json};
+
+use crate::card::{AdaptiveCard, Version};
+use crate::version::{features_used, implied_type, is_opaque, type_name};
+
+/// What happened to a feature that the target version does not support.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// The feature was dropped.
+    Removed,
+    /// The feature was rewritten using the named element or action.
+    Replaced(&'static str),
+}
+
+/// A single feature that was removed or replaced while down-levelling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// JSON pointer to the feature in the original card. Changes nested inside a replaced
+    /// element point into the replacement.
+    pub path: String,
+    /// The feature that is not supported, e.g. `Input.Text.label`.
+    pub feature: String,
+    /// What happened to it.
+    pub kind: ChangeKind,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ChangeKind::Removed => write!(f, "{}: removed {}", self.path, self.feature),
+            ChangeKind::Replaced(with) => {
+                write!(f, "{}: replaced {} with {with}", self.path, self.feature)
+            }
+        }
+    }
+}
+
+/// The result of down-levelling a card.
+#[derive(Debug, Clone)]
+pub struct Downlevelled {
+    /// The rewritten card, declaring the target version.
+    pub card: AdaptiveCard,
+    /// Every feature that was removed or replaced.
+    pub changes: Vec<Change>,
+}
+
+impl Downlevelled {
+    /// Returns true if any feature was dropped without a replacement.
+    pub fn is_lossy(&self) -> bool {
+        self.changes
+            .iter()
+            .any(|change| change.kind == ChangeKind::Removed)
+    }
+}
+
+impl AdaptiveCard {
+    /// Rewrites the card so that it only uses features available in `target`.
+    pub fn downlevel(&self, target: Version) -> Result<Downlevelled, serde_json::Error> {
+        let mut value = serde_json::to_value(self)?;
+        let mut cx = Context {
+            target,
+            changes: Vec::new(),
+        };
+        process(&mut value, Some("AdaptiveCard"), "", &mut cx);
+
+        Ok(Downlevelled {
+            card: serde_json::from_value(value)?,
+            changes: cx.changes,
+        })
+    }
+}
+
+struct Context {
+    target: Version,
+    changes: Vec<Change>,
+}
+
+impl Context {
+    fn record(&mut self, path: String, feature: String, kind: ChangeKind) {
+        self.changes.push(Change {
+            path,
+            feature,
+            kind,
+        });
+    }
+}
+
+enum Outcome {
+    Keep,
+    Remove,
+    Replace(Vec<Value>),
+}
+
+fn process(value: &mut Value, implied: Option<&str>, path: &str, cx: &mut Context) -> Outcome {
+    let object = match value {
+        Value::Object(object) => object,
+        Value::Array(items) => {
+            process_items(items, implied, path, cx);
+            return Outcome::Keep;
+        }
+        _ => return Outcome::Keep,
+    };
+    let ty = type_name(object, implied).to_string();
+
+    if let Some(Converted {
+        value: replacement,
+        with,
+        dropped,
+    }) = convert(object, &ty, cx.target)
+    {
+        cx.record(path.to_string(), ty, ChangeKind::Replaced(with));
+        for (property, feature) in dropped {
+            cx.record(format!("{path}/{property}"), feature, ChangeKind::Removed);
+        }
+        *value = replacement;
+        return process(value, None, path, cx);
+    }
+
+    if ty == "AdaptiveCard" {
+        object.insert("version".to_string(), json!(cx.target.as_str()));
+    }
+
+    if cx.target < Version::V1_2 && object.get("isVisible") == Some(&Value::Bool(false)) {
+        cx.record(
+            path.to_string(),
+            format!("hidden {ty}"),
+            ChangeKind::Removed,
+        );
+        return Outcome::Remove;
+    }
+
+    if let Some(feature) = features_used(object, &ty)
+        .find(|feature| feature.property.is_none() && feature.version > cx.target)
+    {
+        cx.record(path.to_string(), feature.name(&ty), ChangeKind::Removed);
+        return Outcome::Remove;
+    }
+
+    let label = match object.get("label") {
+        Some(Value::String(label)) if ty.starts_with("Input.") && cx.target < Version::V1_3 => {
+            let label = label.clone();
+            object.remove("label");
+            cx.record(
+                format!("{path}/label"),
+                format!("{ty}.label"),
+                ChangeKind::Replaced("TextBlock"),
+            );
+            Some(label)
+        }
+        _ => None,
+    };
+
+    let unsupported: Vec<(&str, String)> = features_used(object, &ty)
+        .filter(|feature| feature.version > cx.target)
+        .filter_map(|feature| Some((feature.property?, feature.name(&ty))))
+        .collect();
+    for (property, feature) in unsupported {
+        object.remove(property);
+        cx.record(format!("{path}/{property}"), feature, ChangeKind::Removed);
+    }
+
+    let keys: Vec<String> = object.keys().filter(|k| !is_opaque(k)).cloned().collect();
+    for key in keys {
+        let child = object.get_mut(&key).expect("key was just listed");
+        match process(child, implied_type(&key), &format!("{path}/{key}"), cx) {
+            Outcome::Keep => {}
+            Outcome::Remove => {
+                object.remove(&key);
+            }
+            Outcome::Replace(mut values) => {
+                if let Some(last) = values.pop() {
+                    object.insert(key, last);
+                }
+            }
+        }
+    }
+
+    match label {
+        Some(label) => Outcome::Replace(vec![
+            json!({ "type": "TextBlock", "text": label, "wrap": true }),
+            value.take(),
+        ]),
+        None => Outcome::Keep,
+    }
+}
+
+fn process_items(items: &mut Vec<Value>, implied: Option<&str>, path: &str, cx: &mut Context) {
+    for (i, mut item) in std::mem::take(items).into_iter().enumerate() {
+        match process(&mut item, implied, &format!("{path}/{i}"), cx) {
+            Outcome::Keep => items.push(item),
+            Outcome::Remove => {}
+            Outcome::Replace(values) => items.extend(values),
+        }
+    }
+}
+
+/// An equivalent of an object whose type is too new.
+struct Converted {
+    value: Value,
+    /// The type of the replacement.
+    with: &'static str,
+    /// Properties the replacement cannot express, as paths relative to the object and
+    /// feature names.
+    dropped: Vec<(String, String)>,
+}
+
+/// Returns an equivalent of the object built from features available in `target`, if the
+/// object's type is too new and has one.
+fn convert(object: &Map<String, Value>, ty: &str, target: Version) -> Option<Converted> {
+    let mut dropped = Vec::new();
+    let (value, with) = match ty {
+        "RichTextBlock" if target < Version::V1_2 => (rich_text_to_text_block(object), "TextBlock"),
+        "Table" if target < Version::V1_5 => {
+            (table_to_container(object, &mut dropped), "Container")
+        }
+        "Action.Execute" if target < Version::V1_4 => {
+            (execute_to_submit(object, &mut dropped), "Action.Submit")
+        }
+        _ => return None,
+    };
+    Some(Converted {
+        value,
+        with,
+        dropped,
+    })
+}
+
+fn rich_text_to_text_block(object: &Map<String, Value>) -> Value {
+    let text: String = object
+        .get("inlines")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(|inline| match inline {
+            Value::String(text) => escape_markdown(text),
+            Value::Object(run) => {
+                let mut text =
+                    escape_markdown(run.get("text").and_then(Value::as_str).unwrap_or_default());
+                if run.get("weight").and_then(Value::as_str) == Some("bolder") {
+                    text = format!("**{text}**");
+                }
+                if run.get("italic") == Some(&Value::Bool(true)) {
+                    text = format!("_{text}_");
+                }
+                text
+            }
+            _ => String::new(),
+        })
+        .collect();
+
+    let mut block = Map::new();
+    block.insert("type".to_string(), json!("TextBlock"));
+    block.insert("text".to_string(), json!(text));
+    block.insert("wrap".to_string(), json!(true));
+    copy_common(object, &mut block, &["horizontalAlignment"]);
+    Value::Object(block)
+}
+
+/// Escapes the characters that Markdown in a `TextBlock` reads as formatting, since text runs
+/// are plain text.
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn table_to_container(object: &Map<String, Value>, dropped: &mut Vec<(String, String)>) -> Value {
+    for property in ["gridStyle", "horizontalCellContentAlignment"] {
+        if object.contains_key(property) {
+            dropped.push((property.to_string(), format!("Table.{property}")));
+        }
+    }
+    let definitions: Vec<&Value> = object
+        .get("columns")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .collect();
+    for (j, definition) in definitions.iter().enumerate() {
+        if definition.get("horizontalCellContentAlignment").is_some() {
+            dropped.push((
+                format!("columns/{j}/horizontalCellContentAlignment"),
+                "TableColumnDefinition.horizontalCellContentAlignment".to_string(),
+            ));
+        }
+    }
+    let grid_lines = object.get("showGridLines") != Some(&Value::Bool(false));
+    // `firstRowAsHeaders` defaults to true.
+    let headers = object.get("firstRowAsHeaders") != Some(&Value::Bool(false));
+
+    let rows: Vec<Value> = object
+        .get("rows")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .map(|(i, row)| {
+            if row.get("horizontalCellContentAlignment").is_some() {
+                dropped.push((
+                    format!("rows/{i}/horizontalCellContentAlignment"),
+                    "TableRow.horizontalCellContentAlignment".to_string(),
+                ));
+            }
+            let columns: Vec<Value> = row
+                .get("cells")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .enumerate()
+                .map(|(j, cell)| {
+                    let definition = definitions.get(j);
+                    let mut column = Map::new();
+                    column.insert("type".to_string(), json!("Column"));
+                    if let Some(width) = definition.and_then(|d| d.get("width")) {
+                        column.insert("width".to_string(), width.clone());
+                    }
+                    for property in ["style", "selectAction", "bleed", "minHeight", "rtl"] {
+                        if let Some(value) = cell.get(property) {
+                            column.insert(property.to_string(), value.clone());
+                        }
+                    }
+                    // The nearest vertical alignment applies, as it does for table cells.
+                    let vertical = cell
+                        .get("verticalContentAlignment")
+                        .or_else(|| row.get("verticalCellContentAlignment"))
+                        .or_else(|| definition.and_then(|d| d.get("verticalCellContentAlignment")))
+                        .or_else(|| object.get("verticalCellContentAlignment"));
+                    if let Some(vertical) = vertical {
+                        column.insert("verticalContentAlignment".to_string(), vertical.clone());
+                    }
+                    let mut items = cell.get("items").cloned().unwrap_or_else(|| json!([]));
+                    if headers && i == 0 {
+                        embolden(&mut items);
+                    }
+                    column.insert("items".to_string(), items);
+                    Value::Object(column)
+                })
+                .collect();
+
+            let mut column_set = Map::new();
+            column_set.insert("type".to_string(), json!("ColumnSet"));
+            column_set.insert("columns".to_string(), Value::Array(columns));
+            if let Some(style) = row.get("style") {
+                column_set.insert("style".to_string(), style.clone());
+            }
+            if grid_lines && i > 0 {
+                column_set.insert("separator".to_string(), json!(true));
+            }
+            Value::Object(column_set)
+        })
+        .collect();
+
+    let mut container = Map::new();
+    container.insert("type".to_string(), json!("Container"));
+    container.insert("items".to_string(), Value::Array(rows));
+    copy_common(object, &mut container, &[]);
+    Value::Object(container)
+}
+
+/// Makes the text blocks in a header cell bold, as hosts style table headers. Text blocks
+/// that set their own weight keep it.
+fn embolden(items: &mut Value) {
+    for item in items.as_array_mut().into_iter().flatten() {
+        let Some(object) = item.as_object_mut() else {
+            continue;
+        };
+        match object.get("type").and_then(Value::as_str) {
+            Some("TextBlock") => {
+                object.entry("weight").or_insert_with(|| json!("bolder"));
+            }
+            Some("Container") => {
+                if let Some(items) = object.get_mut("items") {
+                    embolden(items);
+                }
+            }
+            Some("ColumnSet") => {
+                let columns = object.get_mut("columns").and_then(Value::as_array_mut);
+                for column in columns.into_iter().flatten() {
+                    if let Some(items) = column.get_mut("items") {
+                        embolden(items);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn execute_to_submit(object: &Map<String, Value>, dropped: &mut Vec<(String, String)>) -> Value {
+    let mut submit = object.clone();
+    submit.insert("type".to_string(), json!("Action.Submit"));
+    if let Some(verb) = submit.remove("verb") {
+        // The verb travels in `data`, unless `data` cannot hold it or already has a verb.
+        let kept = match submit.get_mut("data") {
+            None | Some(Value::Null) => {
+                submit.insert("data".to_string(), json!({ "verb": verb }));
+                true
+            }
+            Some(Value::Object(data)) => match data.get("verb") {
+                None => {
+                    data.insert("verb".to_string(), verb);
+                    true
+                }
+                Some(existing) => *existing == verb,
+            },
+            Some(_) => false,
+        };
+        if !kept {
+            dropped.push(("verb".to_string(), "Action.Execute.verb".to_string()));
+        }
+    }
+    Value::Object(submit)
+}
+
+fn copy_common(from: &Map<String, Value>, to: &mut Map<String, Value>, extra: &[&str]) {
+    for key in ["id", "separator", "spacing", "height", "isVisible"]
+        .iter()
+        .chain(extra)
+    {
+        if let Some(value) = from.get(*key) {
+            to.insert(key.to_string(), value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{Action, ExecuteAction};
+    use crate::card::{
+        CardElement, Inline, RichTextBlock, Table, TableCell, TableRow, TextBlock, TextRun,
+        TextWeight,
+    };
+    use expect_test::expect;
+    use serde_json::json;
+
+    #[test]
+    fn test_card_within_target_is_unchanged() {
+        let card = AdaptiveCard {
+            version: Version::V1_2,
+            body: vec![CardElement::TextBlock(TextBlock {
+                text: "Hello".to_string(),
+                ..Default::default()
+            })],
+            ..Default::default()
+        };
+
+        let downlevelled = card.downlevel(Version::V1_2).unwrap();
+        assert_eq!(downlevelled.changes, vec![]);
+        assert_eq!(
+            serde_json::to_value(&downlevelled.card).unwrap(),
+            serde_json::to_value(&card).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_replacements_and_removals() {
+        let card = AdaptiveCard {
+            version: Version::V1_5,
+            body: vec![
+                CardElement::RichTextBlock(RichTextBlock {
+                    inlines: vec![
+                        Inline::Text("Build ".to_string()),
+                        Inline::TextRun(TextRun {
+                            type_field: "TextRun".to_string(),
+                            text: "failed".to_string(),
+                            color: None,
+                            font_type: None,
+                            highlight: None,
+                            is_subtle: None,
+                            italic: None,
+                            size: None,
+                            strikethrough: None,
+                            weight: Some(TextWeight::Bolder),
+                        }),
+                    ],
+                    horizontal_alignment: None,
+                    id: None,
+                    separator: None,
+                    spacing: None,
+                    height: None,
+                    is_visible: None,
+                }),
+                CardElement::Table(Table {
+                    rows: Some(vec![TableRow {
+                        cells: vec![TableCell {
+                            items: vec![CardElement::TextBlock(TextBlock {
+                                text: "cell".to_string(),
+                                ..Default::default()
+                            })],
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                }),
+                CardElement::TextBlock(TextBlock {
+                    text: "hidden".to_string(),
+                    is_visible: Some(false),
+                    ..Default::default()
+                }),
+            ],
+            actions: Some(vec![Action::Execute(ExecuteAction {
+                title: Some("Approve".to_string()),
+                verb: Some("approve".to_string()),
+                tooltip: Some("Approve the request".to_string()),
+                ..Default::default()
+            })]),
+            ..Default::default()
+        };
+
+        let downlevelled = card.downlevel(Version::V1_0).unwrap();
+
+        let changes: Vec<String> = downlevelled
+            .changes
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                "/actions/0: replaced Action.Execute with Action.Submit",
+                "/actions/0/tooltip: removed Action.Submit.tooltip",
+                "/body/0: replaced RichTextBlock with TextBlock",
+                "/body/1: replaced Table with Container",
+                "/body/2: removed hidden TextBlock",
+            ]
+        );
+        assert!(downlevelled.is_lossy());
+
+        let expected = expect![[r#"
+            {
+              "type": "AdaptiveCard",
+              "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+              "version": "1.0",
+              "body": [
+                {
+                  "type": "TextBlock",
+                  "text": "Build **failed**",
+                  "wrap": true
+                },
+                {
+                  "type": "Container",
+                  "items": [
+                    {
+                      "type": "ColumnSet",
+                      "columns": [
+                        {
+                          "type": "Column",
+                          "items": [
+                            {
+                              "type": "TextBlock",
+                              "text": "cell",
+                              "weight": "bolder"
+                            }
+                          ]
+                        }
+                      ]
+                    }
+                  ]
+                }
+              ],
+              "actions": [
+                {
+                  "type": "Action.Submit",
+                  "title": "Approve",
+                  "data": {
+                    "verb": "approve"
+                  }
+                }
+              ]
+            }"#]];
+        expected.assert_eq(&serde_json::to_string_pretty(&downlevelled.card).unwrap());
+    }
+
+    #[test]
+    fn test_execute_verb_that_cannot_be_kept_is_reported() {
+        let card = AdaptiveCard {
+            version: Version::V1_4,
+            actions: Some(vec![
+                Action::Execute(ExecuteAction {
+                    verb: Some("approve".to_string()),
+                    data: Some(json!("order-42")),
+                    ..Default::default()
+                }),
+                Action::Execute(ExecuteAction {
+                    verb: Some("approve".to_string()),
+                    data: Some(json!({ "verb": "legacy" })),
+                    ..Default::default()
+                }),
+                Action::Execute(ExecuteAction {
+                    verb: Some("approve".to_string()),
+                    data: Some(json!({ "id": 42 })),
+                    ..Default::default()
+                }),
+            ]),
+            ..Default::default()
+        };
+
+        let downlevelled = card.downlevel(Version::V1_3).unwrap();
+        let changes: Vec<String> = downlevelled
+            .changes
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                "/actions/0: replaced Action.Execute with Action.Submit",
+                "/actions/0/verb: removed Action.Execute.verb",
+                "/actions/1: replaced Action.Execute with Action.Submit",
+                "/actions/1/verb: removed Action.Execute.verb",
+                "/actions/2: replaced Action.Execute with Action.Submit",
+            ]
+        );
+        assert!(downlevelled.is_lossy());
+        let actions = serde_json::to_value(&downlevelled.card).unwrap()["actions"].clone();
+        assert_eq!(actions[0]["data"], json!("order-42"));
+        assert_eq!(actions[1]["data"], json!({ "verb": "legacy" }));
+        assert_eq!(actions[2]["data"], json!({ "id": 42, "verb": "approve" }));
+    }
+
+    #[test]
+    fn test_table_properties_without_equivalent_are_reported() {
+        let card: AdaptiveCard = serde_json::from_value(json!({
+            "type": "AdaptiveCard",
+            "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+            "version": "1.5",
+            "body": [{
+                "type": "Table",
+                "firstRowAsHeaders": true,
+                "gridStyle": "accent",
+                "verticalCellContentAlignment": "center",
+                "columns": [{ "width": 1, "horizontalCellContentAlignment": "right" }],
+                "rows": [{
+                    "type": "TableRow",
+                    "horizontalCellContentAlignment": "center",
+                    "cells": [{
+                        "type": "TableCell",
+                        "style": "good",
+                        "items": [{ "type": "TextBlock", "text": "cell" }]
+                    }]
+                }]
+            }]
+        }))
+        .unwrap();
+
+        let downlevelled = card.downlevel(Version::V1_2).unwrap();
+        let changes: Vec<String> = downlevelled
+            .changes
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                "/body/0: replaced Table with Container",
+                "/body/0/gridStyle: removed Table.gridStyle",
+                "/body/0/columns/0/horizontalCellContentAlignment: removed TableColumnDefinition.horizontalCellContentAlignment",
+                "/body/0/rows/0/horizontalCellContentAlignment: removed TableRow.horizontalCellContentAlignment",
+            ]
+        );
+        let column =
+            serde_json::to_value(&downlevelled.card).unwrap()["body"][0]["items"][0]["columns"][0]
+                .clone();
+        assert_eq!(column["style"], "good");
+        assert_eq!(column["verticalContentAlignment"], "center");
+    }
+
+    #[test]
+    fn test_table_header_row_is_bold() {
+        let table = |first_row_as_headers: Option<bool>| AdaptiveCard {
+            version: Version::V1_5,
+            body: vec![CardElement::Table(Table {
+                first_row_as_headers,
+                rows: Some(
+                    ["Name", "Ada"]
+                        .into_iter()
+                        .map(|text| TableRow {
+                            cells: vec![TableCell {
+                                items: vec![CardElement::TextBlock(TextBlock {
+                                    text: text.to_string(),
+                                    ..Default::default()
+                                })],
+                                ..Default::default()
+                            }],
+                            ..Default::default()
+                        })
+                        .collect(),
+                ),
+                ..Default::default()
+            })],
+            ..Default::default()
+        };
+        let weights = |card: AdaptiveCard| {
+            let card = serde_json::to_value(card.downlevel(Version::V1_2).unwrap().card).unwrap();
+            card["body"][0]["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|row| row["columns"][0]["items"][0]["weight"].clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(weights(table(None)), vec![json!("bolder"), Value::Null]);
+        assert_eq!(
+            weights(table(Some(true))),
+            vec![json!("bolder"), Value::Null]
+        );
+        assert_eq!(weights(table(Some(false))), vec![Value::Null, Value::Null]);
+    }
+
+    #[test]
+    fn test_rich_text_markdown_characters_are_escaped() {
+        let card = AdaptiveCard {
+            version: Version::V1_2,
+            body: vec![CardElement::RichTextBlock(RichTextBlock {
+                inlines: vec![
+                    Inline::Text("2 * 3 in snake_case ".to_string()),
+                    Inline::TextRun(TextRun {
+                        type_field: "TextRun".to_string(),
+                        text: "[x]".to_string(),
+                        color: None,
+                        font_type: None,
+                        highlight: None,
+                        is_subtle: None,
+                        italic: Some(true),
+                        size: None,
+                        strikethrough: None,
+                        weight: None,
+                    }),
+                ],
+                ..Default::default()
+            })],
+            ..Default::default()
+        };
+
+        let card = serde_json::to_value(card.downlevel(Version::V1_1).unwrap().card).unwrap();
+        assert_eq!(card["body"][0]["text"], r"2 \* 3 in snake\_case _\[x\]_");
+    }
+}
@@ -0,0 +1,622 @@
+//! The [`card!`](crate::card!) macro, a compact way to write cards.
+//!
+//! ```
+//! use adaptive_card_rs::card;
+//!
+//! let env = "production";
+//! let sha = "1a2b3c4";
+//! let url = "https://ci.example.com/runs/42";
+//!
+//! let card = card! {
+//!     version: 1.5,
+//!     TextBlock("Deploy finished", size: large, weight: bolder),
+//!     FactSet { "Env" => env, "SHA" => sha },
+//!     actions: [OpenUrl("Logs", url)],
+//! };
+//!
+//! assert_eq!(card.body.len(), 2);
+//! ```
+//!
+//! The body of the macro is a comma separated list of card properties and elements:
+//!
+//! - `name: value` sets a property. A bare identifier as the value names one of the
+//!   [`keywords`], e.g. `size: large` or `style: emphasis`. Anything else is an expression, so
+//!   wrap variables in braces: `label: { label }`.
+//! - `version: 1.5` sets the card version and `actions: [..]` adds actions to the card.
+//! - `Element(required, .., name: value, ..)` adds an element. The positional arguments are the
+//!   element's required fields, in the same order as its `builder` function. Parentheses can
+//!   be left out when there are none.
+//! - `{ expression }` adds an element built elsewhere, e.g. a `Table` from its builder.
+//!
+//! Elements that hold content take it in braces after the arguments:
+//!
+//! - `Container` and `Column` hold elements, `ColumnSet` holds `Column`s and `ActionSet`
+//!   holds actions.
+//! - `FactSet` and `InputChoiceSet` hold `title => value` pairs.
+//! - `ImageSet` holds image URLs and `RichTextBlock` holds text.
+//!
+//! Actions are written `OpenUrl(title, url)`, `Submit(title)`, `Execute(title)`,
+//! `ShowCard(title) { card body }` and `ToggleVisibility(title) { target ids }`, each
+//! followed by any properties.
+//!
+//! Properties are checked by the compiler: an unknown property is a missing builder method,
+//! and a keyword that doesn't apply to a property is a type error.
+//!
+//! ```compile_fail
+//! use adaptive_card_rs::card;
+//!
+//! // `TextBlock` has no `colour` property.
+//! let card = card! { TextBlock("Hi", colour: good) };
+//! ```
+//!
+//! Unknown elements and actions are reported by name:
+//!
+//! ```compile_fail
+//! use adaptive_card_rs::card;
+//!
+//! // error: unknown element `Button`
+//! let card = card! { Button("Hi") };
+//! ```
+
+/// Builds an [`AdaptiveCard`](crate::card::AdaptiveCard). See the [`dsl`](crate::dsl) module
+/// for the syntax.
+#[macro_export]
+macro_rules! card {
+    // Card properties and elements.
+    (@card ($b:expr)) => { $b.build() };
+    (@card ($b:expr) , $($rest:tt)*) => { $crate::card!(@card ($b) $($rest)*) };
+    (@card ($b:expr) version : 1.0 $(, $($rest:tt)*)?) => {
+        $crate::card!(@card ($b.version($crate::card::Version::V1_0)) $($($rest)*)?)
+    };
+    (@card ($b:expr) version : 1.1 $(, $($rest:tt)*)?) => {
+        $crate::card!(@card ($b.version($crate::card::Version::V1_1)) $($($rest)*)?)
+    };
+    (@card ($b:expr) version : 1.2 $(, $($rest:tt)*)?) => {
+        $crate::card!(@card ($b.version($crate::card::Version::V1_2)) $($($rest)*)?)
+    };
+    (@card ($b:expr) version : 1.3 $(, $($rest:tt)*)?) => {
+        $crate::card!(@card ($b.version($crate::card::Version::V1_3)) $($($rest)*)?)
+    };
+    (@card ($b:expr) version : 1.4 $(, $($rest:tt)*)?) => {
+        $crate::card!(@card ($b.version($crate::card::Version::V1_4)) $($($rest)*)?)
+    };
+    (@card ($b:expr) version : 1.5 $(, $($rest:tt)*)?) => {
+        $crate::card!(@card ($b.version($crate::card::Version::V1_5)) $($($rest)*)?)
+    };
+    (@card ($b:expr) version : 1.6 $(, $($rest:tt)*)?) => {
+        $crate::card!(@card ($b.version($crate::card::Version::V1_6)) $($($rest)*)?)
+    };
+    (@card ($b:expr) version : $version:literal $(, $($rest:tt)*)?) => {
+        compile_error!(concat!("unsupported card version ", stringify!($version)))
+    };
+    (@card ($b:expr) actions : [$($actions:tt)*] $(, $($rest:tt)*)?) => {
+        $crate::card!(@card ($crate::card!(@actions ($b) $($actions)*)) $($($rest)*)?)
+    };
+    (@card ($b:expr) select_action : $name:ident ($($args:tt)*) $(, $($rest:tt)*)?) => {
+        $crate::card!(@card ($b.select_action($crate::card!(@action $name [$($args)*] []))) $($($rest)*)?)
+    };
+    (@card ($b:expr) $key:ident : $($rest:tt)*) => {
+        $crate::card!(@card_prop ($b) $key : $($rest)*)
+    };
+    (@card ($b:expr) {$($element:tt)*} $(, $($rest:tt)*)?) => {
+        $crate::card!(@card ($b.element({ $($element)* })) $($($rest)*)?)
+    };
+    (@card ($b:expr) $name:ident $(($($args:tt)*))? $({$($children:tt)*})? $(, $($rest:tt)*)?) => {
+        $crate::card!(@card ($b.element($crate::card!(@element $name [$($($args)*)?] [$($($children)*)?]))) $($($rest)*)?)
+    };
+
+    // A card property: sets it and goes back to the card.
+    (@card_prop ($b:expr) $key:ident : true $(, $($rest:tt)*)?) => {
+        $crate::card!(@card ($b.$key(true)) $($($rest)*)?)
+    };
+    (@card_prop ($b:expr) $key:ident : false $(, $($rest:tt)*)?) => {
+        $crate::card!(@card ($b.$key(false)) $($($rest)*)?)
+    };
+    (@card_prop ($b:expr) $key:ident : $keyword:ident $(, $($rest:tt)*)?) => {
+        $crate::card!(@card ($b.$key($crate::dsl::keywords::$keyword)) $($($rest)*)?)
+    };
+    (@card_prop ($b:expr) $key:ident : $value:expr $(, $($rest:tt)*)?) => {
+        $crate::card!(@card ($b.$key($value)) $($($rest)*)?)
+    };
+
+    // Element and action properties.
+    (@props ($b:expr)) => { $b };
+    (@props ($b:expr) , $($rest:tt)*) => { $crate::card!(@props ($b) $($rest)*) };
+    (@props ($b:expr) $key:ident : true $(, $($rest:tt)*)?) => {
+        $crate::card!(@props ($b.$key(true)) $($($rest)*)?)
+    };
+    (@props ($b:expr) $key:ident : false $(, $($rest:tt)*)?) => {
+        $crate::card!(@props ($b.$key(false)) $($($rest)*)?)
+    };
+    (@props ($b:expr) $key:ident : $keyword:ident $(, $($rest:tt)*)?) => {
+        $crate::card!(@props ($b.$key($crate::dsl::keywords::$keyword)) $($($rest)*)?)
+    };
+    (@props ($b:expr) $key:ident : $value:expr $(, $($rest:tt)*)?) => {
+        $crate::card!(@props ($b.$key($value)) $($($rest)*)?)
+    };
+
+    // An element: splits its arguments into required fields and properties.
+    (@element $name:ident [$($args:tt)*] [$($children:tt)*]) => {
+        $crate::card!(@split $name [] [$($children)*] $($args)*)
+    };
+    (@split $name:ident [$($required:expr),*] [$($children:tt)*]) => {
+        $crate::card!(@children $name ($crate::card!(@new $name $($required),*)) $($children)*)
+    };
+    (@split $name:ident [$($required:expr),*] [$($children:tt)*] $key:ident : $($props:tt)*) => {
+        $crate::card!(@children $name ($crate::card!(@props ($crate::card!(@new $name $($required),*)) $key : $($props)*)) $($children)*)
+    };
+    (@split $name:ident [$($required:expr),*] [$($children:tt)*] $arg:expr $(, $($rest:tt)*)?) => {
+        $crate::card!(@split $name [$($required,)* $arg] [$($children)*] $($($rest)*)?)
+    };
+
+    (@new TextBlock $($arg:expr),*) => { $crate::card::TextBlock::builder($($arg),*) };
+    (@new Image $($arg:expr),*) => { $crate::card::Image::builder($($arg),*) };
+    (@new Container $($arg:expr),*) => { $crate::card::Container::builder($($arg),*) };
+    (@new ColumnSet $($arg:expr),*) => { $crate::card::ColumnSet::builder($($arg),*) };
+    (@new Column $($arg:expr),*) => { $crate::card::Column::builder($($arg),*) };
+    (@new FactSet $($arg:expr),*) => { $crate::card::FactSet::builder($($arg),*) };
+    (@new ImageSet $($arg:expr),*) => { $crate::card::ImageSet::builder($($arg),*) };
+    (@new RichTextBlock $($arg:expr),*) => { $crate::card::RichTextBlock::builder($($arg),*) };
+    (@new ActionSet $($arg:expr),*) => { $crate::actions::ActionSet::builder($($arg),*) };
+    (@new InputText $($arg:expr),*) => { $crate::inputs::InputText::builder($($arg),*) };
+    (@new InputNumber $($arg:expr),*) => { $crate::inputs::InputNumber::builder($($arg),*) };
+    (@new InputDate $($arg:expr),*) => { $crate::inputs::InputDate::builder($($arg),*) };
+    (@new InputTime $($arg:expr),*) => { $crate::inputs::InputTime::builder($($arg),*) };
+    (@new InputToggle $($arg:expr),*) => { $crate::inputs::InputToggle::builder($($arg),*) };
+    (@new InputChoiceSet $($arg:expr),*) => { $crate::inputs::InputChoiceSet::builder($($arg),*) };
+    (@new $name:ident $($arg:expr),*) => {
+        compile_error!(concat!("unknown element `", stringify!($name), "`"))
+    };
+
+    // The content of an element.
+    (@children $name:ident ($b:expr)) => { $b };
+    (@children FactSet ($b:expr) $($title:expr => $value:expr),* $(,)?) => {
+        $b$(.fact($title, $value))*
+    };
+    (@children InputChoiceSet ($b:expr) $($title:expr => $value:expr),* $(,)?) => {
+        $b$(.choice($title, $value))*
+    };
+    (@children ImageSet ($b:expr) $($url:expr),* $(,)?) => {
+        $b$(.image($url, |image| image))*
+    };
+    (@children RichTextBlock ($b:expr) $($text:expr),* $(,)?) => {
+        $b$(.text($text))*
+    };
+    (@children ActionSet ($b:expr) $($actions:tt)*) => {
+        $crate::card!(@actions ($b) $($actions)*)
+    };
+    (@children ColumnSet ($b:expr) $($columns:tt)*) => {
+        $crate::card!(@columns ($b) $($columns)*)
+    };
+    (@children $name:ident ($b:expr) $($elements:tt)*) => {
+        $crate::card!(@elements ($b) $($elements)*)
+    };
+
+    (@elements ($b:expr)) => { $b };
+    (@elements ($b:expr) , $($rest:tt)*) => { $crate::card!(@elements ($b) $($rest)*) };
+    (@elements ($b:expr) {$($element:tt)*} $(, $($rest:tt)*)?) => {
+        $crate::card!(@elements ($b.element({ $($element)* })) $($($rest)*)?)
+    };
+    (@elements ($b:expr) $name:ident $(($($args:tt)*))? $({$($children:tt)*})? $(, $($rest:tt)*)?) => {
+        $crate::card!(@elements ($b.element($crate::card!(@element $name [$($($args)*)?] [$($($children)*)?]))) $($($rest)*)?)
+    };
+
+    (@columns ($b:expr)) => { $b };
+    (@columns ($b:expr) , $($rest:tt)*) => { $crate::card!(@columns ($b) $($rest)*) };
+    (@columns ($b:expr) Column $(($($args:tt)*))? $({$($children:tt)*})? $(, $($rest:tt)*)?) => {
+        $crate::card!(@columns ($b.column(|_| $crate::card!(@element Column [$($($args)*)?] [$($($children)*)?]))) $($($rest)*)?)
+    };
+    (@columns ($b:expr) $name:ident $($rest:tt)*) => {
+        compile_error!(concat!("a ColumnSet can only hold columns, found `", stringify!($name), "`"))
+    };
+
+    // Actions.
+    (@actions ($b:expr)) => { $b };
+    (@actions ($b:expr) , $($rest:tt)*) => { $crate::card!(@actions ($b) $($rest)*) };
+    (@actions ($b:expr) {$($action:tt)*} $(, $($rest:tt)*)?) => {
+        $crate::card!(@actions ($b.action({ $($action)* })) $($($rest)*)?)
+    };
+    (@actions ($b:expr) $name:ident ($($args:tt)*) $({$($children:tt)*})? $(, $($rest:tt)*)?) => {
+        $crate::card!(@actions ($b.action($crate::card!(@action $name [$($args)*] [$($($children)*)?]))) $($($rest)*)?)
+    };
+
+    (@action OpenUrl [$title:expr, $url:expr $(, $($props:tt)*)?] []) => {
+        $crate::card!(@props ($crate::actions::OpenUrlAction::builder($url).title($title)) $($($props)*)?)
+    };
+    (@action Submit [$title:expr $(, $($props:tt)*)?] []) => {
+        $crate::card!(@props ($crate::actions::SubmitAction::builder().title($title)) $($($props)*)?)
+    };
+    (@action Execute [$title:expr $(, $($props:tt)*)?] []) => {
+        $crate::card!(@props ($crate::actions::ExecuteAction::builder().title($title)) $($($props)*)?)
+    };
+    (@action ShowCard [$title:expr $(, $($props:tt)*)?] [$($card:tt)*]) => {
+        $crate::card!(@props ($crate::actions::ShowCardAction::builder($crate::card!($($card)*)).title($title)) $($($props)*)?)
+    };
+    (@action ToggleVisibility [$title:expr $(, $($props:tt)*)?] [$($target:expr),* $(,)?]) => {
+        $crate::card!(@props ($crate::actions::ToggleVisibilityAction::builder().title($title)$(.target($target))*) $($($props)*)?)
+    };
+    (@action $name:ident [$($args:tt)*] [$($children:tt)*]) => {
+        compile_error!(concat!("unknown action `", stringify!($name), "`"))
+    };
+
+    ($($body:tt)*) => {
+        $crate::card!(@card ($crate::card::AdaptiveCard::builder()) $($body)*)
+    };
+}
+
+/// Keywords that can be used as property values in [`card!`](crate::card!).
+///
+/// Each keyword converts into the enum variants it names, so `large` works for
+/// [`TextSize`](crate::card::TextSize), [`Spacing`](crate::card::Spacing) and
+/// [`ImageSize`](crate::card::ImageSize).
+#[allow(non_camel_case_types)]
+pub mod keywords {
+    use crate::card::{
+        ColumnWidth, ContainerStyle, ImageFillMode, ImageSize, MsTeamsWidth, Spacing, TextSize,
+        TextWeight,
+    };
+    use crate::common::{
+        ActionMode, ActionStyle, AssociatedInputs, ChoiceInputStyle, Color, FontType, Height,
+        HorizontalAlignment, TextBlockStyle, TextInputStyle, VerticalContentAlignment,
+    };
+
+    macro_rules! keywords {
+        ($($keyword:ident => $($target:ident::$variant:ident),+;)*) => {
+            $(
+                #[doc = concat!("The `", stringify!($keyword), "` keyword.")]
+                #[derive(Debug, Clone, Copy)]
+                pub struct $keyword;
+
+                $(
+                    impl From<$keyword> for $target {
+                        fn from(_: $keyword) -> Self {
+                            $target::$variant
+                        }
+                    }
+                )+
+            )*
+        };
+    }
+
+    keywords! {
+        default => TextSize::Default, TextWeight::Default, ContainerStyle::Default,
+            Spacing::Default, Color::Default, FontType::Default, ActionStyle::Default,
+            TextBlockStyle::Default;
+        none => Spacing::None, AssociatedInputs::None;
+        small => TextSize::Small, Spacing::Small, ImageSize::Small;
+        medium => TextSize::Medium, Spacing::Medium, ImageSize::Medium;
+        large => TextSize::Large, Spacing::Large, ImageSize::Large;
+        extra_large => TextSize::ExtraLarge, Spacing::ExtraLarge;
+        padding => Spacing::Padding;
+        lighter => TextWeight::Lighter;
+        bolder => TextWeight::Bolder;
+        emphasis => ContainerStyle::Emphasis;
+        good => ContainerStyle::Good, Color::Good;
+        attention => ContainerStyle::Attention, Color::Attention;
+        warning => ContainerStyle::Warning, Color::Warning;
+        accent => ContainerStyle::Accent, Color::Accent;
+        dark => Color::Dark;
+        light => Color::Light;
+        auto => ImageSize::Auto, Height::Auto, AssociatedInputs::Auto;
+        stretch => ImageSize::Stretch, Height::Stretch;
+        left => HorizontalAlignment::Left;
+        center => HorizontalAlignment::Center, VerticalContentAlignment::Center;
+        right => HorizontalAlignment::Right;
+        top => VerticalContentAlignment::Top;
+        bottom => VerticalContentAlignment::Bottom;
+        monospace => FontType::Monospace;
+        heading => TextBlockStyle::Heading;
+        positive => ActionStyle::Positive;
+        destructive => ActionStyle::Destructive;
+        primary => ActionMode::Primary;
+        secondary => ActionMode::Secondary;
+        text => TextInputStyle::Text;
+        tel => TextInputStyle::Tel;
+        url => TextInputStyle::Url;
+        email => TextInputStyle::Email;
+        password => TextInputStyle::Password;
+        compact => ChoiceInputStyle::Compact;
+        expanded => ChoiceInputStyle::Expanded;
+        filtered => ChoiceInputStyle::Filtered;
+        full => MsTeamsWidth::Full;
+        cover => ImageFillMode::Cover;
+        repeat => ImageFillMode::Repeat;
+        repeat_horizontally => ImageFillMode::RepeatHorizontally;
+        repeat_vertically => ImageFillMode::RepeatVertically;
+    }
+
+    impl From<auto> for ColumnWidth {
+        fn from(_: auto) -> Self {
+            ColumnWidth::auto()
+        }
+    }
+
+    impl From<stretch> for ColumnWidth {
+        fn from(_: stretch) -> Self {
+            ColumnWidth::stretch()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::actions::{Action, SubmitAction};
+    use crate::card::{CardElement, ColumnWidth, Spacing, TextBlock};
+    use crate::common::Color;
+    use expect_test::expect;
+    use serde_json::json;
+
+    #[test]
+    fn test_card_macro_serialization() {
+        let env = "production";
+        let sha = "1a2b3c4";
+        let url = "https://ci.example.com/runs/42";
+        let label = "Why?";
+
+        let card = card! {
+            version: 1.5,
+            fallback_text: "Deploy finished",
+            TextBlock("Deploy finished", size: large, weight: bolder, wrap: true),
+            FactSet { "Env" => env, "SHA" => sha },
+            ColumnSet {
+                Column(width: auto) { Image("https://example.com/ok.png", size: small) },
+                Column(width: stretch, vertical_content_alignment: center) {
+                    TextBlock(format!("{sha} is live"), color: good),
+                },
+            },
+            Container(style: emphasis, id: "details", is_visible: false) {
+                InputText("reason", label: { label }, style: text, is_multiline: true),
+                InputChoiceSet("rating", style: expanded) { "Good" => "1", "Bad" => "0" },
+            },
+            actions: [
+                OpenUrl("Logs", url, style: positive),
+                ToggleVisibility("Details") { "details" },
+                ShowCard("Roll back") { TextBlock("Are you sure?"), actions: [Execute("Yes", verb: "rollback")] },
+            ],
+        };
+
+        assert!(matches!(card.body[2], CardElement::ColumnSet(_)));
+        assert!(matches!(
+            card.actions.as_deref(),
+            Some([Action::OpenUrl(_), ..])
+        ));
+
+        let expected = expect![[r#"
+            {
+              "type": "AdaptiveCard",
+              "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+              "version": "1.5",
+              "body": [
+                {
+                  "type": "TextBlock",
+                  "text": "Deploy finished",
+                  "size": "large",
+                  "weight": "bolder",
+                  "wrap": true
+                },
+                {
+                  "type": "FactSet",
+                  "facts": [
+                    {
+                      "title": "Env",
+                      "value": "production"
+                    },
+                    {
+                      "title": "SHA",
+                      "value": "1a2b3c4"
+                    }
+                  ]
+                },
+                {
+                  "type": "ColumnSet",
+                  "columns": [
+                    {
+                      "type": "Column",
+                      "width": "auto",
+                      "items": [
+                        {
+                          "type": "Image",
+                          "url": "https://example.com/ok.png",
+                          "size": "small"
+                        }
+                      ]
+                    },
+                    {
+                      "type": "Column",
+                      "width": "stretch",
+                      "items": [
+                        {
+                          "type": "TextBlock",
+                          "text": "1a2b3c4 is live",
+                          "color": "good"
+                        }
+                      ],
+                      "verticalContentAlignment": "center"
+                    }
+                  ]
+                },
+                {
+                  "type": "Container",
+                  "items": [
+                    {
+                      "type": "Input.Text",
+                      "id": "reason",
+                      "isMultiline": true,
+                      "style": "text",
+                      "label": "Why?"
+                    },
+                    {
+                      "type": "Input.ChoiceSet",
+                      "id": "rating",
+                      "choices": [
+                        {
+                          "title": "Good",
+                          "value": "1"
+                        },
+                        {
+                          "title": "Bad",
+                          "value": "0"
+                        }
+                      ],
+                      "style": "expanded"
+                    }
+                  ],
+                  "style": "emphasis",
+                  "id": "details",
+                  "isVisible": false
+                }
+              ],
+              "actions": [
+                {
+                  "type": "Action.OpenUrl",
+                  "title": "Logs",
+                  "url": "https://ci.example.com/runs/42",
+                  "style": "positive"
+                },
+                {
+                  "type": "Action.ToggleVisibility",
+                  "title": "Details",
+                  "targetElements": [
+                    "details"
+                  ]
+                },
+                {
+                  "type": "Action.ShowCard",
+                  "title": "Roll back",
+                  "card": {
+                    "type": "AdaptiveCard",
+                    "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                    "version": "1.2",
+                    "body": [
+                      {
+                        "type": "TextBlock",
+                        "text": "Are you sure?"
+                      }
+                    ],
+                    "actions": [
+                      {
+                        "type": "Action.Execute",
+                        "title": "Yes",
+                        "verb": "rollback"
+                      }
+                    ]
+                  }
+                }
+              ],
+              "fallbackText": "Deploy finished"
+            }"#]];
+        expected.assert_eq(&serde_json::to_string_pretty(&card).unwrap());
+    }
+
+    #[test]
+    fn test_nested_containers_and_columns() {
+        let card = card! {
+            Container(id: "outer") {
+                ColumnSet {
+                    Column(width: { ColumnWidth::weight(2) }) {
+                        Container(id: "inner", style: good) { TextBlock("Deep") },
+                        ColumnSet { Column { TextBlock("Deeper") } },
+                    },
+                    Column(width: { ColumnWidth::pixels(80) }) {
+                        ImageSet { "https://example.com/a.png", "https://example.com/b.png" },
+                    },
+                },
+            },
+        };
+
+        let value = serde_json::to_value(&card).unwrap();
+        let columns = &value["body"][0]["items"][0]["columns"];
+        assert_eq!(columns[0]["width"], 2);
+        assert_eq!(columns[0]["items"][0]["id"], "inner");
+        assert_eq!(columns[0]["items"][0]["style"], "good");
+        assert_eq!(columns[0]["items"][0]["items"][0]["text"], "Deep");
+        assert_eq!(
+            columns[0]["items"][1]["columns"][0]["items"][0]["text"],
+            "Deeper"
+        );
+        assert_eq!(columns[1]["width"], "80px");
+        assert_eq!(
+            columns[1]["items"][0]["images"][1]["url"],
+            "https://example.com/b.png"
+        );
+    }
+
+    #[test]
+    fn test_actions() {
+        let card = card! {
+            select_action: OpenUrl("Open", "https://example.com"),
+            ActionSet {
+                Submit("Save", data: json!({ "kind": "save" }), style: destructive),
+                { SubmitAction::builder().title("Built elsewhere") },
+            },
+            actions: [
+                Execute("Approve", verb: "approve", style: positive),
+                ShowCard("More") {
+                    TextBlock("Nested"),
+                    actions: [ToggleVisibility("Toggle") { "a", "b" }],
+                },
+            ],
+        };
+
+        let value = serde_json::to_value(&card).unwrap();
+        assert_eq!(
+            value["selectAction"],
+            json!({ "type": "Action.OpenUrl", "title": "Open", "url": "https://example.com" })
+        );
+        assert_eq!(
+            value["body"][0]["actions"],
+            json!([
+                {
+                    "type": "Action.Submit",
+                    "title": "Save",
+                    "data": { "kind": "save" },
+                    "style": "destructive"
+                },
+                { "type": "Action.Submit", "title": "Built elsewhere" },
+            ])
+        );
+        assert_eq!(
+            value["actions"][0],
+            json!({
+                "type": "Action.Execute",
+                "title": "Approve",
+                "verb": "approve",
+                "style": "positive"
+            })
+        );
+        let show_card = &value["actions"][1]["card"];
+        assert_eq!(show_card["body"][0]["text"], "Nested");
+        assert_eq!(show_card["actions"][0]["targetElements"], json!(["a", "b"]));
+    }
+
+    #[test]
+    fn test_expression_values() {
+        let title = String::from("Status");
+        let color = Color::Attention;
+        let visible = false;
+        let spacing = Spacing::Large;
+        let rows = [("CPU", "92%"), ("Disk", "40%")];
+
+        let card = card! {
+            fallback_text: { format!("{title} report") },
+            TextBlock({ title.clone() }, color: { color }, is_visible: { visible }, spacing: { spacing }),
+            FactSet { rows[0].0 => rows[0].1, rows[1].0 => format!("{} free", rows[1].1) },
+            { TextBlock::builder("Built elsewhere").wrap(true) },
+        };
+
+        let value = serde_json::to_value(&card).unwrap();
+        assert_eq!(value["fallbackText"], "Status report");
+        assert_eq!(
+            value["body"][0],
+            json!({
+                "type": "TextBlock",
+                "text": "Status",
+                "color": "attention",
+                "isVisible": false,
+                "spacing": "large"
+            })
+        );
+        assert_eq!(value["body"][1]["facts"][1]["value"], "40% free");
+        assert!(matches!(&card.body[2], CardElement::TextBlock(t) if t.wrap == Some(true)));
+    }
+}
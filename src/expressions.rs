@@ -0,0 +1,1376 @@
+//! Parser and evaluator for the Adaptive Expressions language used by card templates.
+//!
+//! Expressions are evaluated against a [`serde_json::Value`] and can be used on their own,
+//! without rendering a card:
+//!
+//! ```
+//! use adaptive_card_rs::expressions::{Evaluator, Expr};
+//! use serde_json::json;
+//!
+//! let expr: Expr = "if(count(items) > 1, join(items, ', '), 'a single item')"
+//!     .parse()
+//!     .unwrap();
+//! let value = Evaluator::new()
+//!     .evaluate(&expr, &json!({ "items": ["tea", "cake"] }))
+//!     .unwrap();
+//! assert_eq!(value, json!("tea, cake"));
+//! ```
+//!
+//! Functions that are not built in can be registered on an [`Evaluator`]:
+//!
+//! ```
+//! use adaptive_card_rs::expressions::{Evaluator, Expr};
+//! use serde_json::json;
+//!
+//! let evaluator = Evaluator::new().with_function("double", |args| {
+//!     Ok(json!(args[0].as_i64().unwrap_or_default() * 2))
+//! });
+//! let expr: Expr = "double(price)".parse().unwrap();
+//! assert_eq!(evaluator.evaluate(&expr, &json!({ "price": 4 })).unwrap(), json!(8));
+//! ```
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use serde_json::{Number, Value};
+
+/// A parsed expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// A literal string, number, boolean or null.
+    Literal(Value),
+    /// A bare identifier such as `name` or `$root`.
+    Identifier(String),
+    /// Property access with `.`, e.g. `user.name`.
+    Member(Box<Expr>, String),
+    /// Indexed access with `[]`, e.g. `items[0]` or `user['name']`.
+    Index(Box<Expr>, Box<Expr>),
+    /// A function call, e.g. `if(done, 'yes', 'no')`.
+    Call(String, Vec<Expr>),
+    /// A prefix operator applied to an operand.
+    Unary(UnaryOp, Box<Expr>),
+    /// An infix operator applied to two operands.
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Parses an expression, e.g. `user.name` or `count(items) > 0`.
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            end: source.len(),
+        };
+        let expr = parser.expression(0)?;
+        match parser.next() {
+            None => Ok(expr),
+            Some((position, token)) => {
+                Err(ParseError::new(format!("unexpected {token}"), position))
+            }
+        }
+    }
+}
+
+impl FromStr for Expr {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// A prefix operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    /// `!`
+    Not,
+    /// `-`
+    Negate,
+    /// `+`
+    Plus,
+}
+
+/// An infix operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    /// `||`
+    Or,
+    /// `&&`
+    And,
+    /// `==`
+    Equal,
+    /// `!=` or `<>`
+    NotEqual,
+    /// `<`
+    Less,
+    /// `<=`
+    LessOrEqual,
+    /// `>`
+    Greater,
+    /// `>=`
+    GreaterOrEqual,
+    /// `&`, which always concatenates its operands as strings.
+    Concat,
+    /// `+`, which concatenates when either operand is a string.
+    Add,
+    /// `-`
+    Subtract,
+    /// `*`
+    Multiply,
+    /// `/`
+    Divide,
+    /// `%`
+    Modulo,
+    /// `^`
+    Power,
+}
+
+/// An error raised while parsing an expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// What went wrong.
+    pub message: String,
+    /// The byte offset in the source where the problem was found.
+    pub position: usize,
+}
+
+impl ParseError {
+    fn new(message: impl Into<String>, position: usize) -> Self {
+        Self {
+            message: message.into(),
+            position,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// An error raised while evaluating an expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvalError {
+    /// What went wrong.
+    pub message: String,
+}
+
+impl EvalError {
+    /// Creates an error, e.g. from a custom function.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+/// The data an expression is evaluated against.
+#[derive(Debug, Clone, Copy)]
+pub struct Scope<'a> {
+    /// The current data context, also available as `$data`.
+    pub data: &'a Value,
+    /// The root data context, available as `$root`.
+    pub root: &'a Value,
+    /// The position of the current item when repeating over an array, available as `$index`.
+    pub index: Option<usize>,
+}
+
+impl<'a> Scope<'a> {
+    /// Creates a scope where `data` is both the current and the root data context.
+    pub fn new(data: &'a Value) -> Self {
+        Self {
+            data,
+            root: data,
+            index: None,
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Value {
+        match name {
+            "$data" => self.data.clone(),
+            "$root" => self.root.clone(),
+            "$index" => self.index.map_or(Value::Null, |i| Value::from(i as u64)),
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            "null" => Value::Null,
+            name => self.data.get(name).cloned().unwrap_or(Value::Null),
+        }
+    }
+}
+
+type Function = Arc<dyn Fn(&[Value]) -> Result<Value, EvalError> + Send + Sync>;
+
+/// Evaluates expressions using the built-in functions and any registered custom functions.
+///
+/// Custom functions take precedence over built-in functions of the same name.
+#[derive(Clone, Default)]
+pub struct Evaluator {
+    functions: HashMap<String, Function>,
+}
+
+impl fmt::Debug for Evaluator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names: Vec<_> = self.functions.keys().collect();
+        names.sort();
+        f.debug_struct("Evaluator")
+            .field("functions", &names)
+            .finish()
+    }
+}
+
+impl Evaluator {
+    /// Creates an evaluator with only the built-in functions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a custom function.
+    pub fn with_function(
+        mut self,
+        name: impl Into<String>,
+        function: impl Fn(&[Value]) -> Result<Value, EvalError> + Send + Sync + 'static,
+    ) -> Self {
+        self.functions.insert(name.into(), Arc::new(function));
+        self
+    }
+
+    /// Evaluates an expression against `data`. Properties that do not exist evaluate to `null`.
+    pub fn evaluate(&self, expr: &Expr, data: &Value) -> Result<Value, EvalError> {
+        self.evaluate_in(expr, &Scope::new(data))
+    }
+
+    /// Evaluates an expression in a scope that may differ from the root data context.
+    pub fn evaluate_in(&self, expr: &Expr, scope: &Scope<'_>) -> Result<Value, EvalError> {
+        match expr {
+            Expr::Literal(value) => Ok(value.clone()),
+            Expr::Identifier(name) => Ok(scope.lookup(name)),
+            Expr::Member(target, name) => Ok(member(&self.evaluate_in(target, scope)?, name)),
+            Expr::Index(target, index) => {
+                let target = self.evaluate_in(target, scope)?;
+                Ok(match self.evaluate_in(index, scope)? {
+                    Value::String(name) => member(&target, &name),
+                    Value::Number(n) => n
+                        .as_u64()
+                        .and_then(|i| target.get(i as usize))
+                        .cloned()
+                        .unwrap_or(Value::Null),
+                    _ => Value::Null,
+                })
+            }
+            Expr::Call(name, args) => self.call(name, args, scope),
+            Expr::Unary(op, operand) => {
+                let value = self.evaluate_in(operand, scope)?;
+                match op {
+                    UnaryOp::Not => Ok(Value::Bool(!is_truthy(&value))),
+                    UnaryOp::Negate => arithmetic(BinaryOp::Subtract, &Value::from(0), &value),
+                    UnaryOp::Plus => arithmetic(BinaryOp::Add, &Value::from(0), &value),
+                }
+            }
+            Expr::Binary(BinaryOp::And, left, right) => Ok(Value::Bool(
+                is_truthy(&self.evaluate_in(left, scope)?)
+                    && is_truthy(&self.evaluate_in(right, scope)?),
+            )),
+            Expr::Binary(BinaryOp::Or, left, right) => Ok(Value::Bool(
+                is_truthy(&self.evaluate_in(left, scope)?)
+                    || is_truthy(&self.evaluate_in(right, scope)?),
+            )),
+            Expr::Binary(op, left, right) => binary(
+                *op,
+                &self.evaluate_in(left, scope)?,
+                &self.evaluate_in(right, scope)?,
+            ),
+        }
+    }
+
+    fn call(&self, name: &str, args: &[Expr], scope: &Scope<'_>) -> Result<Value, EvalError> {
+        // `if` only evaluates the branch it takes.
+        if name == "if" && !self.functions.contains_key(name) {
+            let [condition, then, otherwise] = args else {
+                return Err(EvalError::new("if() expects 3 arguments"));
+            };
+            return if is_truthy(&self.evaluate_in(condition, scope)?) {
+                self.evaluate_in(then, scope)
+            } else {
+                self.evaluate_in(otherwise, scope)
+            };
+        }
+
+        let args = args
+            .iter()
+            .map(|arg| self.evaluate_in(arg, scope))
+            .collect::<Result<Vec<_>, _>>()?;
+        match self.functions.get(name) {
+            Some(function) => function(&args),
+            None => builtin(name, &args),
+        }
+    }
+}
+
+/// Returns false for `false` and `null`, true for everything else.
+pub fn is_truthy(value: &Value) -> bool {
+    !matches!(value, Value::Null | Value::Bool(false))
+}
+
+/// Converts a value to the text used when it is interpolated into a string.
+pub fn to_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn member(target: &Value, name: &str) -> Value {
+    target.get(name).cloned().unwrap_or(Value::Null)
+}
+
+const BUILTINS: &[&str] = &[
+    "if",
+    "not",
+    "and",
+    "or",
+    "equals",
+    "exists",
+    "empty",
+    "count",
+    "length",
+    "concat",
+    "join",
+    "contains",
+    "first",
+    "last",
+    "createArray",
+    "toUpper",
+    "toLower",
+    "trim",
+    "substring",
+    "replace",
+    "startsWith",
+    "endsWith",
+    "string",
+    "int",
+    "float",
+    "bool",
+    "add",
+    "sub",
+    "mul",
+    "div",
+    "mod",
+    "min",
+    "max",
+    "round",
+    "floor",
+    "ceiling",
+    "formatNumber",
+    "formatDateTime",
+    "ticks",
+];
+
+fn builtin(name: &str, args: &[Value]) -> Result<Value, EvalError> {
+    match (name, args) {
+        ("not", [value]) => Ok(Value::Bool(!is_truthy(value))),
+        ("and", values) => Ok(Value::Bool(values.iter().all(is_truthy))),
+        ("or", values) => Ok(Value::Bool(values.iter().any(is_truthy))),
+        ("equals", [left, right]) => Ok(Value::Bool(equals(left, right))),
+        ("exists", [value]) => Ok(Value::Bool(!value.is_null())),
+        ("empty", [value]) => Ok(Value::Bool(match value {
+            Value::Null => true,
+            Value::String(s) => s.is_empty(),
+            Value::Array(items) => items.is_empty(),
+            Value::Object(object) => object.is_empty(),
+            _ => false,
+        })),
+        ("count" | "length", [value]) => match value {
+            Value::String(s) => Ok(Value::from(s.chars().count() as u64)),
+            Value::Array(items) => Ok(Value::from(items.len() as u64)),
+            Value::Object(object) => Ok(Value::from(object.len() as u64)),
+            _ => Err(EvalError::new(format!(
+                "{name}() expects a string or an array"
+            ))),
+        },
+        ("concat", values) => {
+            if values.iter().all(Value::is_array) {
+                Ok(Value::Array(
+                    values
+                        .iter()
+                        .flat_map(|v| v.as_array().cloned().unwrap_or_default())
+                        .collect(),
+                ))
+            } else {
+                Ok(Value::String(values.iter().map(to_text).collect()))
+            }
+        }
+        ("join", [Value::Array(items), Value::String(separator)]) => Ok(Value::String(
+            items
+                .iter()
+                .map(to_text)
+                .collect::<Vec<_>>()
+                .join(separator),
+        )),
+        (
+            "join",
+            [
+                Value::Array(items),
+                Value::String(separator),
+                Value::String(last),
+            ],
+        ) => {
+            let items: Vec<_> = items.iter().map(to_text).collect();
+            Ok(Value::String(match items.split_last() {
+                Some((tail, init)) if !init.is_empty() => init.join(separator) + last + tail,
+                _ => items.concat(),
+            }))
+        }
+        ("contains", [Value::String(s), value]) => Ok(Value::Bool(s.contains(&to_text(value)))),
+        ("contains", [Value::Array(items), value]) => {
+            Ok(Value::Bool(items.iter().any(|item| equals(item, value))))
+        }
+        ("contains", [Value::Object(object), Value::String(key)]) => {
+            Ok(Value::Bool(object.contains_key(key)))
+        }
+        ("first", [Value::Array(items)]) => Ok(items.first().cloned().unwrap_or(Value::Null)),
+        ("first", [Value::String(s)]) => Ok(s
+            .chars()
+            .next()
+            .map_or(Value::Null, |c| Value::String(c.to_string()))),
+        ("last", [Value::Array(items)]) => Ok(items.last().cloned().unwrap_or(Value::Null)),
+        ("last", [Value::String(s)]) => Ok(s
+            .chars()
+            .next_back()
+            .map_or(Value::Null, |c| Value::String(c.to_string()))),
+        ("createArray", values) => Ok(Value::Array(values.to_vec())),
+        ("toUpper", [value]) => Ok(Value::String(to_text(value).to_uppercase())),
+        ("toLower", [value]) => Ok(Value::String(to_text(value).to_lowercase())),
+        ("trim", [value]) => Ok(Value::String(to_text(value).trim().to_string())),
+        ("substring", [Value::String(s), start]) => substring(s, start, None),
+        ("substring", [Value::String(s), start, length]) => substring(s, start, Some(length)),
+        ("replace", [Value::String(s), Value::String(old), new]) => {
+            if old.is_empty() {
+                return Err(EvalError::new("replace() cannot replace an empty string"));
+            }
+            Ok(Value::String(s.replace(old.as_str(), &to_text(new))))
+        }
+        ("startsWith", [Value::String(s), prefix]) => {
+            Ok(Value::Bool(s.starts_with(&to_text(prefix))))
+        }
+        ("endsWith", [Value::String(s), suffix]) => Ok(Value::Bool(s.ends_with(&to_text(suffix)))),
+        ("string", [value]) => Ok(Value::String(to_text(value))),
+        ("int", [value]) => {
+            let number = match value {
+                Value::String(s) => s.trim().parse::<f64>().ok(),
+                value => value.as_f64(),
+            };
+            number
+                .filter(|n| n.is_finite())
+                .map(|n| Value::from(n.trunc() as i64))
+                .ok_or_else(|| EvalError::new(format!("cannot convert {value} to an integer")))
+        }
+        ("float", [value]) => {
+            let number = match value {
+                Value::String(s) => s.trim().parse::<f64>().ok(),
+                value => value.as_f64(),
+            };
+            number
+                .and_then(Number::from_f64)
+                .map(Value::Number)
+                .ok_or_else(|| EvalError::new(format!("cannot convert {value} to a number")))
+        }
+        ("bool", [value]) => Ok(Value::Bool(match value {
+            Value::String(s) => !s.eq_ignore_ascii_case("false") && !s.is_empty(),
+            Value::Number(n) => n.as_f64() != Some(0.0),
+            value => is_truthy(value),
+        })),
+        ("add", [left, right]) => binary(BinaryOp::Add, left, right),
+        ("sub", [left, right]) => arithmetic(BinaryOp::Subtract, left, right),
+        ("mul", [left, right]) => arithmetic(BinaryOp::Multiply, left, right),
+        ("div", [left, right]) => arithmetic(BinaryOp::Divide, left, right),
+        ("mod", [left, right]) => arithmetic(BinaryOp::Modulo, left, right),
+        ("min" | "max", values) if !values.is_empty() => {
+            let values = match values {
+                [Value::Array(items)] => items.as_slice(),
+                values => values,
+            };
+            let mut best: Option<&Value> = None;
+            for value in values {
+                let number = value
+                    .as_f64()
+                    .ok_or_else(|| EvalError::new(format!("{name}() expects numbers")))?;
+                let better = best.and_then(Value::as_f64).is_none_or(|best| {
+                    if name == "min" {
+                        number < best
+                    } else {
+                        number > best
+                    }
+                });
+                if better {
+                    best = Some(value);
+                }
+            }
+            Ok(best.cloned().unwrap_or(Value::Null))
+        }
+        ("round", [value]) => round(value, 0),
+        ("round", [value, Value::Number(digits)]) => {
+            round(value, digits.as_u64().unwrap_or_default() as i32)
+        }
+        ("floor" | "ceiling", [value]) => {
+            let number = value
+                .as_f64()
+                .ok_or_else(|| EvalError::new(format!("{name}() expects a number")))?;
+            let result = if name == "floor" {
+                number.floor()
+            } else {
+                number.ceil()
+            };
+            Ok(Value::from(result as i64))
+        }
+        ("formatNumber", [value, Value::Number(precision)]) => {
+            format_number(value, precision, "en-US")
+        }
+        ("formatNumber", [value, Value::Number(precision), Value::String(locale)]) => {
+            format_number(value, precision, locale)
+        }
+        ("formatDateTime", [Value::String(timestamp)]) => {
+            format_date_time(timestamp, "yyyy-MM-ddTHH:mm:ss.fffZ")
+        }
+        ("formatDateTime", [Value::String(timestamp), Value::String(format)]) => {
+            format_date_time(timestamp, format)
+        }
+        ("ticks", [Value::String(timestamp)]) => ticks(timestamp),
+        (name, args) if BUILTINS.contains(&name) => Err(EvalError::new(format!(
+            "{name}() does not accept arguments ({})",
+            args.iter()
+                .map(Value::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+        (name, _) => Err(EvalError::new(format!("unknown function {name}()"))),
+    }
+}
+
+fn substring(s: &str, start: &Value, length: Option<&Value>) -> Result<Value, EvalError> {
+    let start = start
+        .as_u64()
+        .ok_or_else(|| EvalError::new("substring() expects a non-negative start index"))?
+        as usize;
+    let chars: Vec<char> = s.chars().collect();
+    let end = match length {
+        Some(length) => start.saturating_add(
+            length
+                .as_u64()
+                .ok_or_else(|| EvalError::new("substring() expects a non-negative length"))?
+                as usize,
+        ),
+        None => chars.len(),
+    };
+    if start > chars.len() || end > chars.len() {
+        return Err(EvalError::new(format!(
+            "substring() range {start}..{end} is out of bounds for a string of length {}",
+            chars.len()
+        )));
+    }
+    Ok(Value::String(chars[start..end].iter().collect()))
+}
+
+fn round(value: &Value, digits: i32) -> Result<Value, EvalError> {
+    let number = value
+        .as_f64()
+        .ok_or_else(|| EvalError::new("round() expects a number"))?;
+    if digits == 0 {
+        return Ok(Value::from(number.round() as i64));
+    }
+    let factor = 10f64.powi(digits);
+    Number::from_f64((number * factor).round() / factor)
+        .map(Value::Number)
+        .ok_or_else(|| EvalError::new("round() result is not a finite number"))
+}
+
+fn binary(op: BinaryOp, left: &Value, right: &Value) -> Result<Value, EvalError> {
+    match op {
+        BinaryOp::Equal => Ok(Value::Bool(equals(left, right))),
+        BinaryOp::NotEqual => Ok(Value::Bool(!equals(left, right))),
+        BinaryOp::Less | BinaryOp::LessOrEqual | BinaryOp::Greater | BinaryOp::GreaterOrEqual => {
+            let ordering = match (left, right) {
+                (Value::String(l), Value::String(r)) => l.partial_cmp(r),
+                _ => left
+                    .as_f64()
+                    .zip(right.as_f64())
+                    .and_then(|(l, r)| l.partial_cmp(&r)),
+            }
+            .ok_or_else(|| EvalError::new(format!("cannot compare {left} with {right}")))?;
+            Ok(Value::Bool(match op {
+                BinaryOp::Less => ordering.is_lt(),
+                BinaryOp::LessOrEqual => ordering.is_le(),
+                BinaryOp::Greater => ordering.is_gt(),
+                _ => ordering.is_ge(),
+            }))
+        }
+        BinaryOp::Concat => Ok(Value::String(to_text(left) + &to_text(right))),
+        BinaryOp::Add if left.is_string() || right.is_string() => {
+            Ok(Value::String(to_text(left) + &to_text(right)))
+        }
+        _ => arithmetic(op, left, right),
+    }
+}
+
+fn arithmetic(op: BinaryOp, left: &Value, right: &Value) -> Result<Value, EvalError> {
+    if let (Some(l), Some(r)) = (left.as_i64(), right.as_i64()) {
+        let result = match op {
+            BinaryOp::Add => l.checked_add(r),
+            BinaryOp::Subtract => l.checked_sub(r),
+            BinaryOp::Multiply => l.checked_mul(r),
+            BinaryOp::Divide | BinaryOp::Modulo if r == 0 => {
+                return Err(EvalError::new("division by zero"));
+            }
+            BinaryOp::Divide => l.checked_div(r),
+            BinaryOp::Modulo => l.checked_rem(r),
+            BinaryOp::Power => u32::try_from(r).ok().and_then(|r| l.checked_pow(r)),
+            _ => None,
+        };
+        if let Some(result) = result {
+            return Ok(Value::from(result));
+        }
+    }
+
+    let (Some(l), Some(r)) = (left.as_f64(), right.as_f64()) else {
+        return Err(EvalError::new(format!(
+            "arithmetic requires numbers, got {left} and {right}"
+        )));
+    };
+    let result = match op {
+        BinaryOp::Add => l + r,
+        BinaryOp::Subtract => l - r,
+        BinaryOp::Multiply => l * r,
+        BinaryOp::Divide => l / r,
+        BinaryOp::Modulo => l % r,
+        BinaryOp::Power => l.powf(r),
+        _ => unreachable!("not an arithmetic operator"),
+    };
+    Number::from_f64(result)
+        .map(Value::Number)
+        .ok_or_else(|| EvalError::new("arithmetic result is not a finite number"))
+}
+
+fn equals(left: &Value, right: &Value) -> bool {
+    match (left.as_f64(), right.as_f64()) {
+        (Some(l), Some(r)) => l == r,
+        _ => left == right,
+    }
+}
+
+/// Formats a number with a fixed number of decimals and the locale's digit grouping.
+fn format_number(value: &Value, precision: &Number, locale: &str) -> Result<Value, EvalError> {
+    let number = value
+        .as_f64()
+        .ok_or_else(|| EvalError::new(format!("formatNumber() expects a number, got {value}")))?;
+    let precision = precision
+        .as_u64()
+        .filter(|p| *p <= 20)
+        .ok_or_else(|| EvalError::new("formatNumber() expects a precision between 0 and 20"))?
+        as usize;
+
+    let (group, decimal) = match locale
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+        .as_str()
+    {
+        "de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" => (".", ","),
+        "fr" | "ru" | "pl" | "cs" | "sv" | "nb" | "fi" | "uk" => ("\u{a0}", ","),
+        _ => (",", "."),
+    };
+
+    let fixed = format!("{:.*}", precision, number.abs());
+    let (integer, fraction) = fixed.split_once('.').unwrap_or((&fixed, ""));
+    let mut formatted = String::new();
+    if number < 0.0 && fixed.bytes().any(|b| matches!(b, b'1'..=b'9')) {
+        formatted.push('-');
+    }
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            formatted.push_str(group);
+        }
+        formatted.push(digit);
+    }
+    if !fraction.is_empty() {
+        formatted.push_str(decimal);
+        formatted.push_str(fraction);
+    }
+    Ok(Value::String(formatted))
+}
+
+/// Formats an ISO 8601 timestamp, converted to UTC, with a .NET style format string such as
+/// `dd/MM/yyyy HH:mm` or `dddd, MMMM d`. Day and month names are in English.
+fn format_date_time(timestamp: &str, format: &str) -> Result<Value, EvalError> {
+    let parsed = Timestamp::parse(timestamp)
+        .ok_or_else(|| EvalError::new(format!("'{timestamp}' is not an ISO 8601 timestamp")))?;
+    let days = parsed.seconds.div_euclid(86_400);
+    let (year, month, day) = civil_from_days(days);
+    let seconds_of_day = parsed.seconds.rem_euclid(86_400);
+    let (hour, minute, second) = (
+        seconds_of_day / 3600,
+        seconds_of_day / 60 % 60,
+        seconds_of_day % 60,
+    );
+    let hour12 = if hour % 12 == 0 { 12 } else { hour % 12 };
+    // 1970-01-01 was a Thursday.
+    let weekday = (days + 4).rem_euclid(7) as usize;
+
+    let mut formatted = String::new();
+    let mut chars = format.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\'' || c == '"' {
+            formatted.extend(chars.by_ref().take_while(|&ch| ch != c));
+            continue;
+        }
+        if c == '\\' {
+            formatted.extend(chars.next());
+            continue;
+        }
+        if !"yMdHhmsft".contains(c) {
+            formatted.push(c);
+            continue;
+        }
+
+        let mut count = 1;
+        while chars.next_if_eq(&c).is_some() {
+            count += 1;
+        }
+        let part = match (c, count) {
+            ('y', 1) => (year % 100).to_string(),
+            ('y', 2) => format!("{:02}", year % 100),
+            ('y', n) => format!("{year:0n$}"),
+            ('M', 1) => month.to_string(),
+            ('M', 2) => format!("{month:02}"),
+            ('M', 3) => MONTHS[month as usize - 1][..3].to_string(),
+            ('M', _) => MONTHS[month as usize - 1].to_string(),
+            ('d', 1) => day.to_string(),
+            ('d', 2) => format!("{day:02}"),
+            ('d', 3) => DAYS[weekday][..3].to_string(),
+            ('d', _) => DAYS[weekday].to_string(),
+            ('H', 1) => hour.to_string(),
+            ('H', _) => format!("{hour:02}"),
+            ('h', 1) => hour12.to_string(),
+            ('h', _) => format!("{hour12:02}"),
+            ('m', 1) => minute.to_string(),
+            ('m', _) => format!("{minute:02}"),
+            ('s', 1) => second.to_string(),
+            ('s', _) => format!("{second:02}"),
+            ('f', n) => format!("{:09}", parsed.nanos)[..n.min(9)].to_string(),
+            ('t', 1) => if hour < 12 { "A" } else { "P" }.to_string(),
+            _ => if hour < 12 { "AM" } else { "PM" }.to_string(),
+        };
+        formatted.push_str(&part);
+    }
+    Ok(Value::String(formatted))
+}
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const DAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// Returns the number of 100 nanosecond intervals between 0001-01-01T00:00:00Z and an ISO 8601
+/// timestamp, matching .NET's `DateTime.Ticks`.
+fn ticks(timestamp: &str) -> Result<Value, EvalError> {
+    let parsed = Timestamp::parse(timestamp)
+        .ok_or_else(|| EvalError::new(format!("'{timestamp}' is not an ISO 8601 timestamp")))?;
+    let seconds = parsed.seconds - days_from_civil(1, 1, 1) * 86_400;
+    Ok(Value::from(
+        seconds * 10_000_000 + i64::from(parsed.nanos / 100),
+    ))
+}
+
+/// A point in time as seconds and nanoseconds since the Unix epoch.
+struct Timestamp {
+    seconds: i64,
+    nanos: u32,
+}
+
+impl Timestamp {
+    /// Parses `YYYY-MM-DD`, optionally followed by a time and a `Z` or `±HH:MM` offset.
+    fn parse(s: &str) -> Option<Self> {
+        fn number(s: &str) -> Option<i64> {
+            if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            s.parse().ok()
+        }
+        fn two_digits(s: Option<&str>) -> Option<i64> {
+            s.filter(|s| s.len() == 2).and_then(number)
+        }
+
+        let (date, time) = match s.find(['T', 't', ' ']) {
+            Some(i) => (&s[..i], Some(&s[i + 1..])),
+            None => (s, None),
+        };
+        let mut parts = date.splitn(3, '-');
+        let year = parts.next().filter(|p| p.len() == 4).and_then(number)?;
+        let month = two_digits(parts.next())? as u32;
+        let day = two_digits(parts.next())? as u32;
+        if !(1..=12).contains(&month) {
+            return None;
+        }
+        let days = days_from_civil(year, month, day);
+        if civil_from_days(days) != (year, month, day) {
+            return None;
+        }
+
+        let mut seconds = days * 86_400;
+        let mut nanos = 0;
+        if let Some(time) = time {
+            let (time, offset) = match time.find(['Z', 'z', '+', '-']) {
+                Some(i) => (&time[..i], &time[i..]),
+                None => (time, ""),
+            };
+            let (time, fraction) = time.split_once('.').unwrap_or((time, ""));
+            let mut parts = time.split(':');
+            let hour = two_digits(parts.next())?;
+            let minute = two_digits(parts.next())?;
+            let second = match parts.next() {
+                Some(p) => two_digits(Some(p))?,
+                None => 0,
+            };
+            if parts.next().is_some() || hour > 23 || minute > 59 || second > 59 {
+                return None;
+            }
+            if !fraction.is_empty() {
+                if !fraction.bytes().all(|b| b.is_ascii_digit()) {
+                    return None;
+                }
+                let digits = &fraction[..fraction.len().min(9)];
+                nanos = (number(digits)? * 10i64.pow(9 - digits.len() as u32)) as u32;
+            }
+            seconds += hour * 3600 + minute * 60 + second;
+
+            if !matches!(offset, "" | "Z" | "z") {
+                let sign = if offset.starts_with('-') { -1 } else { 1 };
+                let offset = offset[1..].replace(':', "");
+                if offset.len() != 4 {
+                    return None;
+                }
+                let hours = number(offset.get(..2)?)?;
+                let minutes = number(offset.get(2..)?)?;
+                seconds -= sign * (hours * 3600 + minutes * 60);
+            }
+        }
+
+        Some(Self { seconds, nanos })
+    }
+}
+
+/// Returns the number of days since 1970-01-01 for a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The inverse of [`days_from_civil`].
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(Number),
+    String(String),
+    Identifier(String),
+    Symbol(&'static str),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Number(n) => write!(f, "number {n}"),
+            Token::String(s) => write!(f, "string '{s}'"),
+            Token::Identifier(name) => write!(f, "identifier {name}"),
+            Token::Symbol(symbol) => write!(f, "'{symbol}'"),
+        }
+    }
+}
+
+const SYMBOLS: &[&str] = &[
+    "==", "!=", "<>", "<=", ">=", "&&", "||", "<", ">", "!", "+", "-", "*", "/", "%", "^", "&",
+    "(", ")", "[", "]", ",", ".",
+];
+
+/// Splits the source into tokens, each paired with its byte offset.
+fn tokenize(source: &str) -> Result<Vec<(usize, Token)>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                if c.is_ascii_digit() || c == '.' {
+                    end = i + c.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            let text = &source[start..end];
+            let number = match text.parse::<i64>() {
+                Ok(n) => Number::from(n),
+                Err(_) => text
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(Number::from_f64)
+                    .ok_or_else(|| ParseError::new(format!("invalid number {text}"), start))?,
+            };
+            tokens.push((start, Token::Number(number)));
+        } else if c == '\'' || c == '"' {
+            chars.next();
+            let mut text = String::new();
+            loop {
+                match chars.next() {
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, 'n')) => text.push('\n'),
+                        Some((_, 't')) => text.push('\t'),
+                        Some((_, escaped)) => text.push(escaped),
+                        None => return Err(ParseError::new("unterminated string", start)),
+                    },
+                    Some((_, ch)) if ch == c => break,
+                    Some((_, ch)) => text.push(ch),
+                    None => return Err(ParseError::new("unterminated string", start)),
+                }
+            }
+            tokens.push((start, Token::String(text)));
+        } else if c.is_alphabetic() || c == '_' || c == '$' || c == '@' {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                if c.is_alphanumeric() || c == '_' || c == '$' || c == '@' {
+                    end = i + c.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push((start, Token::Identifier(source[start..end].to_string())));
+        } else {
+            let rest = &source[start..];
+            let symbol = SYMBOLS
+                .iter()
+                .find(|symbol| rest.starts_with(**symbol))
+                .ok_or_else(|| ParseError::new(format!("unexpected character '{c}'"), start))?;
+            for _ in 0..symbol.len() {
+                chars.next();
+            }
+            tokens.push((start, Token::Symbol(symbol)));
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    /// The length of the source, reported as the position of errors at the end.
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, token)| token)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens.get(self.pos).map_or(self.end, |(i, _)| *i)
+    }
+
+    fn next(&mut self) -> Option<(usize, Token)> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, symbol: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Symbol(s)) if *s == symbol) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<(), ParseError> {
+        if self.eat(symbol) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("'{symbol}'")))
+        }
+    }
+
+    /// Reports that the current token is not what the parser `expected`.
+    fn unexpected(&self, expected: &str) -> ParseError {
+        let found = match self.peek() {
+            Some(token) => token.to_string(),
+            None => "end of expression".to_string(),
+        };
+        ParseError::new(
+            format!("expected {expected} but found {found}"),
+            self.position(),
+        )
+    }
+
+    /// Parses an expression whose operators all bind tighter than `min_precedence`.
+    fn expression(&mut self, min_precedence: u8) -> Result<Expr, ParseError> {
+        let mut left = self.unary()?;
+        while let Some(Token::Symbol(symbol)) = self.peek() {
+            let Some((op, precedence)) = binary_operator(symbol) else {
+                break;
+            };
+            if precedence < min_precedence {
+                break;
+            }
+            self.pos += 1;
+            // `^` is right associative, everything else is left associative.
+            let next = if op == BinaryOp::Power {
+                precedence
+            } else {
+                precedence + 1
+            };
+            let right = self.expression(next)?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, ParseError> {
+        let op = if self.eat("!") {
+            UnaryOp::Not
+        } else if self.eat("-") {
+            UnaryOp::Negate
+        } else if self.eat("+") {
+            UnaryOp::Plus
+        } else {
+            return self.postfix();
+        };
+        Ok(Expr::Unary(op, Box::new(self.unary()?)))
+    }
+
+    fn postfix(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.primary()?;
+        loop {
+            if self.eat(".") {
+                let position = self.position();
+                match self.next() {
+                    Some((_, Token::Identifier(name))) => expr = Expr::Member(Box::new(expr), name),
+                    Some((_, token)) => {
+                        return Err(ParseError::new(
+                            format!("expected property name after '.' but found {token}"),
+                            position,
+                        ));
+                    }
+                    None => {
+                        return Err(ParseError::new(
+                            "expected property name after '.'",
+                            position,
+                        ));
+                    }
+                }
+            } else if self.eat("[") {
+                let index = self.expression(0)?;
+                self.expect("]")?;
+                expr = Expr::Index(Box::new(expr), Box::new(index));
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, ParseError> {
+        let position = self.position();
+        match self.next() {
+            Some((_, Token::Number(n))) => Ok(Expr::Literal(Value::Number(n))),
+            Some((_, Token::String(s))) => Ok(Expr::Literal(Value::String(s))),
+            Some((_, Token::Identifier(name))) => {
+                if self.eat("(") {
+                    let mut args = Vec::new();
+                    if !self.eat(")") {
+                        loop {
+                            args.push(self.expression(0)?);
+                            if self.eat(")") {
+                                break;
+                            }
+                            if !self.eat(",") {
+                                return Err(self.unexpected("',' or ')'"));
+                            }
+                        }
+                    }
+                    Ok(Expr::Call(name, args))
+                } else {
+                    Ok(Expr::Identifier(name))
+                }
+            }
+            Some((_, Token::Symbol("("))) => {
+                let expr = self.expression(0)?;
+                self.expect(")")?;
+                Ok(expr)
+            }
+            Some((_, token)) => Err(ParseError::new(format!("unexpected {token}"), position)),
+            None => Err(ParseError::new("unexpected end of expression", position)),
+        }
+    }
+}
+
+fn binary_operator(symbol: &str) -> Option<(BinaryOp, u8)> {
+    Some(match symbol {
+        "||" => (BinaryOp::Or, 1),
+        "&&" => (BinaryOp::And, 2),
+        "==" => (BinaryOp::Equal, 3),
+        "!=" | "<>" => (BinaryOp::NotEqual, 3),
+        "<" => (BinaryOp::Less, 4),
+        "<=" => (BinaryOp::LessOrEqual, 4),
+        ">" => (BinaryOp::Greater, 4),
+        ">=" => (BinaryOp::GreaterOrEqual, 4),
+        "&" => (BinaryOp::Concat, 5),
+        "+" => (BinaryOp::Add, 6),
+        "-" => (BinaryOp::Subtract, 6),
+        "*" => (BinaryOp::Multiply, 7),
+        "/" => (BinaryOp::Divide, 7),
+        "%" => (BinaryOp::Modulo, 7),
+        "^" => (BinaryOp::Power, 8),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn eval(source: &str, data: Value) -> Value {
+        let expr = Expr::parse(source).unwrap_or_else(|e| panic!("{source}: {e}"));
+        Evaluator::new()
+            .evaluate(&expr, &data)
+            .unwrap_or_else(|e| panic!("{source}: {e}"))
+    }
+
+    #[test]
+    fn test_operators_and_property_paths() {
+        let data = json!({
+            "user": { "name": "Ada", "tags": ["admin", "dev"] },
+            "price": 2.5,
+            "quantity": 4
+        });
+
+        assert_eq!(eval("1 + 2 * 3 ^ 2", json!({})), json!(19));
+        assert_eq!(eval("2 ^ 3 ^ 2", json!({})), json!(512));
+        assert_eq!(eval("price * quantity", data.clone()), json!(10.0));
+        assert_eq!(eval("user.tags[1]", data.clone()), json!("dev"));
+        assert_eq!(eval("user['name'] & '!'", data.clone()), json!("Ada!"));
+        assert_eq!(eval("quantity > 3 && !missing", data.clone()), json!(true));
+        assert_eq!(eval("missing.deeper", data), Value::Null);
+    }
+
+    #[test]
+    fn test_builtin_functions() {
+        let data = json!({ "items": ["a", "b", "c"], "done": false });
+
+        assert_eq!(eval("if(done, 'yes', 'no')", data.clone()), json!("no"));
+        assert_eq!(eval("count(items)", data.clone()), json!(3));
+        assert_eq!(
+            eval("join(items, ', ', ' and ')", data.clone()),
+            json!("a, b and c")
+        );
+        assert_eq!(eval("concat('x', 1, true)", data.clone()), json!("x1true"));
+        assert_eq!(
+            eval("concat(items, createArray('d'))", data.clone()),
+            json!(["a", "b", "c", "d"])
+        );
+        assert_eq!(eval("contains(items, 'b')", data.clone()), json!(true));
+        assert_eq!(
+            eval("substring('adaptive', 2, 3)", data.clone()),
+            json!("apt")
+        );
+        assert_eq!(eval("max(3, 7.5, 1)", data.clone()), json!(7.5));
+        assert_eq!(eval("round(2.345, 2)", data), json!(2.35));
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(
+            eval("formatNumber(1234567.891, 2)", json!({})),
+            json!("1,234,567.89")
+        );
+        assert_eq!(eval("formatNumber(-0.004, 2)", json!({})), json!("0.00"));
+        assert_eq!(eval("formatNumber(-12, 0)", json!({})), json!("-12"));
+        assert_eq!(
+            eval("formatNumber(1234.5, 1, 'de-DE')", json!({})),
+            json!("1.234,5")
+        );
+    }
+
+    #[test]
+    fn test_format_date_time() {
+        let data = json!({ "when": "2024-02-29T18:05:09.120+02:00" });
+
+        assert_eq!(
+            eval("formatDateTime(when)", data.clone()),
+            json!("2024-02-29T16:05:09.120Z")
+        );
+        assert_eq!(
+            eval(
+                "formatDateTime(when, 'dddd d MMMM yyyy, h:mm tt')",
+                data.clone()
+            ),
+            json!("Thursday 29 February 2024, 4:05 PM")
+        );
+        assert_eq!(
+            eval(
+                "formatDateTime('1999-12-31', \"ddd dd/MM/yy 'at' HH:mm\")",
+                data
+            ),
+            json!("Fri 31/12/99 at 00:00")
+        );
+
+        let expr = Expr::parse("formatDateTime('2023-02-29')").unwrap();
+        assert!(Evaluator::new().evaluate(&expr, &json!({})).is_err());
+    }
+
+    #[test]
+    fn test_ticks() {
+        let data = json!({ "when": "2024-03-05T14:07:09.1234567+01:00" });
+        assert_eq!(eval("ticks('0001-01-01')", json!({})), json!(0));
+        assert_eq!(
+            eval("ticks('1970-01-01T00:00:00Z')", json!({})),
+            json!(621_355_968_000_000_000_i64)
+        );
+        assert_eq!(
+            eval("ticks(when)", data),
+            json!(638_452_408_291_234_567_i64)
+        );
+    }
+
+    #[test]
+    fn test_non_ascii_timestamps_are_rejected() {
+        for when in [
+            "2024-01-01T00:00:00.1é",
+            "2024-01-01T00:00:00+0é:00",
+            "2024-01-01T00:00:00+0é0",
+            "2024-01-01T00:00:00.12345678é",
+        ] {
+            assert!(Timestamp::parse(when).is_none(), "{when}");
+            let data = json!({ "when": when });
+            for source in ["ticks(when)", "formatDateTime(when)"] {
+                let expr = Expr::parse(source).unwrap();
+                let error = Evaluator::new().evaluate(&expr, &data).unwrap_err();
+                assert_eq!(
+                    error.to_string(),
+                    format!("'{when}' is not an ISO 8601 timestamp"),
+                    "{source}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_errors_report_position() {
+        let cases = [
+            ("1 +", "unexpected end of expression at position 3"),
+            (
+                "count(items",
+                "expected ',' or ')' but found end of expression at position 11",
+            ),
+            (
+                "a.1",
+                "expected property name after '.' but found number 1 at position 2",
+            ),
+            ("'open", "unterminated string at position 0"),
+            ("a # b", "unexpected character '#' at position 2"),
+            ("a b", "unexpected identifier b at position 2"),
+        ];
+
+        for (source, expected) in cases {
+            assert_eq!(Expr::parse(source).unwrap_err().to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_custom_functions() {
+        let evaluator = Evaluator::new()
+            .with_function("greet", |args| match args {
+                [Value::String(name)] => Ok(json!(format!("Hello, {name}!"))),
+                _ => Err(EvalError::new("greet() expects a name")),
+            })
+            .with_function("count", |_| Ok(json!("overridden")));
+
+        let expr = Expr::parse("greet(user.name)").unwrap();
+        assert_eq!(
+            evaluator.evaluate(&expr, &json!({ "user": { "name": "Ada" } })),
+            Ok(json!("Hello, Ada!"))
+        );
+
+        let expr = Expr::parse("greet(1)").unwrap();
+        assert_eq!(
+            evaluator.evaluate(&expr, &json!({})),
+            Err(EvalError::new("greet() expects a name"))
+        );
+
+        let expr = Expr::parse("count(items)").unwrap();
+        assert_eq!(
+            evaluator.evaluate(&expr, &json!({})),
+            Ok(json!("overridden"))
+        );
+
+        let expr = Expr::parse("unknown()").unwrap();
+        assert_eq!(
+            Evaluator::new().evaluate(&expr, &json!({})),
+            Err(EvalError::new("unknown function unknown()"))
+        );
+    }
+}
@@ -0,0 +1,354 @@
+//! Input forms generated from Rust types.
+//!
+//! With the `derive` feature, `#[derive(AdaptiveForm)]` turns a struct into the inputs that
+//! collect it, and `#[derive(AdaptiveChoices)]` turns an enum into a choice set.
+//!
+#![cfg_attr(feature = "derive", doc = "```")]
+#![cfg_attr(not(feature = "derive"), doc = "```ignore")]
+//! use adaptive_card_rs::card::CardElement;
+//! use adaptive_card_rs::form::{AdaptiveChoices, AdaptiveForm};
+//!
+//! #[derive(AdaptiveChoices)]
+//! enum Priority {
+//!     Low,
+//!     High,
+//! }
+//!
+//! #[derive(AdaptiveForm)]
+//! #[adaptive(submit = "Create")]
+//! struct NewTicket {
+//!     #[adaptive(placeholder = "What went wrong?")]
+//!     summary: String,
+//!     priority: Priority,
+//!     estimate: Option<f64>,
+//!     urgent: bool,
+//! }
+//!
+//! let card = NewTicket::form_card();
+//! assert_eq!(card.body.len(), 4);
+//! assert!(matches!(card.body[1], CardElement::InputChoiceSet(_)));
+//! ```
+//!
+//! The input used for a field is chosen by its type's [`FormField`] implementation:
+//!
+//! | Type | Input |
+//! |------|-------|
+//! | `String` | `Input.Text` |
+//! | numbers | `Input.Number` |
+//! | `bool` | `Input.Toggle` |
+//! | `chrono::NaiveDate`, `chrono::NaiveTime` (`chrono` feature) | `Input.Date`, `Input.Time` |
+//! | enums deriving [`AdaptiveChoices`] | `Input.ChoiceSet` |
+//! | `Option<T>` | the input for `T`, not required |
+//!
+//! `Input.Number` has no integer mode, so a field of an integer type gets the same input as
+//! an `f64` and [`AdaptiveCard::validate_submission`] accepts a fraction such as `1.5` for
+//! it. Deserializing the submission into the struct rejects it, so parse with
+//! [`AdaptiveCard::parse_submission`] before trusting the values.
+use crate::actions::{Action, SubmitAction};
+use crate::card::{AdaptiveCard, CardElement};
+use crate::inputs::{InputChoice, InputChoiceSet, InputNumber, InputText, InputToggle};
+#[cfg(feature = "chrono")]
+use crate::inputs::{InputDate, InputTime};
+
+#[cfg(feature = "derive")]
+pub use adaptive_card_rs_derive::{AdaptiveChoices, AdaptiveForm};
+
+/// A type that can be collected with a form of inputs.
+pub trait AdaptiveForm {
+    /// The inputs for each field, in declaration order.
+    fn form_elements() -> Vec<CardElement>;
+
+    /// The action that submits the form.
+    fn submit_action() -> Action {
+        SubmitAction::builder().title("Submit").into()
+    }
+
+    /// A card holding the form's inputs and its submit action.
+    ///
+    /// The card declares the lowest version that supports every input property it uses, and
+    /// never less than the default version.
+    fn form_card() -> AdaptiveCard {
+        let mut card = AdaptiveCard {
+            body: Self::form_elements(),
+            actions: Some(vec![Self::submit_action()]),
+            ..Default::default()
+        };
+        card.version = card.version.max(card.minimum_version());
+        card
+    }
+}
+
+/// A type whose values are picked from a fixed list.
+pub trait AdaptiveChoices {
+    /// The choices, in declaration order.
+    fn choices() -> Vec<InputChoice>;
+}
+
+/// What a form knows about a field when creating its input.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Field {
+    /// The input id, which is also the key of the field's value in the submitted data.
+    pub id: String,
+    /// The input label.
+    pub label: String,
+    /// Optional placeholder text.
+    pub placeholder: Option<String>,
+    /// Whether a value must be entered before submitting.
+    pub required: bool,
+}
+
+/// A type that can be entered with an input.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be used as a form field",
+    label = "no input for this type",
+    note = "implement `FormField` for it, derive `AdaptiveChoices` if it is an enum, or mark the field `#[adaptive(skip)]`"
+)]
+pub trait FormField {
+    /// Creates the input for a field of this type.
+    fn input(field: Field) -> CardElement;
+}
+
+/// Creates a choice set input from a type's [`AdaptiveChoices`].
+pub fn choice_set<T: AdaptiveChoices>(field: Field) -> CardElement {
+    CardElement::InputChoiceSet(InputChoiceSet {
+        id: field.id,
+        choices: Some(T::choices()),
+        placeholder: field.placeholder,
+        label: Some(field.label),
+        is_required: field.required.then_some(true),
+        ..Default::default()
+    })
+}
+
+impl<T: FormField> FormField for Option<T> {
+    fn input(field: Field) -> CardElement {
+        T::input(Field {
+            required: false,
+            ..field
+        })
+    }
+}
+
+impl FormField for String {
+    fn input(field: Field) -> CardElement {
+        CardElement::InputText(InputText {
+            id: field.id,
+            placeholder: field.placeholder,
+            label: Some(field.label),
+            is_required: field.required.then_some(true),
+            ..Default::default()
+        })
+    }
+}
+
+macro_rules! number_field {
+    ($($ty:ty),*) => {
+        $(
+            impl FormField for $ty {
+                fn input(field: Field) -> CardElement {
+                    CardElement::InputNumber(InputNumber {
+                        id: field.id,
+                        placeholder: field.placeholder,
+                        label: Some(field.label),
+                        is_required: field.required.then_some(true),
+                        ..Default::default()
+                    })
+                }
+            }
+        )*
+    };
+}
+
+number_field!(
+    f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+/// Booleans always have a value, so toggles are never required. The submitted value is
+/// `"true"` or `"false"`.
+impl FormField for bool {
+    fn input(field: Field) -> CardElement {
+        CardElement::InputToggle(InputToggle {
+            id: field.id,
+            title: field.label,
+            value_on: Some("true".to_string()),
+            value_off: Some("false".to_string()),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FormField for chrono::NaiveDate {
+    fn input(field: Field) -> CardElement {
+        CardElement::InputDate(InputDate {
+            id: field.id,
+            placeholder: field.placeholder,
+            label: Some(field.label),
+            is_required: field.required.then_some(true),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FormField for chrono::NaiveTime {
+    fn input(field: Field) -> CardElement {
+        CardElement::InputTime(InputTime {
+            id: field.id,
+            placeholder: field.placeholder,
+            label: Some(field.label),
+            is_required: field.required.then_some(true),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adaptive_card_rs_derive::{AdaptiveChoices, AdaptiveForm};
+    use expect_test::expect;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[allow(dead_code)]
+    #[derive(AdaptiveChoices, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum Priority {
+        Low,
+        #[adaptive(title = "Needs attention")]
+        NeedsAttention,
+        #[serde(rename = "p0")]
+        Outage,
+    }
+
+    #[allow(dead_code)]
+    #[derive(AdaptiveForm, Deserialize)]
+    #[adaptive(submit = "Create")]
+    #[serde(rename_all = "camelCase")]
+    struct NewTicket {
+        #[adaptive(placeholder = "What went wrong?")]
+        summary: String,
+        priority: Priority,
+        #[adaptive(label = "Estimate (hours)")]
+        estimate_hours: Option<f64>,
+        notify_owner: bool,
+        #[adaptive(skip)]
+        #[serde(default)]
+        internal: u32,
+    }
+
+    #[test]
+    fn test_derived_form() {
+        let card = NewTicket::form_card();
+        let json = serde_json::to_string_pretty(&card).unwrap();
+        let expected = expect![[r#"
+            {
+              "type": "AdaptiveCard",
+              "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+              "version": "1.3",
+              "body": [
+                {
+                  "type": "Input.Text",
+                  "id": "summary",
+                  "placeholder": "What went wrong?",
+                  "label": "Summary",
+                  "isRequired": true
+                },
+                {
+                  "type": "Input.ChoiceSet",
+                  "id": "priority",
+                  "choices": [
+                    {
+                      "title": "Low",
+                      "value": "low"
+                    },
+                    {
+                      "title": "Needs attention",
+                      "value": "needs_attention"
+                    },
+                    {
+                      "title": "Outage",
+                      "value": "p0"
+                    }
+                  ],
+                  "label": "Priority",
+                  "isRequired": true
+                },
+                {
+                  "type": "Input.Number",
+                  "id": "estimateHours",
+                  "label": "Estimate (hours)"
+                },
+                {
+                  "type": "Input.Toggle",
+                  "id": "notifyOwner",
+                  "title": "Notify owner",
+                  "valueOn": "true",
+                  "valueOff": "false"
+                }
+              ],
+              "actions": [
+                {
+                  "type": "Action.Submit",
+                  "title": "Create"
+                }
+              ]
+            }"#]];
+        expected.assert_eq(&json);
+    }
+
+    #[test]
+    fn test_derived_form_is_valid() {
+        let card = NewTicket::form_card();
+        assert_eq!(card.check_version(), []);
+        assert_eq!(card.lint(), []);
+        #[cfg(feature = "schema-validation")]
+        assert_eq!(card.validate_schema(), Ok(()));
+    }
+
+    #[test]
+    fn test_field_inputs() {
+        let field = || Field {
+            id: "when".to_string(),
+            label: "When".to_string(),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            Option::<String>::input(field()),
+            CardElement::InputText(InputText {
+                is_required: None,
+                ..
+            })
+        ));
+        #[cfg(feature = "chrono")]
+        {
+            assert!(matches!(
+                chrono::NaiveDate::input(field()),
+                CardElement::InputDate(_)
+            ));
+            assert!(matches!(
+                chrono::NaiveTime::input(field()),
+                CardElement::InputTime(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_integer_fields() {
+        #[derive(AdaptiveForm, Deserialize)]
+        struct Order {
+            count: u32,
+        }
+
+        let card = Order::form_card();
+        let whole: Order = card.parse_submission(&json!({ "count": "2" })).unwrap();
+        assert_eq!(whole.count, 2);
+
+        // Input.Number has no integer mode, so only deserializing catches fractions.
+        let fraction = json!({ "count": "1.5" });
+        assert!(card.validate_submission(&fraction).is_ok());
+        assert!(card.parse_submission::<Order>(&fraction).is_err());
+    }
+}
@@ -0,0 +1,853 @@
+//! Host configuration, which maps the abstract values used in cards to concrete sizes and
+//! colors.
+//!
+//! Cards say `"size": "large"` or `"color": "good"`; the host decides what that looks like.
+//! [`HostConfig`] follows the JSON format used by the official Adaptive Cards renderers, so
+//! existing host config files can be deserialized into it. Missing properties take the
+//! official default values. [`HostConfig::teams_light`] and [`HostConfig::teams_dark`] match
+//! Microsoft Teams.
+//!
+//! ```
+//! use adaptive_card_rs::card::{ContainerStyle, Spacing, TextSize};
+//! use adaptive_card_rs::common::Color;
+//! use adaptive_card_rs::host_config::HostConfig;
+//!
+//! let config = HostConfig::default();
+//! assert_eq!(config.spacing(&Spacing::Medium), 20);
+//! assert_eq!(config.font_size(None, &TextSize::Large), 21);
+//! assert_eq!(config.foreground_color(&ContainerStyle::Default, &Color::Good, false), "#028A02");
+//! ```
+use serde::{Deserialize, Serialize};
+
+use crate::card::{ContainerStyle, ImageSize, Spacing, TextSize, TextWeight};
+use crate::common::{Color, FontType, TextBlockStyle};
+
+/// The configuration of a host application.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HostConfig {
+    /// Whether the host supports inputs and actions.
+    pub supports_interactivity: bool,
+    /// Pixel values for each [`Spacing`].
+    pub spacing: SpacingConfig,
+    /// How separators are drawn.
+    pub separator: SeparatorConfig,
+    /// Font families, sizes and weights.
+    pub font_types: FontTypesConfig,
+    /// Background and text colors for each [`ContainerStyle`].
+    pub container_styles: ContainerStylesConfig,
+    /// Pixel sizes for each [`ImageSize`].
+    pub image_sizes: ImageSizesConfig,
+    /// How actions are laid out.
+    pub actions: ActionsConfig,
+    /// How fact titles and values are displayed.
+    pub fact_set: FactSetConfig,
+    /// How input labels and error messages are displayed.
+    pub inputs: InputsConfig,
+    /// Text settings for each [`TextBlockStyle`].
+    pub text_styles: TextStylesConfig,
+}
+
+impl Default for HostConfig {
+    fn default() -> Self {
+        Self {
+            supports_interactivity: true,
+            spacing: SpacingConfig::default(),
+            separator: SeparatorConfig::default(),
+            font_types: FontTypesConfig::default(),
+            container_styles: ContainerStylesConfig::default(),
+            image_sizes: ImageSizesConfig::default(),
+            actions: ActionsConfig::default(),
+            fact_set: FactSetConfig::default(),
+            inputs: InputsConfig::default(),
+            text_styles: TextStylesConfig::default(),
+        }
+    }
+}
+
+/// Pixel values for each [`Spacing`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SpacingConfig {
+    pub small: u32,
+    pub default: u32,
+    pub medium: u32,
+    pub large: u32,
+    pub extra_large: u32,
+    /// The padding inside containers with a style.
+    pub padding: u32,
+}
+
+impl Default for SpacingConfig {
+    fn default() -> Self {
+        Self {
+            small: 3,
+            default: 8,
+            medium: 20,
+            large: 30,
+            extra_large: 40,
+            padding: 15,
+        }
+    }
+}
+
+/// How separators are drawn.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SeparatorConfig {
+    /// The thickness of the line in pixels.
+    pub line_thickness: u32,
+    /// The color of the line.
+    pub line_color: String,
+}
+
+impl Default for SeparatorConfig {
+    fn default() -> Self {
+        Self {
+            line_thickness: 1,
+            line_color: "#EEEEEE".to_string(),
+        }
+    }
+}
+
+/// Font settings for each [`FontType`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FontTypesConfig {
+    pub default: FontTypeConfig,
+    pub monospace: FontTypeConfig,
+}
+
+impl Default for FontTypesConfig {
+    fn default() -> Self {
+        Self {
+            default: FontTypeConfig::default(),
+            monospace: FontTypeConfig {
+                font_family: "'Courier New', Courier, monospace".to_string(),
+                ..FontTypeConfig::default()
+            },
+        }
+    }
+}
+
+/// The family, sizes and weights of one font type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FontTypeConfig {
+    /// A CSS font family list.
+    pub font_family: String,
+    pub font_sizes: FontSizesConfig,
+    pub font_weights: FontWeightsConfig,
+}
+
+impl Default for FontTypeConfig {
+    fn default() -> Self {
+        Self {
+            font_family: "'Segoe UI', Tahoma, Geneva, Verdana, sans-serif".to_string(),
+            font_sizes: FontSizesConfig::default(),
+            font_weights: FontWeightsConfig::default(),
+        }
+    }
+}
+
+/// Pixel sizes for each [`TextSize`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FontSizesConfig {
+    pub small: u32,
+    pub default: u32,
+    pub medium: u32,
+    pub large: u32,
+    pub extra_large: u32,
+}
+
+impl Default for FontSizesConfig {
+    fn default() -> Self {
+        Self {
+            small: 12,
+            default: 14,
+            medium: 17,
+            large: 21,
+            extra_large: 26,
+        }
+    }
+}
+
+/// CSS font weights for each [`TextWeight`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FontWeightsConfig {
+    pub lighter: u32,
+    pub default: u32,
+    pub bolder: u32,
+}
+
+impl Default for FontWeightsConfig {
+    fn default() -> Self {
+        Self {
+            lighter: 200,
+            default: 400,
+            bolder: 600,
+        }
+    }
+}
+
+/// Colors for each [`ContainerStyle`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ContainerStylesConfig {
+    pub default: ContainerStyleConfig,
+    pub emphasis: ContainerStyleConfig,
+    pub good: ContainerStyleConfig,
+    pub attention: ContainerStyleConfig,
+    pub warning: ContainerStyleConfig,
+    pub accent: ContainerStyleConfig,
+}
+
+impl Default for ContainerStylesConfig {
+    fn default() -> Self {
+        let style = |background: &str| ContainerStyleConfig {
+            background_color: background.to_string(),
+            ..ContainerStyleConfig::default()
+        };
+        Self {
+            default: style("#FFFFFF"),
+            emphasis: style("#08000000"),
+            good: style("#CCFFCC"),
+            attention: style("#FFC5B2"),
+            warning: style("#FFE2B2"),
+            accent: style("#C7DEF9"),
+        }
+    }
+}
+
+/// The background and text colors of one container style.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ContainerStyleConfig {
+    /// The background color, as `#RRGGBB` or `#AARRGGBB`.
+    pub background_color: String,
+    pub foreground_colors: ForegroundColorsConfig,
+}
+
+impl Default for ContainerStyleConfig {
+    fn default() -> Self {
+        Self {
+            background_color: "#FFFFFF".to_string(),
+            foreground_colors: ForegroundColorsConfig::default(),
+        }
+    }
+}
+
+/// Text colors for each [`Color`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ForegroundColorsConfig {
+    pub default: ColorConfig,
+    pub dark: ColorConfig,
+    pub light: ColorConfig,
+    pub accent: ColorConfig,
+    pub good: ColorConfig,
+    pub warning: ColorConfig,
+    pub attention: ColorConfig,
+}
+
+impl Default for ForegroundColorsConfig {
+    fn default() -> Self {
+        Self {
+            default: ColorConfig::new("#333333", "#EE333333"),
+            dark: ColorConfig::new("#000000", "#66000000"),
+            light: ColorConfig::new("#FFFFFF", "#33000000"),
+            accent: ColorConfig::new("#2E89FC", "#882E89FC"),
+            good: ColorConfig::new("#028A02", "#DD027502"),
+            warning: ColorConfig::new("#E69500", "#DDE69500"),
+            attention: ColorConfig::new("#CC3300", "#DDCC3300"),
+        }
+    }
+}
+
+/// A text color, and the color used when the text is subtle.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ColorConfig {
+    pub default: String,
+    pub subtle: String,
+}
+
+impl ColorConfig {
+    /// Creates a color from its normal and subtle values.
+    pub fn new(default: impl Into<String>, subtle: impl Into<String>) -> Self {
+        Self {
+            default: default.into(),
+            subtle: subtle.into(),
+        }
+    }
+}
+
+/// Pixel widths for each [`ImageSize`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ImageSizesConfig {
+    pub small: u32,
+    pub medium: u32,
+    pub large: u32,
+}
+
+impl Default for ImageSizesConfig {
+    fn default() -> Self {
+        Self {
+            small: 40,
+            medium: 80,
+            large: 160,
+        }
+    }
+}
+
+/// How actions are laid out.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ActionsConfig {
+    /// The most actions shown in one action set. Extra actions are dropped.
+    pub max_actions: usize,
+    /// The space between buttons, in pixels.
+    pub button_spacing: u32,
+    /// Whether buttons are laid out in a row or a column.
+    pub actions_orientation: ActionsOrientation,
+    /// How the cards of `Action.ShowCard` are displayed.
+    pub show_card: ShowCardConfig,
+}
+
+impl Default for ActionsConfig {
+    fn default() -> Self {
+        Self {
+            max_actions: 5,
+            button_spacing: 10,
+            actions_orientation: ActionsOrientation::Horizontal,
+            show_card: ShowCardConfig::default(),
+        }
+    }
+}
+
+/// How the cards of `Action.ShowCard` are displayed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ShowCardConfig {
+    /// The container style of the shown card.
+    pub style: ContainerStyle,
+    /// The space between the buttons and the shown card, in pixels.
+    pub inline_top_margin: u32,
+}
+
+impl Default for ShowCardConfig {
+    fn default() -> Self {
+        Self {
+            style: ContainerStyle::Emphasis,
+            inline_top_margin: 16,
+        }
+    }
+}
+
+/// Whether buttons are laid out in a row or a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ActionsOrientation {
+    Horizontal,
+    Vertical,
+}
+
+/// How fact titles and values are displayed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FactSetConfig {
+    pub title: FactSetTextConfig,
+    pub value: FactSetTextConfig,
+    /// The space between titles and values, in pixels.
+    pub spacing: u32,
+}
+
+impl Default for FactSetConfig {
+    fn default() -> Self {
+        Self {
+            title: FactSetTextConfig {
+                weight: TextWeight::Bolder,
+                max_width: Some(150),
+                ..FactSetTextConfig::default()
+            },
+            value: FactSetTextConfig::default(),
+            spacing: 10,
+        }
+    }
+}
+
+/// The text settings of fact titles or values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FactSetTextConfig {
+    pub size: TextSize,
+    pub weight: TextWeight,
+    pub color: Color,
+    pub font_type: FontType,
+    pub is_subtle: bool,
+    pub wrap: bool,
+    /// The widest the column may be, in pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_width: Option<u32>,
+}
+
+impl Default for FactSetTextConfig {
+    fn default() -> Self {
+        Self {
+            size: TextSize::Default,
+            weight: TextWeight::Default,
+            color: Color::Default,
+            font_type: FontType::Default,
+            is_subtle: false,
+            wrap: true,
+            max_width: None,
+        }
+    }
+}
+
+/// How input labels and error messages are displayed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InputsConfig {
+    pub label: InputLabelsConfig,
+    pub error_message: ErrorMessageConfig,
+}
+
+/// Label settings for required and optional inputs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InputLabelsConfig {
+    /// The space between a label and its input.
+    pub input_spacing: Spacing,
+    pub required_inputs: InputLabelConfig,
+    pub optional_inputs: InputLabelConfig,
+}
+
+impl Default for InputLabelsConfig {
+    fn default() -> Self {
+        Self {
+            input_spacing: Spacing::Small,
+            required_inputs: InputLabelConfig {
+                suffix: " *".to_string(),
+                ..InputLabelConfig::default()
+            },
+            optional_inputs: InputLabelConfig::default(),
+        }
+    }
+}
+
+/// The text settings of an input label.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InputLabelConfig {
+    pub size: TextSize,
+    pub weight: TextWeight,
+    pub color: Color,
+    pub is_subtle: bool,
+    /// Text appended to the label, e.g. ` *` for required inputs.
+    pub suffix: String,
+}
+
+impl Default for InputLabelConfig {
+    fn default() -> Self {
+        Self {
+            size: TextSize::Default,
+            weight: TextWeight::Default,
+            color: Color::Default,
+            is_subtle: false,
+            suffix: String::new(),
+        }
+    }
+}
+
+/// The text settings of an input's error message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ErrorMessageConfig {
+    /// The space between the input and its error message.
+    pub spacing: Spacing,
+    pub size: TextSize,
+    pub weight: TextWeight,
+}
+
+impl Default for ErrorMessageConfig {
+    fn default() -> Self {
+        Self {
+            spacing: Spacing::Small,
+            size: TextSize::Default,
+            weight: TextWeight::Default,
+        }
+    }
+}
+
+/// Text settings for each [`TextBlockStyle`] other than the default.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TextStylesConfig {
+    pub heading: TextStyleConfig,
+    /// The style of header cells in tables.
+    pub column_header: TextStyleConfig,
+}
+
+impl Default for TextStylesConfig {
+    fn default() -> Self {
+        Self {
+            heading: TextStyleConfig {
+                size: TextSize::Large,
+                ..TextStyleConfig::default()
+            },
+            column_header: TextStyleConfig::default(),
+        }
+    }
+}
+
+/// The text settings of one text style.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TextStyleConfig {
+    pub size: TextSize,
+    pub weight: TextWeight,
+    pub color: Color,
+    pub font_type: FontType,
+    pub is_subtle: bool,
+}
+
+impl Default for TextStyleConfig {
+    fn default() -> Self {
+        Self {
+            size: TextSize::Default,
+            weight: TextWeight::Bolder,
+            color: Color::Default,
+            font_type: FontType::Default,
+            is_subtle: false,
+        }
+    }
+}
+
+impl HostConfig {
+    /// The host config of Microsoft Teams with its light theme.
+    pub fn teams_light() -> Self {
+        Self::teams(
+            [
+                "#FFFFFFFF",
+                "#FFF9F9F9",
+                "#FFE7F2DA",
+                "#FFFCF4F6",
+                "#FFFBF6D9",
+                "#FFE8E8F4",
+            ],
+            TEAMS_LIGHT_COLORS,
+            "#FFE1DFDD",
+        )
+    }
+
+    /// The host config of Microsoft Teams with its dark theme.
+    pub fn teams_dark() -> Self {
+        Self::teams(
+            [
+                "#FF2D2C2C",
+                "#FF292828",
+                "#FF0D2E0D",
+                "#FF3E1F25",
+                "#FF463F1F",
+                "#FF2E2F4F",
+            ],
+            TEAMS_DARK_COLORS,
+            "#FF3B3A39",
+        )
+    }
+
+    /// The settings shared by both Teams themes.
+    ///
+    /// `backgrounds` are the default, emphasis, good, attention, warning and accent container
+    /// backgrounds.
+    fn teams(backgrounds: [&str; 6], colors: ColorTable, line_color: &str) -> Self {
+        let [default, emphasis, good, attention, warning, accent] =
+            backgrounds.map(|background| ContainerStyleConfig {
+                background_color: background.to_string(),
+                foreground_colors: colors.into(),
+            });
+        let font_type = FontTypeConfig {
+            font_family: "'Segoe UI', system-ui, -apple-system, sans-serif".to_string(),
+            font_sizes: FontSizesConfig {
+                small: 12,
+                default: 14,
+                medium: 14,
+                large: 18,
+                extra_large: 24,
+            },
+            font_weights: FontWeightsConfig {
+                lighter: 200,
+                default: 400,
+                bolder: 600,
+            },
+        };
+        Self {
+            supports_interactivity: true,
+            spacing: SpacingConfig {
+                small: 8,
+                default: 12,
+                medium: 16,
+                large: 20,
+                extra_large: 24,
+                padding: 16,
+            },
+            separator: SeparatorConfig {
+                line_thickness: 1,
+                line_color: line_color.to_string(),
+            },
+            font_types: FontTypesConfig {
+                monospace: FontTypeConfig {
+                    font_family: "Menlo, Consolas, 'Courier New', monospace".to_string(),
+                    ..font_type.clone()
+                },
+                default: font_type,
+            },
+            container_styles: ContainerStylesConfig {
+                default,
+                emphasis,
+                good,
+                attention,
+                warning,
+                accent,
+            },
+            image_sizes: ImageSizesConfig {
+                small: 32,
+                medium: 52,
+                large: 100,
+            },
+            actions: ActionsConfig {
+                max_actions: 6,
+                button_spacing: 8,
+                ..ActionsConfig::default()
+            },
+            fact_set: FactSetConfig {
+                spacing: 16,
+                ..FactSetConfig::default()
+            },
+            inputs: InputsConfig::default(),
+            text_styles: TextStylesConfig::default(),
+        }
+    }
+}
+
+/// The `default`, `dark`, `light`, `accent`, `good`, `warning` and `attention` colors as
+/// normal and subtle pairs.
+type ColorTable = [(&'static str, &'static str); 7];
+
+const TEAMS_LIGHT_COLORS: ColorTable = [
+    ("#FF242424", "#FF616161"),
+    ("#FF252424", "#BF252424"),
+    ("#FFFFFFFF", "#FFFFFFFF"),
+    ("#FF5B5FC7", "#FF8B8CC7"),
+    ("#FF237B4B", "#E5237B4B"),
+    ("#FF835C00", "#E5835C00"),
+    ("#FFC4314B", "#E5C4314B"),
+];
+
+const TEAMS_DARK_COLORS: ColorTable = [
+    ("#FFFFFFFF", "#FFD6D6D6"),
+    ("#FF201F1F", "#FF2D2C2C"),
+    ("#FFFFFFFF", "#FFFFFFFF"),
+    ("#FF7F85F5", "#FFA6A7DC"),
+    ("#FF92C353", "#E592C353"),
+    ("#FFF8D22A", "#E5F8D22A"),
+    ("#FFF9526B", "#E5F9526B"),
+];
+
+impl From<ColorTable> for ForegroundColorsConfig {
+    fn from([default, dark, light, accent, good, warning, attention]: ColorTable) -> Self {
+        Self {
+            default: ColorConfig::new(default.0, default.1),
+            dark: ColorConfig::new(dark.0, dark.1),
+            light: ColorConfig::new(light.0, light.1),
+            accent: ColorConfig::new(accent.0, accent.1),
+            good: ColorConfig::new(good.0, good.1),
+            warning: ColorConfig::new(warning.0, warning.1),
+            attention: ColorConfig::new(attention.0, attention.1),
+        }
+    }
+}
+
+impl HostConfig {
+    /// Resolves a [`Spacing`] to pixels.
+    pub fn spacing(&self, spacing: &Spacing) -> u32 {
+        let config = &self.spacing;
+        match spacing {
+            Spacing::None => 0,
+            Spacing::Small => config.small,
+            Spacing::Default => config.default,
+            Spacing::Medium => config.medium,
+            Spacing::Large => config.large,
+            Spacing::ExtraLarge => config.extra_large,
+            Spacing::Padding => config.padding,
+        }
+    }
+
+    /// Returns the font settings for a [`FontType`], the default type if `None`.
+    pub fn font_type(&self, font_type: Option<&FontType>) -> &FontTypeConfig {
+        match font_type {
+            Some(FontType::Monospace) => &self.font_types.monospace,
+            Some(FontType::Default) | None => &self.font_types.default,
+        }
+    }
+
+    /// Resolves a [`TextSize`] to pixels.
+    pub fn font_size(&self, font_type: Option<&FontType>, size: &TextSize) -> u32 {
+        let sizes = &self.font_type(font_type).font_sizes;
+        match size {
+            TextSize::Small => sizes.small,
+            TextSize::Default => sizes.default,
+            TextSize::Medium => sizes.medium,
+            TextSize::Large => sizes.large,
+            TextSize::ExtraLarge => sizes.extra_large,
+        }
+    }
+
+    /// Resolves a [`TextWeight`] to a CSS font weight.
+    pub fn font_weight(&self, font_type: Option<&FontType>, weight: &TextWeight) -> u32 {
+        let weights = &self.font_type(font_type).font_weights;
+        match weight {
+            TextWeight::Lighter => weights.lighter,
+            TextWeight::Default => weights.default,
+            TextWeight::Bolder => weights.bolder,
+        }
+    }
+
+    /// Returns the colors of a [`ContainerStyle`].
+    pub fn container_style(&self, style: &ContainerStyle) -> &ContainerStyleConfig {
+        let styles = &self.container_styles;
+        match style {
+            ContainerStyle::Default => &styles.default,
+            ContainerStyle::Emphasis => &styles.emphasis,
+            ContainerStyle::Good => &styles.good,
+            ContainerStyle::Attention => &styles.attention,
+            ContainerStyle::Warning => &styles.warning,
+            ContainerStyle::Accent => &styles.accent,
+        }
+    }
+
+    /// Resolves a text [`Color`] inside a container of the given style to a hex color.
+    pub fn foreground_color(&self, style: &ContainerStyle, color: &Color, subtle: bool) -> &str {
+        let colors = &self.container_style(style).foreground_colors;
+        let color = match color {
+            Color::Default => &colors.default,
+            Color::Dark => &colors.dark,
+            Color::Light => &colors.light,
+            Color::Accent => &colors.accent,
+            Color::Good => &colors.good,
+            Color::Warning => &colors.warning,
+            Color::Attention => &colors.attention,
+        };
+        if subtle {
+            &color.subtle
+        } else {
+            &color.default
+        }
+    }
+
+    /// Returns the text settings of a [`TextBlockStyle`], or `None` for the default style,
+    /// which takes its settings from the text block.
+    pub fn text_style(&self, style: &TextBlockStyle) -> Option<&TextStyleConfig> {
+        match style {
+            TextBlockStyle::Default => None,
+            TextBlockStyle::Heading => Some(&self.text_styles.heading),
+        }
+    }
+
+    /// Returns the label settings for a required or optional input.
+    pub fn input_label(&self, required: bool) -> &InputLabelConfig {
+        if required {
+            &self.inputs.label.required_inputs
+        } else {
+            &self.inputs.label.optional_inputs
+        }
+    }
+
+    /// Resolves an [`ImageSize`] to a pixel width. `Auto` and `Stretch` have no fixed width.
+    pub fn image_size(&self, size: &ImageSize) -> Option<u32> {
+        match size {
+            ImageSize::Small => Some(self.image_sizes.small),
+            ImageSize::Medium => Some(self.image_sizes.medium),
+            ImageSize::Large => Some(self.image_sizes.large),
+            ImageSize::Auto | ImageSize::Stretch => None,
+        }
+    }
+}
+
+/// Converts a host config color, `#RRGGBB` or `#AARRGGBB`, to a CSS color.
+pub fn css_color(color: &str) -> String {
+    let hex = color.trim_start_matches('#');
+    if hex.len() == 8
+        && let Ok(argb) = u32::from_str_radix(hex, 16)
+    {
+        let [a, r, g, b] = argb.to_be_bytes();
+        let alpha = f64::from(a) / 255.0;
+        return format!("rgba({r}, {g}, {b}, {alpha:.2})");
+    }
+    color.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_host_config_uses_defaults() {
+        let config: HostConfig = serde_json::from_value(serde_json::json!({
+            "spacing": { "medium": 24 },
+            "containerStyles": { "emphasis": { "backgroundColor": "#F0F0F0" } },
+        }))
+        .unwrap();
+
+        assert_eq!(config.spacing(&Spacing::Medium), 24);
+        assert_eq!(config.spacing(&Spacing::Small), 3);
+        assert_eq!(
+            config
+                .container_style(&ContainerStyle::Emphasis)
+                .background_color,
+            "#F0F0F0"
+        );
+        assert_eq!(
+            config.foreground_color(&ContainerStyle::Emphasis, &Color::Attention, false),
+            "#CC3300"
+        );
+        assert_eq!(config.actions.max_actions, 5);
+    }
+
+    #[test]
+    fn test_teams_presets() {
+        let light = HostConfig::teams_light();
+        let dark = HostConfig::teams_dark();
+
+        assert_eq!(light.spacing(&Spacing::Padding), 16);
+        assert_eq!(dark.image_size(&ImageSize::Small), Some(32));
+        assert_ne!(
+            light.foreground_color(&ContainerStyle::Default, &Color::Default, false),
+            dark.foreground_color(&ContainerStyle::Default, &Color::Default, false)
+        );
+        assert_eq!(
+            light
+                .text_style(&TextBlockStyle::Heading)
+                .map(|style| &style.size),
+            Some(&TextSize::Large)
+        );
+        assert!(light.text_style(&TextBlockStyle::Default).is_none());
+        assert_eq!(light.input_label(true).suffix, " *");
+        assert_eq!(light.input_label(false).suffix, "");
+
+        // Presets survive a round trip through the JSON format.
+        let json = serde_json::to_value(&dark).unwrap();
+        assert_eq!(json["factSet"]["title"]["maxWidth"], 150);
+        assert_eq!(serde_json::from_value::<HostConfig>(json).unwrap(), dark);
+    }
+
+    #[test]
+    fn test_css_color() {
+        assert_eq!(css_color("#CC3300"), "#CC3300");
+        assert_eq!(css_color("#80FF0000"), "rgba(255, 0, 0, 0.50)");
+    }
+}
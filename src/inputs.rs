@@ -3,7 +3,7 @@ use crate::card::Spacing;
 use serde::{Deserialize, Serialize};
 
 /// Lets a user enter text.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct InputText {
     /// Unique identifier for the value. Used to identify collected input when the Submit action is performed.
@@ -50,7 +50,7 @@ pub struct InputText {
 }
 
 /// Allows a user to enter a number.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct InputNumber {
     /// Unique identifier for the value.
@@ -91,7 +91,7 @@ pub struct InputNumber {
 }
 
 /// Lets a user choose a date.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct InputDate {
     /// Unique identifier for the value.
@@ -132,7 +132,7 @@ pub struct InputDate {
 }
 
 /// Lets a user select a time.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct InputTime {
     /// Unique identifier for the value.
@@ -173,7 +173,7 @@ pub struct InputTime {
 }
 
 /// Lets a user choose between two options.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct InputToggle {
     /// Unique identifier for the value.
@@ -216,7 +216,7 @@ pub struct InputToggle {
 }
 
 /// Allows a user to input a choice.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct InputChoiceSet {
     /// Unique identifier for the value.
@@ -265,7 +265,7 @@ pub struct InputChoiceSet {
 }
 
 /// Represents a choice for an Input.ChoiceSet.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct InputChoice {
     /// The text to display for this choice.
@@ -0,0 +1,4 @@
+//! Clients for services that accept Adaptive Cards without a bot.
+//!
+//! - [`teams_webhook`] posts cards to Microsoft Teams incoming webhooks and Workflows.
+pub mod teams_webhook;
@@ -0,0 +1,306 @@
+//! Posting cards to Microsoft Teams incoming webhooks and Workflows.
+//!
+//! Both accept a message with the card as its only attachment. [`payload`] builds that
+//! message and rejects it when it is over the [`MAX_PAYLOAD_BYTES`] Teams accepts, so an
+//! oversized card fails before it leaves the process rather than as an opaque HTTP error.
+//!
+//! ```
+//! use adaptive_card_rs::card::AdaptiveCard;
+//! use adaptive_card_rs::integrations::teams_webhook::{Target, payload};
+//!
+//! let card = AdaptiveCard::builder().text("Deploy finished").build();
+//! let body = payload(&card, Target::Workflow).unwrap();
+//! let message: serde_json::Value = serde_json::from_slice(&body).unwrap();
+//! assert_eq!(message["attachments"][0]["content"]["body"][0]["text"], "Deploy finished");
+//! ```
+//!
+//! With the `teams-webhook` feature, [`Webhook`] sends the payload as well.
+use std::fmt;
+
+use serde_json::json;
+
+use crate::bot::ADAPTIVE_CARD_CONTENT_TYPE;
+use crate::card::AdaptiveCard;
+
+/// The largest message, in bytes, Teams accepts from a webhook.
+pub const MAX_PAYLOAD_BYTES: usize = 28 * 1024;
+
+/// The kind of webhook a card is posted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    /// An incoming webhook created from the channel's connectors.
+    IncomingWebhook,
+    /// A Workflows (Power Automate) flow started by the "When a Teams webhook request is
+    /// received" trigger, which posts each attachment's `content`.
+    Workflow,
+}
+
+/// An error building or sending a webhook message.
+#[derive(Debug)]
+pub enum WebhookError {
+    /// The message is larger than [`MAX_PAYLOAD_BYTES`].
+    TooLarge {
+        /// The size of the serialized message in bytes.
+        size: usize,
+        /// The largest size accepted.
+        limit: usize,
+    },
+    /// The card could not be serialized.
+    Serialize(serde_json::Error),
+    /// The request could not be sent.
+    #[cfg(feature = "teams-webhook")]
+    Http(reqwest::Error),
+    /// The webhook rejected the message.
+    #[cfg(feature = "teams-webhook")]
+    Status {
+        /// The HTTP status code.
+        status: u16,
+        /// The response body, which explains the rejection.
+        body: String,
+    },
+}
+
+impl fmt::Display for WebhookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WebhookError::TooLarge { size, limit } => {
+                write!(f, "message is {size} bytes, over the {limit} byte limit")
+            }
+            WebhookError::Serialize(error) => write!(f, "{error}"),
+            #[cfg(feature = "teams-webhook")]
+            WebhookError::Http(error) => write!(f, "{error}"),
+            #[cfg(feature = "teams-webhook")]
+            WebhookError::Status { status, body } => {
+                write!(f, "webhook returned {status}: {body}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WebhookError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WebhookError::Serialize(error) => Some(error),
+            #[cfg(feature = "teams-webhook")]
+            WebhookError::Http(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Builds the JSON body that posts `card` to a webhook of the given kind.
+///
+/// Fails with [`WebhookError::TooLarge`] when the body is over [`MAX_PAYLOAD_BYTES`].
+pub fn payload(card: &AdaptiveCard, target: Target) -> Result<Vec<u8>, WebhookError> {
+    let content = serde_json::to_value(card).map_err(WebhookError::Serialize)?;
+    let attachment = match target {
+        // The connector documentation spells out the null `contentUrl`.
+        Target::IncomingWebhook => json!({
+            "contentType": ADAPTIVE_CARD_CONTENT_TYPE,
+            "contentUrl": null,
+            "content": content,
+        }),
+        Target::Workflow => json!({
+            "contentType": ADAPTIVE_CARD_CONTENT_TYPE,
+            "content": content,
+        }),
+    };
+    let message = json!({ "type": "message", "attachments": [attachment] });
+    let body = serde_json::to_vec(&message).map_err(WebhookError::Serialize)?;
+    if body.len() > MAX_PAYLOAD_BYTES {
+        return Err(WebhookError::TooLarge {
+            size: body.len(),
+            limit: MAX_PAYLOAD_BYTES,
+        });
+    }
+    Ok(body)
+}
+
+/// A webhook URL cards can be posted to.
+///
+/// Requests are made with a blocking client, so call [`Webhook::send`] from a thread that is
+/// not running an async runtime, e.g. inside `tokio::task::spawn_blocking`.
+#[cfg(feature = "teams-webhook")]
+#[derive(Debug, Clone)]
+pub struct Webhook {
+    url: String,
+    target: Target,
+    client: reqwest::blocking::Client,
+}
+
+#[cfg(feature = "teams-webhook")]
+impl Webhook {
+    /// Creates a webhook for `url`.
+    pub fn new(url: impl Into<String>, target: Target) -> Self {
+        Self {
+            url: url.into(),
+            target,
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+
+    /// Sends requests with `client`, e.g. one configured with a proxy or timeout.
+    pub fn with_client(mut self, client: reqwest::blocking::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Posts `card` to the webhook.
+    pub fn send(&self, card: &AdaptiveCard) -> Result<(), WebhookError> {
+        let body = payload(card, self.target)?;
+        let response = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .map_err(WebhookError::Http)?;
+        let status = response.status();
+        let text = response.text().map_err(WebhookError::Http)?;
+        // Incoming webhooks answer "1" on success, and some failures (such as throttling)
+        // come back as a 200 with the error in the body.
+        let rejected = match self.target {
+            Target::IncomingWebhook => !status.is_success() || text.trim() != "1",
+            Target::Workflow => !status.is_success(),
+        };
+        if rejected {
+            return Err(WebhookError::Status {
+                status: status.as_u16(),
+                body: text,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expect_test::expect;
+
+    #[test]
+    fn test_payload_envelopes() {
+        let card = AdaptiveCard::builder().text("Hello").build();
+        let incoming = payload(&card, Target::IncomingWebhook).unwrap();
+        expect![[r#"{"attachments":[{"content":{"$schema":"http://adaptivecards.io/schemas/adaptive-card.json","body":[{"text":"Hello","type":"TextBlock"}],"type":"AdaptiveCard","version":"1.2"},"contentType":"application/vnd.microsoft.card.adaptive","contentUrl":null}],"type":"message"}"#]]
+            .assert_eq(std::str::from_utf8(&incoming).unwrap());
+        let workflow = payload(&card, Target::Workflow).unwrap();
+        expect![[r#"{"attachments":[{"content":{"$schema":"http://adaptivecards.io/schemas/adaptive-card.json","body":[{"text":"Hello","type":"TextBlock"}],"type":"AdaptiveCard","version":"1.2"},"contentType":"application/vnd.microsoft.card.adaptive"}],"type":"message"}"#]]
+            .assert_eq(std::str::from_utf8(&workflow).unwrap());
+    }
+
+    #[test]
+    fn test_payload_size_limit() {
+        let fits = AdaptiveCard::builder().text("x".repeat(20 * 1024)).build();
+        assert!(payload(&fits, Target::IncomingWebhook).is_ok());
+
+        let card = AdaptiveCard::builder()
+            .text("x".repeat(MAX_PAYLOAD_BYTES))
+            .build();
+        match payload(&card, Target::Workflow) {
+            Err(WebhookError::TooLarge { size, limit }) => {
+                assert!(size > limit);
+                assert_eq!(limit, 28672);
+            }
+            other => panic!("expected TooLarge, got {other:?}"),
+        }
+    }
+
+    #[cfg(feature = "teams-webhook")]
+    mod send {
+        use super::*;
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::net::TcpListener;
+        use std::thread::{self, JoinHandle};
+
+        /// Serves a single request with `response`, returning the URL and the request's
+        /// head and body.
+        fn serve(response: impl Into<String>) -> (String, JoinHandle<(String, String)>) {
+            let response = response.into();
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}/webhook", listener.local_addr().unwrap());
+            let handle = thread::spawn(move || {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut head = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':')
+                        && name.eq_ignore_ascii_case("content-length")
+                    {
+                        length = value.trim().parse().unwrap();
+                    }
+                    head.push_str(&line);
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                stream.write_all(response.as_bytes()).unwrap();
+                (head, String::from_utf8(body).unwrap())
+            });
+            (url, handle)
+        }
+
+        #[test]
+        fn test_send() {
+            let (url, server) = serve("HTTP/1.1 200 OK\r\nContent-Length: 1\r\n\r\n1");
+            let card = AdaptiveCard::builder().text("Hello").build();
+            Webhook::new(url, Target::IncomingWebhook)
+                .send(&card)
+                .unwrap();
+            let (head, body) = server.join().unwrap();
+            assert!(head.starts_with("POST /webhook HTTP/1.1\r\n"));
+            assert!(
+                head.to_lowercase()
+                    .contains("content-type: application/json")
+            );
+            assert_eq!(
+                body.as_bytes(),
+                payload(&card, Target::IncomingWebhook).unwrap()
+            );
+
+            let (url, server) = serve("HTTP/1.1 202 Accepted\r\nContent-Length: 0\r\n\r\n");
+            Webhook::new(url, Target::Workflow).send(&card).unwrap();
+            server.join().unwrap();
+        }
+
+        #[test]
+        fn test_send_rejected() {
+            let card = AdaptiveCard::builder().text("Hello").build();
+            let (url, server) = serve(
+                "HTTP/1.1 400 Bad Request\r\nContent-Length: 27\r\n\r\nSummary or Text is required",
+            );
+            let error = Webhook::new(url, Target::Workflow).send(&card).unwrap_err();
+            server.join().unwrap();
+            expect!["webhook returned 400: Summary or Text is required"]
+                .assert_eq(&error.to_string());
+
+            let throttled = "Microsoft Teams endpoint returned HTTP error 429";
+            let (url, server) = serve(format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{throttled}",
+                throttled.len()
+            ));
+            let error = Webhook::new(url, Target::IncomingWebhook)
+                .send(&card)
+                .unwrap_err();
+            server.join().unwrap();
+            assert!(matches!(error, WebhookError::Status { status: 200, .. }));
+        }
+
+        #[test]
+        fn test_send_too_large() {
+            // Nothing listens on the URL: the card is rejected before connecting.
+            let card = AdaptiveCard::builder()
+                .text("x".repeat(MAX_PAYLOAD_BYTES))
+                .build();
+            let error = Webhook::new("http://127.0.0.1:9/webhook", Target::Workflow)
+                .send(&card)
+                .unwrap_err();
+            assert!(matches!(error, WebhookError::TooLarge { .. }));
+        }
+    }
+}
@@ -27,7 +27,29 @@
 //! };
 //! assert_eq!(card.body.len(), 1);
 //! ```
+// Lets code generated by the derive macros name this crate from inside it.
+extern crate self as adaptive_card_rs;
+
 pub mod actions;
+pub mod bot;
+pub mod builder;
 pub mod card;
 pub mod common;
+pub mod convert;
+pub mod downlevel;
+pub mod dsl;
+pub mod expressions;
+pub mod form;
+pub mod host_config;
 pub mod inputs;
+pub mod integrations;
+pub mod lint;
+pub mod query;
+pub mod render;
+#[cfg(feature = "schema-validation")]
+pub mod schema;
+pub mod submission;
+pub mod teams;
+pub mod template;
+pub mod version;
+pub mod visit;
@@ -0,0 +1,479 @@
+//! Semantic checks for cards that are valid according to the JSON schema but will not behave
+//! as intended when rendered.
+//!
+//! ```
+//! use adaptive_card_rs::card::{AdaptiveCard, CardElement, FactSet};
+//! use adaptive_card_rs::lint::Severity;
+//!
+//! let card = AdaptiveCard {
+//!     body: vec![CardElement::FactSet(FactSet { facts: vec![] })],
+//!     ..Default::default()
+//! };
+//! let diagnostics = card.lint();
+//! assert_eq!(diagnostics[0].code, "empty-fact-set");
+//! assert_eq!(diagnostics[0].severity, Severity::Warning);
+//! ```
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::actions::Action;
+use crate::card::{AdaptiveCard, CardElement, Column};
+use crate::version::ExceedsDeclaredVersion;
+use crate::visit::Visitor;
+
+/// How serious a diagnostic is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The card will render, but probably not as the author intended.
+    Warning,
+    /// The card is broken and will misbehave for users.
+    Error,
+}
+
+/// A single problem reported by a lint rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// How serious the problem is.
+    pub severity: Severity,
+    /// Stable, kebab-case identifier of the rule that produced the diagnostic.
+    pub code: &'static str,
+    /// JSON pointer to the offending element or action, e.g. `/body/0/items/1`.
+    pub path: String,
+    /// Human readable description of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(
+            f,
+            "{severity}[{}] {}: {}",
+            self.code, self.path, self.message
+        )
+    }
+}
+
+/// A check run against a whole card.
+///
+/// Implement this trait to add project-specific rules to a [`Linter`].
+pub trait Rule {
+    /// Inspects the card and appends any problems found to `diagnostics`.
+    fn check(&self, card: &AdaptiveCard, diagnostics: &mut Vec<Diagnostic>);
+}
+
+/// Runs a set of rules against a card.
+pub struct Linter {
+    rules: Vec<Box<dyn Rule>>,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self {
+            rules: vec![
+                Box::new(DuplicateInputId),
+                Box::new(UnknownToggleTarget),
+                Box::new(ShowCardSelectAction),
+                Box::new(EmptyFactSet),
+                Box::new(ChoiceValueNotInChoices),
+                Box::new(ExceedsDeclaredVersion),
+            ],
+        }
+    }
+}
+
+impl Linter {
+    /// Creates a linter with the built-in rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a linter with no rules.
+    pub fn empty() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Adds a rule to the linter.
+    pub fn with_rule(mut self, rule: impl Rule + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Runs every rule against the card and returns the diagnostics in rule order.
+    pub fn lint(&self, card: &AdaptiveCard) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for rule in &self.rules {
+            rule.check(card, &mut diagnostics);
+        }
+        diagnostics
+    }
+}
+
+impl AdaptiveCard {
+    /// Runs the built-in lint rules against this card.
+    pub fn lint(&self) -> Vec<Diagnostic> {
+        Linter::new().lint(self)
+    }
+}
+
+/// Reports inputs that share an `id`, which makes their submitted values collide.
+pub struct DuplicateInputId;
+
+impl Rule for DuplicateInputId {
+    fn check(&self, card: &AdaptiveCard, diagnostics: &mut Vec<Diagnostic>) {
+        let mut seen: HashMap<String, String> = HashMap::new();
+        walk_card(card, |path, node| {
+            if let Node::Element(element) = node {
+                if !element.is_input() {
+                    return;
+                }
+                let id = element.id().unwrap_or_default();
+                match seen.get(id) {
+                    Some(first) => diagnostics.push(Diagnostic {
+                        severity: Severity::Error,
+                        code: "duplicate-input-id",
+                        path: path.to_string(),
+                        message: format!("input id \"{id}\" is already used at {first}"),
+                    }),
+                    None => {
+                        seen.insert(id.to_string(), path.to_string());
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Reports `Action.ToggleVisibility` targets that do not match any element id in the card.
+pub struct UnknownToggleTarget;
+
+impl Rule for UnknownToggleTarget {
+    fn check(&self, card: &AdaptiveCard, diagnostics: &mut Vec<Diagnostic>) {
+        let mut ids = HashSet::new();
+        walk_card(card, |_, node| match node {
+            Node::Element(element) => ids.extend(element.id().map(str::to_string)),
+            Node::Column(column) => ids.extend(column.id.clone()),
+            _ => {}
+        });
+
+        walk_card(card, |path, node| {
+            if let Node::Action(Action::ToggleVisibility(action))
+            | Node::SelectAction(Action::ToggleVisibility(action)) = node
+            {
+                for target in &action.target_elements {
+                    if !ids.contains(target) {
+                        diagnostics.push(Diagnostic {
+                            severity: Severity::Error,
+                            code: "unknown-toggle-target",
+                            path: path.to_string(),
+                            message: format!("target element \"{target}\" does not exist"),
+                        });
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Reports `Action.ShowCard` used as a `selectAction`, which hosts do not support.
+pub struct ShowCardSelectAction;
+
+impl Rule for ShowCardSelectAction {
+    fn check(&self, card: &AdaptiveCard, diagnostics: &mut Vec<Diagnostic>) {
+        walk_card(card, |path, node| {
+            if let Node::SelectAction(Action::ShowCard(_)) = node {
+                diagnostics.push(Diagnostic {
+                    severity: Severity::Error,
+                    code: "show-card-select-action",
+                    path: path.to_string(),
+                    message: "Action.ShowCard is not supported as a selectAction".to_string(),
+                });
+            }
+        });
+    }
+}
+
+/// Reports `FactSet` elements without any facts.
+pub struct EmptyFactSet;
+
+impl Rule for EmptyFactSet {
+    fn check(&self, card: &AdaptiveCard, diagnostics: &mut Vec<Diagnostic>) {
+        walk_card(card, |path, node| {
+            if let Node::Element(CardElement::FactSet(fact_set)) = node
+                && fact_set.facts.is_empty()
+            {
+                diagnostics.push(Diagnostic {
+                    severity: Severity::Warning,
+                    code: "empty-fact-set",
+                    path: path.to_string(),
+                    message: "FactSet has no facts and will render nothing".to_string(),
+                });
+            }
+        });
+    }
+}
+
+/// Reports `Input.ChoiceSet` initial values that are not among its choices.
+pub struct ChoiceValueNotInChoices;
+
+impl Rule for ChoiceValueNotInChoices {
+    fn check(&self, card: &AdaptiveCard, diagnostics: &mut Vec<Diagnostic>) {
+        walk_card(card, |path, node| {
+            let Node::Element(CardElement::InputChoiceSet(input)) = node else {
+                return;
+            };
+            let Some(value) = input.value.as_deref().filter(|v| !v.is_empty()) else {
+                return;
+            };
+            let choices = input.choices.as_deref().unwrap_or_default();
+            // Multi-select values are read the way submissions are: trimmed, empty ones dropped.
+            let values: Vec<&str> = if input.is_multi_select.unwrap_or(false) {
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .collect()
+            } else {
+                vec![value]
+            };
+            for value in values {
+                if !choices.iter().any(|choice| choice.value == value) {
+                    diagnostics.push(Diagnostic {
+                        severity: Severity::Error,
+                        code: "choice-value-not-in-choices",
+                        path: path.to_string(),
+                        message: format!(
+                            "value \"{value}\" of input \"{}\" is not one of its choices",
+                            input.id
+                        ),
+                    });
+                }
+            }
+        });
+    }
+}
+
+enum Node<'a> {
+    Element(&'a CardElement),
+    Column(&'a Column),
+    Action(&'a Action),
+    SelectAction(&'a Action),
+}
+
+/// Adapts a closure over [`Node`]s to a [`Visitor`], so rules can be written inline.
+struct NodeVisitor<F>(F);
+
+impl<'a, F: FnMut(&str, Node<'a>)> Visitor<'a> for NodeVisitor<F> {
+    fn visit_element(&mut self, path: &str, element: &'a CardElement) {
+        (self.0)(path, Node::Element(element));
+    }
+
+    fn visit_column(&mut self, path: &str, column: &'a Column) {
+        (self.0)(path, Node::Column(column));
+    }
+
+    fn visit_action(&mut self, path: &str, action: &'a Action) {
+        (self.0)(path, Node::Action(action));
+    }
+
+    fn visit_select_action(&mut self, path: &str, action: &'a Action) {
+        (self.0)(path, Node::SelectAction(action));
+    }
+}
+
+fn walk_card<'a>(card: &'a AdaptiveCard, f: impl FnMut(&str, Node<'a>)) {
+    card.walk(&mut NodeVisitor(f));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{ActionSet, ShowCardAction, ToggleVisibilityAction};
+    use crate::card::{Container, FactSet, TextBlock};
+    use crate::inputs::{InputChoice, InputChoiceSet, InputToggle};
+
+    fn toggle(id: &str) -> CardElement {
+        CardElement::InputToggle(InputToggle {
+            id: id.to_string(),
+            title: "Toggle".to_string(),
+            value: None,
+            value_on: None,
+            value_off: None,
+            wrap: None,
+            label: None,
+            is_required: None,
+            error_message: None,
+            separator: None,
+            spacing: None,
+            height: None,
+            is_visible: None,
+        })
+    }
+
+    fn toggle_visibility(targets: &[&str]) -> Action {
+        Action::ToggleVisibility(ToggleVisibilityAction {
+            title: None,
+            target_elements: targets.iter().map(|t| t.to_string()).collect(),
+            id: None,
+            icon_url: None,
+            style: None,
+            tooltip: None,
+            is_enabled: None,
+            mode: None,
+        })
+    }
+
+    fn codes(card: &AdaptiveCard) -> Vec<(&'static str, String)> {
+        card.lint().into_iter().map(|d| (d.code, d.path)).collect()
+    }
+
+    #[test]
+    fn test_clean_card_has_no_diagnostics() {
+        let card = AdaptiveCard {
+            body: vec![
+                CardElement::TextBlock(TextBlock {
+                    id: Some("details".to_string()),
+                    text: "Details".to_string(),
+                    ..Default::default()
+                }),
+                toggle("accept"),
+            ],
+            actions: Some(vec![toggle_visibility(&["details"])]),
+            ..Default::default()
+        };
+
+        assert_eq!(card.lint(), vec![]);
+    }
+
+    #[test]
+    fn test_duplicate_input_ids_across_show_card() {
+        let card = AdaptiveCard {
+            body: vec![toggle("accept")],
+            actions: Some(vec![Action::ShowCard(ShowCardAction {
+                title: None,
+                card: Box::new(AdaptiveCard {
+                    body: vec![toggle("accept")],
+                    ..Default::default()
+                }),
+                id: None,
+                icon_url: None,
+                style: None,
+                tooltip: None,
+                is_enabled: None,
+                mode: None,
+            })]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            codes(&card),
+            vec![("duplicate-input-id", "/actions/0/card/body/0".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_unknown_toggle_target_and_show_card_select_action() {
+        let card = AdaptiveCard {
+            body: vec![CardElement::Container(Container {
+                items: vec![CardElement::ActionSet(ActionSet {
+                    actions: vec![toggle_visibility(&["missing"])],
+                })],
+                select_action: Some(Box::new(Action::ShowCard(ShowCardAction {
+                    title: None,
+                    card: Box::default(),
+                    id: None,
+                    icon_url: None,
+                    style: None,
+                    tooltip: None,
+                    is_enabled: None,
+                    mode: None,
+                }))),
+                ..Default::default()
+            })],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            codes(&card),
+            vec![
+                (
+                    "unknown-toggle-target",
+                    "/body/0/items/0/actions/0".to_string()
+                ),
+                (
+                    "show-card-select-action",
+                    "/body/0/selectAction".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_choice_value_not_in_choices() {
+        let card = AdaptiveCard {
+            body: vec![CardElement::InputChoiceSet(InputChoiceSet {
+                id: "colors".to_string(),
+                choices: Some(vec![
+                    InputChoice {
+                        title: "Red".to_string(),
+                        value: "red".to_string(),
+                    },
+                    InputChoice {
+                        title: "Blue".to_string(),
+                        value: "blue".to_string(),
+                    },
+                ]),
+                is_multi_select: Some(true),
+                style: None,
+                value: Some("red, blue,,green".to_string()),
+                placeholder: None,
+                wrap: None,
+                label: None,
+                is_required: None,
+                error_message: None,
+                separator: None,
+                spacing: None,
+                height: None,
+                is_visible: None,
+            })],
+            ..Default::default()
+        };
+
+        let diagnostics = card.lint();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "choice-value-not-in-choices");
+        assert!(diagnostics[0].message.contains("\"green\""));
+    }
+
+    #[test]
+    fn test_custom_rule() {
+        struct RequireFallbackText;
+
+        impl Rule for RequireFallbackText {
+            fn check(&self, card: &AdaptiveCard, diagnostics: &mut Vec<Diagnostic>) {
+                if card.fallback_text.is_none() {
+                    diagnostics.push(Diagnostic {
+                        severity: Severity::Warning,
+                        code: "missing-fallback-text",
+                        path: String::new(),
+                        message: "card has no fallbackText".to_string(),
+                    });
+                }
+            }
+        }
+
+        let card = AdaptiveCard {
+            body: vec![CardElement::FactSet(FactSet { facts: vec![] })],
+            ..Default::default()
+        };
+        let diagnostics = Linter::new().with_rule(RequireFallbackText).lint(&card);
+
+        assert_eq!(
+            diagnostics.iter().map(|d| d.code).collect::<Vec<_>>(),
+            vec!["empty-fact-set", "missing-fallback-text"]
+        );
+    }
+}
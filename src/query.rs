@@ -0,0 +1,346 @@
+//! Finding elements and actions in a card.
+//!
+//! The methods here are available on both [`AdaptiveCard`] and [`Container`]. They descend
+//! through containers, columns, table cells and the cards shown by `Action.ShowCard`,
+//! including the refresh action's, and return matches in document order.
+//!
+//! Each call walks the whole card with [`walk`](AdaptiveCard::walk) and collects what it
+//! reached into a `Vec` before returning, so `find_by_id` and adapters such as `take` or
+//! `count` don't stop the walk early, and every call walks again. To run many queries on a
+//! large card, collect [`elements`](AdaptiveCard::elements) once and filter that.
+//!
+//! ```
+//! use adaptive_card_rs::card::{AdaptiveCard, CardElement, TextBlock};
+//!
+//! let mut card = AdaptiveCard::builder()
+//!     .text_block("Pending", |t| t.id("status"))
+//!     .container(|c| c.text("Details").input_text("comment", |i| i))
+//!     .build();
+//!
+//! if let Some(CardElement::TextBlock(status)) = card.find_by_id_mut("status") {
+//!     status.text = "Done".to_string();
+//! }
+//!
+//! let texts: Vec<&str> = card.elements_of::<TextBlock>().map(|t| t.text.as_str()).collect();
+//! assert_eq!(texts, ["Done", "Details"]);
+//! assert_eq!(card.inputs().count(), 1);
+//! ```
+use crate::actions::Action;
+use crate::card::{
+    AdaptiveCard, CardElement, ColumnSet, Container, FactSet, Image, ImageSet, Media,
+    RichTextBlock, Table, TextBlock,
+};
+use crate::inputs::{InputChoiceSet, InputDate, InputNumber, InputText, InputTime, InputToggle};
+use crate::visit::{Visitor, walk_action, walk_elements};
+
+/// A type wrapped by one of the [`CardElement`] variants, used by `elements_of`.
+pub trait ElementType {
+    /// Returns the wrapped value if `element` is this type's variant.
+    fn from_element(element: &CardElement) -> Option<&Self>;
+}
+
+macro_rules! element_type {
+    ($($variant:ident => $ty:ty),* $(,)?) => {
+        $(
+            impl ElementType for $ty {
+                fn from_element(element: &CardElement) -> Option<&Self> {
+                    match element {
+                        CardElement::$variant(inner) => Some(inner),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+element_type!(
+    TextBlock => TextBlock,
+    Container => Container,
+    ColumnSet => ColumnSet,
+    Image => Image,
+    ActionSet => crate::actions::ActionSet,
+    FactSet => FactSet,
+    RichTextBlock => RichTextBlock,
+    Table => Table,
+    Media => Media,
+    ImageSet => ImageSet,
+    InputText => InputText,
+    InputNumber => InputNumber,
+    InputDate => InputDate,
+    InputTime => InputTime,
+    InputToggle => InputToggle,
+    InputChoiceSet => InputChoiceSet,
+);
+
+/// Collects every element and action reached by a walk.
+#[derive(Default)]
+struct Collect<'a> {
+    elements: Vec<&'a CardElement>,
+    actions: Vec<&'a Action>,
+}
+
+impl<'a> Visitor<'a> for Collect<'a> {
+    fn visit_element(&mut self, _path: &str, element: &'a CardElement) {
+        self.elements.push(element);
+    }
+
+    fn visit_action(&mut self, _path: &str, action: &'a Action) {
+        self.actions.push(action);
+    }
+}
+
+macro_rules! query_methods {
+    ($ty:ty) => {
+        impl $ty {
+            /// Returns every element, including nested ones. The elements are collected
+            /// before the first is returned.
+            pub fn elements(&self) -> impl Iterator<Item = &CardElement> {
+                self.collect().elements.into_iter()
+            }
+
+            /// Returns every input element.
+            pub fn inputs(&self) -> impl Iterator<Item = &CardElement> {
+                self.elements().filter(|element| element.is_input())
+            }
+
+            /// Returns every action, including select actions and those in action sets. The
+            /// actions are collected before the first is returned.
+            pub fn actions(&self) -> impl Iterator<Item = &Action> {
+                self.collect().actions.into_iter()
+            }
+
+            /// Returns every element of type `T`, e.g. `elements_of::<TextBlock>()`.
+            pub fn elements_of<'a, T: ElementType + 'a>(&'a self) -> impl Iterator<Item = &'a T> {
+                self.elements().filter_map(T::from_element)
+            }
+
+            /// Returns the first element with the given id.
+            pub fn find_by_id(&self, id: &str) -> Option<&CardElement> {
+                self.elements().find(|element| element.id() == Some(id))
+            }
+        }
+    };
+}
+
+query_methods!(AdaptiveCard);
+query_methods!(Container);
+
+impl AdaptiveCard {
+    fn collect(&self) -> Collect<'_> {
+        let mut collect = Collect::default();
+        self.walk(&mut collect);
+        collect
+    }
+
+    /// Returns the first element with the given id, so that it can be changed in place.
+    ///
+    /// Unlike the other queries this stops at the first match. It searches the same places
+    /// as [`walk_mut`](AdaptiveCard::walk_mut), in the same order, so it finds the element
+    /// [`find_by_id`](AdaptiveCard::find_by_id) returns.
+    pub fn find_by_id_mut(&mut self, id: &str) -> Option<&mut CardElement> {
+        find_in_card_mut(self, id)
+    }
+}
+
+impl Container {
+    fn collect(&self) -> Collect<'_> {
+        let mut collect = Collect::default();
+        walk_elements(&mut collect, "/items", &self.items);
+        if let Some(action) = &self.select_action {
+            walk_action(&mut collect, "/selectAction", action);
+        }
+        collect
+    }
+
+    /// Returns the first element with the given id, so that it can be changed in place.
+    pub fn find_by_id_mut(&mut self, id: &str) -> Option<&mut CardElement> {
+        find_in_elements_mut(&mut self.items, id)
+            .or_else(|| find_in_action_mut(self.select_action.as_deref_mut(), id))
+    }
+}
+
+fn find_in_card_mut<'a>(card: &'a mut AdaptiveCard, id: &str) -> Option<&'a mut CardElement> {
+    if let Some(found) = find_in_elements_mut(&mut card.body, id) {
+        return Some(found);
+    }
+    if let Some(found) = card
+        .actions
+        .iter_mut()
+        .flatten()
+        .find_map(|action| find_in_action_mut(Some(action), id))
+    {
+        return Some(found);
+    }
+    if let Some(found) = find_in_action_mut(card.select_action.as_deref_mut(), id) {
+        return Some(found);
+    }
+    find_in_action_mut(card.refresh.as_mut().map(|refresh| &mut refresh.action), id)
+}
+
+fn find_in_elements_mut<'a>(
+    elements: &'a mut [CardElement],
+    id: &str,
+) -> Option<&'a mut CardElement> {
+    for element in elements {
+        if element.id() == Some(id) {
+            return Some(element);
+        }
+        if let Some(found) = find_in_children_mut(element, id) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+fn find_in_children_mut<'a>(element: &'a mut CardElement, id: &str) -> Option<&'a mut CardElement> {
+    match element {
+        CardElement::Container(container) => container.find_by_id_mut(id),
+        CardElement::ColumnSet(column_set) => {
+            for column in &mut column_set.columns {
+                if let Some(found) = find_in_elements_mut(&mut column.items, id) {
+                    return Some(found);
+                }
+                if let Some(found) = find_in_action_mut(column.select_action.as_deref_mut(), id) {
+                    return Some(found);
+                }
+            }
+            find_in_action_mut(column_set.select_action.as_deref_mut(), id)
+        }
+        CardElement::Image(image) => find_in_action_mut(image.select_action.as_deref_mut(), id),
+        CardElement::ImageSet(image_set) => image_set
+            .images
+            .iter_mut()
+            .find_map(|image| find_in_action_mut(image.select_action.as_deref_mut(), id)),
+        CardElement::ActionSet(action_set) => action_set
+            .actions
+            .iter_mut()
+            .find_map(|action| find_in_action_mut(Some(action), id)),
+        CardElement::Table(table) => {
+            for cell in table
+                .rows
+                .iter_mut()
+                .flatten()
+                .flat_map(|row| &mut row.cells)
+            {
+                if let Some(found) = find_in_elements_mut(&mut cell.items, id) {
+                    return Some(found);
+                }
+                if let Some(found) = find_in_action_mut(cell.select_action.as_deref_mut(), id) {
+                    return Some(found);
+                }
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+fn find_in_action_mut<'a>(action: Option<&'a mut Action>, id: &str) -> Option<&'a mut CardElement> {
+    match action? {
+        Action::ShowCard(show_card) => find_in_card_mut(&mut show_card.card, id),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::ShowCardAction;
+    use crate::card::Refresh;
+
+    fn card() -> AdaptiveCard {
+        AdaptiveCard::builder()
+            .text_block("Pending", |t| t.id("status"))
+            .column_set(|c| {
+                c.column(|c| c.text("Left").input_number("amount", |i| i))
+                    .column(|c| c.fact_set(|f| f.fact("Owner", "Ada")))
+            })
+            .show_card(
+                AdaptiveCard::builder()
+                    .container(|c| c.id("nested").input_text("comment", |i| i))
+                    .submit(|a| a.title("Send"))
+                    .build(),
+                |a| a.title("Comment"),
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_queries_descend_into_columns_and_show_cards() {
+        let card = card();
+
+        let inputs: Vec<_> = card.inputs().filter_map(CardElement::id).collect();
+        assert_eq!(inputs, ["amount", "comment"]);
+
+        let texts: Vec<_> = card
+            .elements_of::<TextBlock>()
+            .map(|t| t.text.as_str())
+            .collect();
+        assert_eq!(texts, ["Pending", "Left"]);
+
+        assert_eq!(card.elements_of::<FactSet>().count(), 1);
+        assert_eq!(card.actions().count(), 2);
+        assert!(matches!(
+            card.find_by_id("comment"),
+            Some(CardElement::InputText(_))
+        ));
+        assert!(card.find_by_id("missing").is_none());
+    }
+
+    #[test]
+    fn test_find_by_id_mut() {
+        let mut card = card();
+
+        if let Some(CardElement::TextBlock(status)) = card.find_by_id_mut("status") {
+            status.text = "Done".to_string();
+        }
+        let Some(CardElement::Container(nested)) = card.find_by_id_mut("nested") else {
+            panic!("expected the nested container");
+        };
+        if let Some(CardElement::InputText(comment)) = nested.find_by_id_mut("comment") {
+            comment.placeholder = Some("Say something".to_string());
+        }
+
+        assert!(matches!(
+            card.find_by_id("status"),
+            Some(CardElement::TextBlock(TextBlock { text, .. })) if text == "Done"
+        ));
+        let Some([Action::ShowCard(ShowCardAction { card: shown, .. }), ..]) =
+            card.actions.as_deref()
+        else {
+            panic!("expected a show card action");
+        };
+        assert_eq!(shown.inputs().count(), 1);
+        assert!(matches!(
+            shown.find_by_id("comment"),
+            Some(CardElement::InputText(InputText { placeholder: Some(p), .. })) if p == "Say something"
+        ));
+    }
+
+    #[test]
+    fn test_find_in_refresh_show_card() {
+        let mut card = AdaptiveCard::builder().text("Stale").build();
+        card.refresh = Some(
+            Refresh::builder(ShowCardAction::builder(
+                AdaptiveCard::builder()
+                    .text_block("Refreshed", |t| t.id("fresh"))
+                    .build(),
+            ))
+            .build(),
+        );
+
+        assert!(matches!(
+            card.find_by_id("fresh"),
+            Some(CardElement::TextBlock(_))
+        ));
+        let Some(CardElement::TextBlock(fresh)) = card.find_by_id_mut("fresh") else {
+            panic!("expected the text block in the refresh card");
+        };
+        fresh.text = "Updated".to_string();
+        assert!(matches!(
+            card.find_by_id("fresh"),
+            Some(CardElement::TextBlock(TextBlock { text, .. })) if text == "Updated"
+        ));
+    }
+}
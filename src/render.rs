@@ -0,0 +1,7 @@
+//! Renderers that turn cards into formats shown without an Adaptive Cards SDK.
+//!
+//! - [`html`] produces self-contained HTML for web pages and emails.
+//! - [`text`] produces plain text or Markdown for channels that cannot show cards.
+pub mod html;
+pub(crate) mod markdown;
+pub mod text;
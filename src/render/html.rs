@@ -0,0 +1,1221 @@
+//! Renders cards as self-contained HTML.
+//!
+//! All styling is inline, so the output can be embedded in a web page or an email without a
+//! stylesheet or script. Sizes and colors come from a [`HostConfig`].
+//!
+//! ```
+//! use adaptive_card_rs::card::AdaptiveCard;
+//! use adaptive_card_rs::host_config::HostConfig;
+//! use adaptive_card_rs::render::html;
+//!
+//! let card = AdaptiveCard::builder().text("Hello").build();
+//! let html = html::render(&card, &HostConfig::default());
+//! assert!(html.contains(">Hello</p>"));
+//! ```
+//!
+//! Elements map to semantic HTML: facts and tables become `<table>`s, inputs become form
+//! controls inside a `<form>`, and actions become links and buttons. Without scripts, some
+//! behaviour is approximated:
+//!
+//! - `Action.ShowCard` is a `<details>` element that expands to show its card.
+//! - `Action.Submit` and `Action.Execute` submit the form, sending the action's `data` as JSON
+//!   in a `data` field or its verb in a `verb` field.
+//! - `Action.ToggleVisibility` is a button with a `data-target-elements` attribute listing
+//!   the ids of the elements it toggles; elements with `isVisible: false` are `hidden`.
+use std::fmt::Write;
+
+use crate::actions::Action;
+use crate::card::{
+    AdaptiveCard, CardElement, ColumnSet, ColumnSizing, Container, ContainerStyle, FactSet, Image,
+    ImageSet, ImageSize, Inline, Media, RichTextBlock, Spacing, Table, TextBlock, TextSize,
+    TextWeight,
+};
+use crate::common::{
+    ActionStyle, ChoiceInputStyle, Color, FontType, HorizontalAlignment, TextBlockStyle,
+    TextInputStyle, VerticalContentAlignment,
+};
+use crate::host_config::{ActionsOrientation, FactSetTextConfig, HostConfig, css_color};
+use crate::inputs::{InputChoiceSet, InputDate, InputNumber, InputText, InputTime, InputToggle};
+use crate::render::markdown::{self, Span};
+
+/// Renders `card` as an HTML fragment styled by `config`.
+pub fn render(card: &AdaptiveCard, config: &HostConfig) -> String {
+    let mut renderer = Renderer {
+        config,
+        out: String::new(),
+        style: ContainerStyle::Default,
+    };
+    renderer.card(card, false);
+    renderer.out
+}
+
+struct Renderer<'a> {
+    config: &'a HostConfig,
+    out: String,
+    /// The style of the innermost container, which determines text colors.
+    style: ContainerStyle,
+}
+
+impl Renderer<'_> {
+    fn card(&mut self, card: &AdaptiveCard, nested: bool) {
+        let font = &self.config.font_type(None).font_family;
+        let padding = self.config.spacing(&Spacing::Padding);
+        let mut style = if nested {
+            let show_card = &self.config.actions.show_card;
+            let background = self.background(&show_card.style);
+            self.style = show_card.style.clone();
+            format!(
+                "margin-top:{}px;padding:{padding}px;{background}",
+                show_card.inline_top_margin
+            )
+        } else {
+            let background = self.background(&ContainerStyle::Default);
+            format!(
+                "font-family:{font};padding:{padding}px;border:1px solid {};border-radius:4px;{background}",
+                css_color(&self.config.separator.line_color)
+            )
+        };
+        if let Some(min_height) = card.min_height.as_deref().and_then(pixels) {
+            let _ = write!(style, "min-height:{min_height};");
+        }
+
+        self.out.push_str("<div class=\"ac-card\"");
+        if let Some(lang) = &card.lang {
+            let _ = write!(self.out, " lang=\"{}\"", escape(lang));
+        }
+        if card.rtl == Some(true) {
+            self.out.push_str(" dir=\"rtl\"");
+        }
+        let _ = writeln!(self.out, " style=\"{}\">", escape(&style));
+
+        let form = !nested && self.config.supports_interactivity && card.inputs().next().is_some();
+        if form {
+            self.out.push_str("<form method=\"post\">\n");
+        }
+        self.elements(&card.body);
+        if let Some(actions) = &card.actions {
+            self.actions(actions, !card.body.is_empty());
+        }
+        if form {
+            self.out.push_str("</form>\n");
+        }
+        self.out.push_str("</div>\n");
+    }
+
+    fn elements(&mut self, elements: &[CardElement]) {
+        let elements = elements
+            .iter()
+            .filter(|element| self.config.supports_interactivity || !is_interactive(element));
+        for (i, element) in elements.enumerate() {
+            let mut style = String::new();
+            if i > 0 {
+                let spacing = self
+                    .config
+                    .spacing(element.spacing().unwrap_or(&Spacing::Default));
+                if element.separator() {
+                    let separator = &self.config.separator;
+                    let _ = write!(
+                        style,
+                        "margin-top:{spacing}px;padding-top:{spacing}px;border-top:{}px solid {};",
+                        separator.line_thickness,
+                        css_color(&separator.line_color)
+                    );
+                } else {
+                    let _ = write!(style, "margin-top:{spacing}px;");
+                }
+            }
+
+            self.out.push_str("<div");
+            if let Some(id) = element.id() {
+                let _ = write!(self.out, " id=\"{}\"", escape(id));
+            }
+            if !style.is_empty() {
+                let _ = write!(self.out, " style=\"{style}\"");
+            }
+            if !element.is_visible() {
+                self.out.push_str(" hidden");
+            }
+            self.out.push('>');
+            self.element(element);
+            self.out.push_str("</div>\n");
+        }
+    }
+
+    fn element(&mut self, element: &CardElement) {
+        match element {
+            CardElement::TextBlock(text_block) => self.text_block(text_block),
+            CardElement::RichTextBlock(rich_text) => self.rich_text_block(rich_text),
+            CardElement::Container(container) => self.container(container),
+            CardElement::ColumnSet(column_set) => self.column_set(column_set),
+            CardElement::Image(image) => self.image(image, None),
+            CardElement::ImageSet(image_set) => self.image_set(image_set),
+            CardElement::FactSet(fact_set) => self.fact_set(fact_set),
+            CardElement::Table(table) => self.table(table),
+            CardElement::Media(media) => self.media(media),
+            CardElement::ActionSet(action_set) => self.actions(&action_set.actions, false),
+            CardElement::InputText(input) => self.input_text(input),
+            CardElement::InputNumber(input) => self.input_number(input),
+            CardElement::InputDate(input) => self.input_date(input),
+            CardElement::InputTime(input) => self.input_time(input),
+            CardElement::InputToggle(input) => self.input_toggle(input),
+            CardElement::InputChoiceSet(input) => self.input_choice_set(input),
+        }
+    }
+
+    fn text_block(&mut self, text_block: &TextBlock) {
+        // Styled text takes its settings from the host config unless the block overrides them.
+        let text_style = text_block
+            .style
+            .as_ref()
+            .and_then(|style| self.config.text_style(style));
+        let mut style = self.text_style(
+            text_block
+                .font_type
+                .as_ref()
+                .or(text_style.map(|s| &s.font_type)),
+            text_block.size.as_ref().or(text_style.map(|s| &s.size)),
+            text_block.weight.as_ref().or(text_style.map(|s| &s.weight)),
+            text_block.color.as_ref().or(text_style.map(|s| &s.color)),
+            text_block
+                .is_subtle
+                .or(text_style.map(|s| s.is_subtle))
+                .unwrap_or_default(),
+        );
+        style.push_str(alignment(text_block.horizontal_alignment.as_ref()));
+        match (text_block.wrap, text_block.max_lines) {
+            (Some(true), Some(lines)) => {
+                let _ = write!(
+                    style,
+                    "display:-webkit-box;-webkit-box-orient:vertical;-webkit-line-clamp:{lines};overflow:hidden;"
+                );
+            }
+            (Some(true), None) => {}
+            _ => style.push_str("white-space:nowrap;overflow:hidden;text-overflow:ellipsis;"),
+        }
+        let heading = matches!(text_block.style, Some(TextBlockStyle::Heading));
+        let tag = if heading { "h2" } else { "p" };
+        let _ = write!(
+            self.out,
+            "<{tag} style=\"{style}\">{}</{tag}>",
+            markdown(&text_block.text)
+        );
+    }
+
+    fn rich_text_block(&mut self, rich_text: &RichTextBlock) {
+        let mut style = self.text_style(None, None, None, None, false);
+        style.push_str(alignment(rich_text.horizontal_alignment.as_ref()));
+        let _ = write!(self.out, "<p style=\"{style}\">");
+        for inline in &rich_text.inlines {
+            match inline {
+                Inline::Text(text) => self.out.push_str(&escape(text)),
+                Inline::TextRun(run) => {
+                    let mut style = self.text_style(
+                        run.font_type.as_ref(),
+                        run.size.as_ref(),
+                        run.weight.as_ref(),
+                        run.color.as_ref(),
+                        run.is_subtle == Some(true),
+                    );
+                    style = style.replacen("margin:0;", "", 1);
+                    if run.italic == Some(true) {
+                        style.push_str("font-style:italic;");
+                    }
+                    if run.strikethrough == Some(true) {
+                        style.push_str("text-decoration:line-through;");
+                    }
+                    let tag = if run.highlight == Some(true) {
+                        "mark"
+                    } else {
+                        "span"
+                    };
+                    let _ = write!(
+                        self.out,
+                        "<{tag} style=\"{style}\">{}</{tag}>",
+                        escape(&run.text)
+                    );
+                }
+            }
+        }
+        self.out.push_str("</p>");
+    }
+
+    fn container(&mut self, container: &Container) {
+        let style = self.box_style(
+            container.style.as_ref(),
+            container.vertical_content_alignment.as_ref(),
+            container.min_height.as_deref(),
+        );
+        let link = self.open_link(container.select_action.as_deref());
+        let _ = writeln!(self.out, "<div style=\"{style}\">");
+        self.styled(container.style.as_ref(), |renderer| {
+            renderer.elements(&container.items)
+        });
+        self.out.push_str("</div>");
+        self.close_link(link);
+    }
+
+    fn column_set(&mut self, column_set: &ColumnSet) {
+        let mut style = self.box_style(
+            column_set.style.as_ref(),
+            None,
+            column_set.min_height.as_deref(),
+        );
+        style.push_str("display:flex;");
+        let link = self.open_link(column_set.select_action.as_deref());
+        let _ = writeln!(self.out, "<div style=\"{style}\">");
+        self.styled(column_set.style.as_ref(), |renderer| {
+            for (i, column) in column_set.columns.iter().enumerate() {
+                let mut style = match column.width.as_ref().map(|width| width.sizing()) {
+                    Some(ColumnSizing::Auto) => "flex:0 0 auto;".to_string(),
+                    Some(ColumnSizing::Pixels(px)) => format!("flex:0 0 {px}px;"),
+                    Some(ColumnSizing::Weight(weight)) => format!("flex:{weight} 1 0;"),
+                    Some(ColumnSizing::Stretch) | None => "flex:1 1 0;".to_string(),
+                };
+                style.push_str("min-width:0;");
+                if i > 0 {
+                    let spacing = renderer
+                        .config
+                        .spacing(column.spacing.as_ref().unwrap_or(&Spacing::Default));
+                    if column.separator == Some(true) {
+                        let separator = &renderer.config.separator;
+                        let _ = write!(
+                            style,
+                            "margin-left:{spacing}px;padding-left:{spacing}px;border-left:{}px solid {};",
+                            separator.line_thickness,
+                            css_color(&separator.line_color)
+                        );
+                    } else {
+                        let _ = write!(style, "margin-left:{spacing}px;");
+                    }
+                }
+                style.push_str(&renderer.box_style(
+                    column.style.as_ref(),
+                    column.vertical_content_alignment.as_ref(),
+                    column.min_height.as_deref(),
+                ));
+
+                let link = renderer.open_link(column.select_action.as_deref());
+                renderer.out.push_str("<div");
+                if let Some(id) = &column.id {
+                    let _ = write!(renderer.out, " id=\"{}\"", escape(id));
+                }
+                let _ = write!(renderer.out, " style=\"{style}\"");
+                if column.is_visible == Some(false) {
+                    renderer.out.push_str(" hidden");
+                }
+                renderer.out.push_str(">\n");
+                renderer.styled(column.style.as_ref(), |renderer| {
+                    renderer.elements(&column.items)
+                });
+                renderer.out.push_str("</div>");
+                renderer.close_link(link);
+                renderer.out.push('\n');
+            }
+        });
+        self.out.push_str("</div>");
+        self.close_link(link);
+    }
+
+    fn image(&mut self, image: &Image, size: Option<&ImageSize>) {
+        let Some(url) = safe_url(&image.url, true) else {
+            return;
+        };
+        let mut style = "display:block;".to_string();
+        if let Some(width) = image.width.as_deref().and_then(pixels) {
+            let _ = write!(style, "width:{width};");
+        } else {
+            match image
+                .size
+                .as_ref()
+                .or(size)
+                .and_then(|size| self.config.image_size(size))
+            {
+                Some(width) => {
+                    let _ = write!(style, "width:{width}px;");
+                }
+                None if matches!(image.size, Some(ImageSize::Stretch)) => {
+                    style.push_str("width:100%;");
+                }
+                None => {}
+            }
+        }
+        style.push_str("max-width:100%;");
+        match image.horizontal_alignment {
+            Some(HorizontalAlignment::Center) => style.push_str("margin:0 auto;"),
+            Some(HorizontalAlignment::Right) => style.push_str("margin-left:auto;"),
+            _ => {}
+        }
+        if let Some(background) = &image.background_color {
+            let _ = write!(
+                style,
+                "background-color:{};",
+                escape(&css_color(background))
+            );
+        }
+
+        let link = self.open_link(image.select_action.as_deref());
+        let _ = write!(
+            self.out,
+            "<img src=\"{}\" alt=\"{}\" style=\"{style}\">",
+            escape(url),
+            escape(image.alt_text.as_deref().unwrap_or_default())
+        );
+        self.close_link(link);
+    }
+
+    fn image_set(&mut self, image_set: &ImageSet) {
+        let gap = self.config.spacing(&Spacing::Default);
+        let _ = write!(
+            self.out,
+            "<div style=\"display:flex;flex-wrap:wrap;gap:{gap}px;\">"
+        );
+        let size = image_set.image_size.as_ref().unwrap_or(&ImageSize::Medium);
+        for image in &image_set.images {
+            self.image(image, Some(size));
+        }
+        self.out.push_str("</div>");
+    }
+
+    fn fact_set(&mut self, fact_set: &FactSet) {
+        let config = &self.config.fact_set;
+        let column_style = |text: &FactSetTextConfig| {
+            let mut style = self.text_style(
+                Some(&text.font_type),
+                Some(&text.size),
+                Some(&text.weight),
+                Some(&text.color),
+                text.is_subtle,
+            );
+            style.push_str("text-align:left;vertical-align:top;");
+            if !text.wrap {
+                style.push_str("white-space:nowrap;");
+            }
+            if let Some(max_width) = text.max_width {
+                let _ = write!(style, "max-width:{max_width}px;");
+            }
+            style
+        };
+        let title = format!(
+            "{}padding:0 {}px 0 0;",
+            column_style(&config.title),
+            config.spacing
+        );
+        let value = format!("{}padding:0;", column_style(&config.value));
+        self.out
+            .push_str("<table style=\"border-collapse:collapse;\">\n");
+        for fact in &fact_set.facts {
+            let _ = writeln!(
+                self.out,
+                "<tr><th scope=\"row\" style=\"{title}\">{}</th><td style=\"{value}\">{}</td></tr>",
+                markdown(&fact.title),
+                markdown(&fact.value)
+            );
+        }
+        self.out.push_str("</table>");
+    }
+
+    fn table(&mut self, table: &Table) {
+        let grid = if table.show_grid_lines != Some(false) {
+            let style = table
+                .grid_style
+                .as_ref()
+                .unwrap_or(&ContainerStyle::Default);
+            let color = if matches!(style, ContainerStyle::Default) {
+                css_color(&self.config.separator.line_color)
+            } else {
+                css_color(&self.config.container_style(style).background_color)
+            };
+            format!("border:1px solid {color};")
+        } else {
+            String::new()
+        };
+        let padding = self.config.spacing(&Spacing::Default);
+        self.out
+            .push_str("<table style=\"border-collapse:collapse;width:100%;\">\n");
+
+        if let Some(columns) = &table.columns {
+            let total: u32 = columns
+                .iter()
+                .map(|column| match column.width.as_ref().map(|w| w.sizing()) {
+                    Some(ColumnSizing::Weight(weight)) => weight,
+                    Some(ColumnSizing::Pixels(_)) => 0,
+                    _ => 1,
+                })
+                .sum();
+            self.out.push_str("<colgroup>");
+            for column in columns {
+                let width = match column.width.as_ref().map(|w| w.sizing()) {
+                    Some(ColumnSizing::Pixels(px)) => format!("{px}px"),
+                    Some(ColumnSizing::Weight(weight)) => {
+                        format!("{}%", weight * 100 / total.max(1))
+                    }
+                    _ => format!("{}%", 100 / total.max(1)),
+                };
+                let _ = write!(self.out, "<col style=\"width:{width};\">");
+            }
+            self.out.push_str("</colgroup>\n");
+        }
+
+        let headers = table.first_row_as_headers != Some(false);
+        for (i, row) in table.rows.iter().flatten().enumerate() {
+            let row_style = row.style.as_ref();
+            self.out.push_str("<tr>");
+            for cell in &row.cells {
+                let tag = if headers && i == 0 { "th" } else { "td" };
+                let mut style =
+                    format!("{grid}padding:{padding}px;vertical-align:top;text-align:left;");
+                if let Some(cell_style) = cell.style.as_ref().or(row_style) {
+                    style.push_str(&self.background(cell_style));
+                }
+                let _ = writeln!(self.out, "<{tag} style=\"{style}\">");
+                self.styled(cell.style.as_ref().or(row_style), |renderer| {
+                    renderer.elements(&cell.items)
+                });
+                let _ = write!(self.out, "</{tag}>");
+            }
+            self.out.push_str("</tr>\n");
+        }
+        self.out.push_str("</table>");
+    }
+
+    fn media(&mut self, media: &Media) {
+        let audio = media.sources.iter().all(|source| {
+            source
+                .mime_type
+                .as_deref()
+                .is_some_and(|mime| mime.starts_with("audio/"))
+        });
+        let tag = if audio && !media.sources.is_empty() {
+            "audio"
+        } else {
+            "video"
+        };
+        let _ = write!(self.out, "<{tag} controls style=\"width:100%;\"");
+        if let Some(poster) = media.poster.as_deref().and_then(|url| safe_url(url, true)) {
+            let _ = write!(self.out, " poster=\"{}\"", escape(poster));
+        }
+        if let Some(alt_text) = &media.alt_text {
+            let _ = write!(self.out, " aria-label=\"{}\"", escape(alt_text));
+        }
+        self.out.push('>');
+        for source in &media.sources {
+            let Some(url) = safe_url(&source.url, false) else {
+                continue;
+            };
+            let _ = write!(self.out, "<source src=\"{}\"", escape(url));
+            if let Some(mime_type) = &source.mime_type {
+                let _ = write!(self.out, " type=\"{}\"", escape(mime_type));
+            }
+            self.out.push('>');
+        }
+        let _ = write!(self.out, "</{tag}>");
+    }
+
+    fn input_text(&mut self, input: &InputText) {
+        self.label(&input.id, input.label.as_deref(), input.is_required);
+        let mut attributes =
+            self.input_attributes(&input.id, input.is_required, input.placeholder.as_deref());
+        if let Some(max_length) = input.max_length {
+            let _ = write!(attributes, " maxlength=\"{max_length}\"");
+        }
+        if let Some(regex) = &input.regex {
+            let _ = write!(attributes, " pattern=\"{}\"", escape(regex));
+        }
+        let value = escape(input.value.as_deref().unwrap_or_default());
+        if input.is_multiline == Some(true) {
+            let _ = write!(
+                self.out,
+                "<textarea{attributes} rows=\"3\">{value}</textarea>"
+            );
+        } else {
+            let kind = match input.style {
+                Some(TextInputStyle::Tel) => "tel",
+                Some(TextInputStyle::Url) => "url",
+                Some(TextInputStyle::Email) => "email",
+                Some(TextInputStyle::Password) => "password",
+                Some(TextInputStyle::Text) | None => "text",
+            };
+            let _ = write!(
+                self.out,
+                "<input type=\"{kind}\"{attributes} value=\"{value}\">"
+            );
+        }
+    }
+
+    fn input_number(&mut self, input: &InputNumber) {
+        self.label(&input.id, input.label.as_deref(), input.is_required);
+        let mut attributes =
+            self.input_attributes(&input.id, input.is_required, input.placeholder.as_deref());
+        for (name, value) in [
+            ("min", input.min),
+            ("max", input.max),
+            ("value", input.value),
+        ] {
+            if let Some(value) = value {
+                let _ = write!(attributes, " {name}=\"{value}\"");
+            }
+        }
+        let _ = write!(self.out, "<input type=\"number\"{attributes}>");
+    }
+
+    fn input_date(&mut self, input: &InputDate) {
+        self.label(&input.id, input.label.as_deref(), input.is_required);
+        self.input_with_range(
+            "date",
+            &input.id,
+            input.is_required,
+            input.placeholder.as_deref(),
+            [&input.min, &input.max, &input.value],
+        );
+    }
+
+    fn input_time(&mut self, input: &InputTime) {
+        self.label(&input.id, input.label.as_deref(), input.is_required);
+        self.input_with_range(
+            "time",
+            &input.id,
+            input.is_required,
+            input.placeholder.as_deref(),
+            [&input.min, &input.max, &input.value],
+        );
+    }
+
+    fn input_with_range(
+        &mut self,
+        kind: &str,
+        id: &str,
+        required: Option<bool>,
+        placeholder: Option<&str>,
+        [min, max, value]: [&Option<String>; 3],
+    ) {
+        let mut attributes = self.input_attributes(id, required, placeholder);
+        for (name, value) in [("min", min), ("max", max), ("value", value)] {
+            if let Some(value) = value {
+                let _ = write!(attributes, " {name}=\"{}\"", escape(value));
+            }
+        }
+        let _ = write!(self.out, "<input type=\"{kind}\"{attributes}>");
+    }
+
+    fn input_toggle(&mut self, input: &InputToggle) {
+        self.label(&input.id, input.label.as_deref(), input.is_required);
+        let value_on = input.value_on.as_deref().unwrap_or("true");
+        let checked = input.value.as_deref() == Some(value_on);
+        let _ = write!(
+            self.out,
+            "<label style=\"{}\"><input type=\"checkbox\" id=\"{id}\" name=\"{id}\" value=\"{}\"{}{}> {}</label>",
+            self.text_style(None, None, None, None, false),
+            escape(value_on),
+            if checked { " checked" } else { "" },
+            if input.is_required == Some(true) {
+                " required"
+            } else {
+                ""
+            },
+            escape(&input.title),
+            id = escape(&input.id),
+        );
+    }
+
+    fn input_choice_set(&mut self, input: &InputChoiceSet) {
+        self.label(&input.id, input.label.as_deref(), input.is_required);
+        let multi = input.is_multi_select == Some(true);
+        let selected: Vec<&str> = input
+            .value
+            .as_deref()
+            .map(|value| value.split(',').collect())
+            .unwrap_or_default();
+        let choices = input.choices.as_deref().unwrap_or_default();
+        let id = escape(&input.id);
+
+        match input.style {
+            Some(ChoiceInputStyle::Expanded) => {
+                let kind = if multi { "checkbox" } else { "radio" };
+                let text = self.text_style(None, None, None, None, false);
+                let _ = write!(
+                    self.out,
+                    "<div role=\"{}\" id=\"{id}\">",
+                    if multi { "group" } else { "radiogroup" }
+                );
+                for choice in choices {
+                    let checked = selected.contains(&choice.value.as_str());
+                    let _ = write!(
+                        self.out,
+                        "<label style=\"display:block;{text}\"><input type=\"{kind}\" name=\"{id}\" value=\"{}\"{}> {}</label>",
+                        escape(&choice.value),
+                        if checked { " checked" } else { "" },
+                        escape(&choice.title)
+                    );
+                }
+                self.out.push_str("</div>");
+            }
+            Some(ChoiceInputStyle::Filtered) if !multi => {
+                let attributes = self.input_attributes(
+                    &input.id,
+                    input.is_required,
+                    input.placeholder.as_deref(),
+                );
+                let _ = write!(
+                    self.out,
+                    "<input type=\"text\"{attributes} list=\"{id}-choices\" value=\"{}\"><datalist id=\"{id}-choices\">",
+                    escape(input.value.as_deref().unwrap_or_default())
+                );
+                for choice in choices {
+                    let _ = write!(
+                        self.out,
+                        "<option value=\"{}\">{}</option>",
+                        escape(&choice.value),
+                        escape(&choice.title)
+                    );
+                }
+                self.out.push_str("</datalist>");
+            }
+            _ => {
+                let attributes = self.input_attributes(&input.id, input.is_required, None);
+                let _ = write!(
+                    self.out,
+                    "<select{attributes}{}>",
+                    if multi { " multiple" } else { "" }
+                );
+                if let Some(placeholder) = &input.placeholder
+                    && !multi
+                {
+                    let _ = write!(
+                        self.out,
+                        "<option value=\"\" disabled{}>{}</option>",
+                        if selected.is_empty() { " selected" } else { "" },
+                        escape(placeholder)
+                    );
+                }
+                for choice in choices {
+                    let checked = selected.contains(&choice.value.as_str());
+                    let _ = write!(
+                        self.out,
+                        "<option value=\"{}\"{}>{}</option>",
+                        escape(&choice.value),
+                        if checked { " selected" } else { "" },
+                        escape(&choice.title)
+                    );
+                }
+                self.out.push_str("</select>");
+            }
+        }
+    }
+
+    fn label(&mut self, id: &str, label: Option<&str>, required: Option<bool>) {
+        let Some(label) = label else {
+            return;
+        };
+        let required = required == Some(true);
+        let config = self.config.input_label(required);
+        let style = self.text_style(
+            None,
+            Some(&config.size),
+            Some(&config.weight),
+            Some(&config.color),
+            config.is_subtle,
+        );
+        let _ = write!(
+            self.out,
+            "<label for=\"{}\" style=\"{style}display:block;margin-bottom:{}px;\">{}",
+            escape(id),
+            self.config.spacing(&self.config.inputs.label.input_spacing),
+            escape(label)
+        );
+        if !config.suffix.is_empty() {
+            // The suffix marks required inputs, so it stands out like the official renderers.
+            let color = if required {
+                Color::Attention
+            } else {
+                config.color.clone()
+            };
+            let _ = write!(
+                self.out,
+                "<span style=\"color:{};\">{}</span>",
+                css_color(self.config.foreground_color(&self.style, &color, false)),
+                escape(&config.suffix)
+            );
+        }
+        self.out.push_str("</label>");
+    }
+
+    fn input_attributes(
+        &self,
+        id: &str,
+        required: Option<bool>,
+        placeholder: Option<&str>,
+    ) -> String {
+        let id = escape(id);
+        let mut attributes = format!(
+            " id=\"{id}\" name=\"{id}\" style=\"display:block;width:100%;box-sizing:border-box;padding:6px 8px;font:inherit;border:1px solid {};border-radius:2px;\"",
+            css_color(&self.config.separator.line_color)
+        );
+        if let Some(placeholder) = placeholder {
+            let _ = write!(attributes, " placeholder=\"{}\"", escape(placeholder));
+        }
+        if required == Some(true) {
+            attributes.push_str(" required");
+        }
+        attributes
+    }
+
+    fn actions(&mut self, actions: &[Action], spaced: bool) {
+        if !self.config.supports_interactivity || actions.is_empty() {
+            return;
+        }
+        let config = &self.config.actions;
+        let direction = match config.actions_orientation {
+            ActionsOrientation::Horizontal => "row",
+            ActionsOrientation::Vertical => "column",
+        };
+        let margin = if spaced {
+            format!("margin-top:{}px;", self.config.spacing(&Spacing::Default))
+        } else {
+            String::new()
+        };
+        let _ = writeln!(
+            self.out,
+            "<div class=\"ac-actions\" style=\"{margin}display:flex;flex-direction:{direction};flex-wrap:wrap;gap:{}px;\">",
+            config.button_spacing
+        );
+        for action in actions.iter().take(config.max_actions) {
+            self.action(action);
+            self.out.push('\n');
+        }
+        self.out.push_str("</div>\n");
+    }
+
+    fn action(&mut self, action: &Action) {
+        let title = escape(action.title().unwrap_or_default());
+        let style = self.button_style(action.style());
+        let mut attributes = String::new();
+        if let Some(id) = action.id() {
+            let _ = write!(attributes, " id=\"{}\"", escape(id));
+        }
+        let tooltip = match action {
+            Action::OpenUrl(a) => a.tooltip.as_deref(),
+            Action::Submit(a) => a.tooltip.as_deref(),
+            Action::ShowCard(a) => a.tooltip.as_deref(),
+            Action::ToggleVisibility(a) => a.tooltip.as_deref(),
+            Action::Execute(a) => a.tooltip.as_deref(),
+        };
+        if let Some(tooltip) = tooltip {
+            let _ = write!(attributes, " title=\"{}\"", escape(tooltip));
+        }
+
+        match action {
+            Action::OpenUrl(open_url) => {
+                let href = safe_url(&open_url.url, false).unwrap_or("#");
+                let _ = write!(
+                    self.out,
+                    "<a{attributes} href=\"{}\" role=\"button\" style=\"{style}\">{title}</a>",
+                    escape(href)
+                );
+            }
+            Action::Submit(submit) => {
+                if let Some(data) = &submit.data {
+                    let _ = write!(
+                        attributes,
+                        " name=\"data\" value=\"{}\"",
+                        escape(&data.to_string())
+                    );
+                }
+                let _ = write!(
+                    self.out,
+                    "<button type=\"submit\"{attributes} style=\"{style}\">{title}</button>"
+                );
+            }
+            Action::Execute(execute) => {
+                if let Some(verb) = &execute.verb {
+                    let _ = write!(attributes, " name=\"verb\" value=\"{}\"", escape(verb));
+                }
+                let _ = write!(
+                    self.out,
+                    "<button type=\"submit\"{attributes} style=\"{style}\">{title}</button>"
+                );
+            }
+            Action::ToggleVisibility(toggle) => {
+                let _ = write!(
+                    self.out,
+                    "<button type=\"button\"{attributes} data-target-elements=\"{}\" style=\"{style}\">{title}</button>",
+                    escape(&toggle.target_elements.join(" "))
+                );
+            }
+            Action::ShowCard(show_card) => {
+                let _ = writeln!(
+                    self.out,
+                    "<details{attributes} style=\"flex:1 1 100%;\"><summary style=\"{style}list-style:none;\">{title}</summary>"
+                );
+                let outer = std::mem::replace(&mut self.style, ContainerStyle::Default);
+                self.card(&show_card.card, true);
+                self.style = outer;
+                self.out.push_str("</details>");
+            }
+        }
+    }
+
+    fn button_style(&self, style: Option<&ActionStyle>) -> String {
+        let accent = css_color(self.config.foreground_color(
+            &ContainerStyle::Default,
+            &Color::Accent,
+            false,
+        ));
+        let (background, color, border) = match style {
+            Some(ActionStyle::Positive) => (accent.clone(), "#FFFFFF".to_string(), accent),
+            Some(ActionStyle::Destructive) => {
+                let attention = css_color(self.config.foreground_color(
+                    &ContainerStyle::Default,
+                    &Color::Attention,
+                    false,
+                ));
+                ("transparent".to_string(), attention.clone(), attention)
+            }
+            Some(ActionStyle::Default) | None => {
+                ("transparent".to_string(), accent.clone(), accent)
+            }
+        };
+        let flex = match self.config.actions.actions_orientation {
+            ActionsOrientation::Horizontal => "flex:1 1 0;",
+            ActionsOrientation::Vertical => "",
+        };
+        format!(
+            "{flex}display:block;box-sizing:border-box;padding:6px 12px;border:1px solid {border};border-radius:4px;background:{background};color:{color};font:inherit;font-weight:{};text-align:center;text-decoration:none;cursor:pointer;",
+            self.config.font_weight(None, &TextWeight::Bolder)
+        )
+    }
+
+    /// Returns the inline style shared by every piece of text.
+    fn text_style(
+        &self,
+        font_type: Option<&FontType>,
+        size: Option<&TextSize>,
+        weight: Option<&TextWeight>,
+        color: Option<&Color>,
+        subtle: bool,
+    ) -> String {
+        let font = self.config.font_type(font_type);
+        let mut style = format!(
+            "margin:0;font-size:{}px;font-weight:{};color:{};",
+            self.config
+                .font_size(font_type, size.unwrap_or(&TextSize::Default)),
+            self.config
+                .font_weight(font_type, weight.unwrap_or(&TextWeight::Default)),
+            css_color(self.config.foreground_color(
+                &self.style,
+                color.unwrap_or(&Color::Default),
+                subtle
+            ))
+        );
+        if matches!(font_type, Some(FontType::Monospace)) {
+            let _ = write!(style, "font-family:{};", font.font_family);
+        }
+        style
+    }
+
+    /// Returns the style of a container: its background and padding when styled differently
+    /// from its parent, its vertical alignment and its minimum height.
+    fn box_style(
+        &self,
+        style: Option<&ContainerStyle>,
+        alignment: Option<&VerticalContentAlignment>,
+        min_height: Option<&str>,
+    ) -> String {
+        let mut css = String::new();
+        if let Some(style) = style
+            && *style != self.style
+        {
+            let _ = write!(
+                css,
+                "padding:{}px;{}",
+                self.config.spacing(&Spacing::Padding),
+                self.background(style)
+            );
+        }
+        if let Some(alignment) = alignment {
+            let justify = match alignment {
+                VerticalContentAlignment::Top => "flex-start",
+                VerticalContentAlignment::Center => "center",
+                VerticalContentAlignment::Bottom => "flex-end",
+            };
+            let _ = write!(
+                css,
+                "display:flex;flex-direction:column;justify-content:{justify};"
+            );
+        }
+        if let Some(min_height) = min_height.and_then(pixels) {
+            let _ = write!(css, "min-height:{min_height};");
+        }
+        css
+    }
+
+    fn background(&self, style: &ContainerStyle) -> String {
+        format!(
+            "background-color:{};",
+            css_color(&self.config.container_style(style).background_color)
+        )
+    }
+
+    /// Renders children with `style` as the current container style, if set.
+    fn styled(&mut self, style: Option<&ContainerStyle>, f: impl FnOnce(&mut Self)) {
+        match style {
+            Some(style) => {
+                let outer = std::mem::replace(&mut self.style, style.clone());
+                f(self);
+                self.style = outer;
+            }
+            None => f(self),
+        }
+    }
+
+    /// Wraps the following content in a link if `action` opens a URL.
+    fn open_link(&mut self, action: Option<&Action>) -> bool {
+        let Some(Action::OpenUrl(open_url)) = action else {
+            return false;
+        };
+        let Some(url) = safe_url(&open_url.url, false) else {
+            return false;
+        };
+        let _ = write!(
+            self.out,
+            "<a href=\"{}\" style=\"display:block;color:inherit;text-decoration:none;\">",
+            escape(url)
+        );
+        true
+    }
+
+    fn close_link(&mut self, open: bool) {
+        if open {
+            self.out.push_str("</a>");
+        }
+    }
+}
+
+fn is_interactive(element: &CardElement) -> bool {
+    element.is_input() || matches!(element, CardElement::ActionSet(_))
+}
+
+fn alignment(alignment: Option<&HorizontalAlignment>) -> &'static str {
+    match alignment {
+        Some(HorizontalAlignment::Center) => "text-align:center;",
+        Some(HorizontalAlignment::Right) => "text-align:right;",
+        Some(HorizontalAlignment::Left) | None => "",
+    }
+}
+
+/// Returns the URL if it is safe to put in a link or image: `http`, `https`, `mailto` and
+/// `tel` URLs, and `data:image` URLs for images.
+fn safe_url(url: &str, image: bool) -> Option<&str> {
+    let lower = url.trim_start().to_ascii_lowercase();
+    let safe = ["http://", "https://", "mailto:", "tel:"]
+        .iter()
+        .any(|scheme| lower.starts_with(scheme))
+        || (image && lower.starts_with("data:image/"));
+    safe.then_some(url)
+}
+
+/// Returns the length if it is in pixels, e.g. `50px`, the only unit cards use. Anything
+/// else is dropped rather than written into a `style` attribute, where it could add
+/// declarations of its own.
+fn pixels(length: &str) -> Option<&str> {
+    let digits = length.strip_suffix("px")?;
+    (!digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())).then_some(length)
+}
+
+/// Escapes text for use in HTML content and attribute values.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Converts the Markdown subset supported in text blocks and facts: bold, italic, links and
+/// line breaks. Teams `<at>` mentions are shown in bold.
+fn markdown(text: &str) -> String {
+    fn write(spans: &[Span], html: &mut String) {
+        for span in spans {
+            match span {
+                Span::Text(text) => html.push_str(&escape(text).replace('\n', "<br>")),
+                Span::Bold(spans) => {
+                    html.push_str("<strong>");
+                    write(spans, html);
+                    html.push_str("</strong>");
+                }
+                Span::Italic(spans) => {
+                    html.push_str("<em>");
+                    write(spans, html);
+                    html.push_str("</em>");
+                }
+                Span::Link { text, url } => match safe_url(url, false) {
+                    Some(url) => {
+                        let _ = write!(html, "<a href=\"{}\">", escape(url));
+                        write(text, html);
+                        html.push_str("</a>");
+                    }
+                    None => write(text, html),
+                },
+                Span::Mention(name) => {
+                    let _ = write!(html, "<strong>@{}</strong>", escape(name));
+                }
+            }
+        }
+    }
+
+    let mut html = String::new();
+    write(&markdown::parse(text), &mut html);
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::ActionSet;
+    use crate::card::ColumnWidth;
+    use expect_test::expect;
+
+    #[test]
+    fn test_render_card() {
+        let card = AdaptiveCard::builder()
+            .text_block("Deploy **finished**", |t| {
+                t.size(TextSize::Large)
+                    .weight(TextWeight::Bolder)
+                    .wrap(true)
+            })
+            .column_set(|c| {
+                c.column(|c| {
+                    c.width(ColumnWidth::auto())
+                        .image("https://example.com/ok.png", |i| {
+                            i.size(ImageSize::Small).alt_text("OK")
+                        })
+                })
+                .column(|c| c.text_block("Status", |t| t.color(Color::Good).is_subtle(true)))
+            })
+            .container(|c| {
+                c.style(ContainerStyle::Emphasis)
+                    .separator(true)
+                    .fact_set(|f| f.fact("Env", "production"))
+            })
+            .input_choice_set("rating", |i| {
+                i.label("Rating")
+                    .required()
+                    .choice("Good", "1")
+                    .choice("Bad", "0")
+            })
+            .open_url("https://ci.example.com/runs/42", |a| a.title("Logs"))
+            .submit(|a| {
+                a.title("Send")
+                    .data(serde_json::json!({"id": 1}))
+                    .style(ActionStyle::Positive)
+            })
+            .build();
+
+        let html = render(&card, &HostConfig::default());
+        let expected = expect![[r#"
+            <div class="ac-card" style="font-family:&#39;Segoe UI&#39;, Tahoma, Geneva, Verdana, sans-serif;padding:15px;border:1px solid #EEEEEE;border-radius:4px;background-color:#FFFFFF;">
+            <form method="post">
+            <div><p style="margin:0;font-size:21px;font-weight:600;color:#333333;">Deploy <strong>finished</strong></p></div>
+            <div style="margin-top:8px;"><div style="display:flex;">
+            <div style="flex:0 0 auto;min-width:0;">
+            <div><img src="https://example.com/ok.png" alt="OK" style="display:block;width:40px;max-width:100%;"></div>
+            </div>
+            <div style="flex:1 1 0;min-width:0;margin-left:8px;">
+            <div><p style="margin:0;font-size:14px;font-weight:400;color:rgba(2, 117, 2, 0.87);white-space:nowrap;overflow:hidden;text-overflow:ellipsis;">Status</p></div>
+            </div>
+            </div></div>
+            <div style="margin-top:8px;padding-top:8px;border-top:1px solid #EEEEEE;"><div style="padding:15px;background-color:rgba(0, 0, 0, 0.03);">
+            <div><table style="border-collapse:collapse;">
+            <tr><th scope="row" style="margin:0;font-size:14px;font-weight:600;color:#333333;text-align:left;vertical-align:top;max-width:150px;padding:0 10px 0 0;">Env</th><td style="margin:0;font-size:14px;font-weight:400;color:#333333;text-align:left;vertical-align:top;padding:0;">production</td></tr>
+            </table></div>
+            </div></div>
+            <div id="rating" style="margin-top:8px;"><label for="rating" style="margin:0;font-size:14px;font-weight:400;color:#333333;display:block;margin-bottom:3px;">Rating<span style="color:#CC3300;"> *</span></label><select id="rating" name="rating" style="display:block;width:100%;box-sizing:border-box;padding:6px 8px;font:inherit;border:1px solid #EEEEEE;border-radius:2px;" required><option value="1">Good</option><option value="0">Bad</option></select></div>
+            <div class="ac-actions" style="margin-top:8px;display:flex;flex-direction:row;flex-wrap:wrap;gap:10px;">
+            <a href="https://ci.example.com/runs/42" role="button" style="flex:1 1 0;display:block;box-sizing:border-box;padding:6px 12px;border:1px solid #2E89FC;border-radius:4px;background:transparent;color:#2E89FC;font:inherit;font-weight:600;text-align:center;text-decoration:none;cursor:pointer;">Logs</a>
+            <button type="submit" name="data" value="{&quot;id&quot;:1}" style="flex:1 1 0;display:block;box-sizing:border-box;padding:6px 12px;border:1px solid #2E89FC;border-radius:4px;background:#2E89FC;color:#FFFFFF;font:inherit;font-weight:600;text-align:center;text-decoration:none;cursor:pointer;">Send</button>
+            </div>
+            </form>
+            </div>
+        "#]];
+        expected.assert_eq(&html);
+    }
+
+    #[test]
+    fn test_render_show_card_and_hidden_elements() {
+        let card = AdaptiveCard::builder()
+            .text_block("Secret", |t| t.id("secret").is_visible(false))
+            .element(ActionSet { actions: vec![] })
+            .toggle_visibility(|a| a.title("Reveal").target("secret"))
+            .show_card(AdaptiveCard::builder().text("More").build(), |a| {
+                a.title("More")
+            })
+            .build();
+
+        let html = render(&card, &HostConfig::default());
+
+        assert!(html.contains("<div id=\"secret\" hidden>"));
+        assert!(html.contains("data-target-elements=\"secret\""));
+        assert!(html.contains("<details style=\"flex:1 1 100%;\"><summary"));
+        assert!(!html.contains("<form"));
+    }
+
+    #[test]
+    fn test_escapes_text_and_unsafe_urls() {
+        let card = AdaptiveCard::builder()
+            .text(
+                "<script>alert(1)</script> [click](javascript:alert(1)) [ok](https://example.com)",
+            )
+            .image("javascript:alert(1)", |i| i)
+            .open_url("javascript:alert(1)", |a| a.title("\"Go\""))
+            .build();
+
+        let html = render(&card, &HostConfig::default());
+
+        assert!(html.contains(
+            "&lt;script&gt;alert(1)&lt;/script&gt; click <a href=\"https://example.com\">ok</a>"
+        ));
+        assert!(!html.contains("<img"));
+        assert!(html.contains("href=\"#\""));
+        assert!(html.contains("&quot;Go&quot;"));
+    }
+
+    #[test]
+    fn test_only_pixel_lengths_are_styled() {
+        let card = AdaptiveCard::builder()
+            .min_height("1px;background:url(https://evil.example)")
+            .image("https://example.com/a.png", |i| {
+                i.width("10px;position:fixed;top:0")
+            })
+            .container(|c| c.min_height("100px").text("Tall"))
+            .column_set(|c| c.column(|c| c.min_height("50%").text("Half")))
+            .image("https://example.com/b.png", |i| i.width("64px"))
+            .build();
+
+        let html = render(&card, &HostConfig::default());
+
+        assert!(!html.contains("evil.example"));
+        assert!(!html.contains("position:fixed"));
+        assert!(!html.contains("50%"));
+        assert!(html.contains("min-height:100px;"));
+        assert!(html.contains("width:64px;"));
+        assert_eq!(pixels("12px"), Some("12px"));
+        assert_eq!(pixels("px"), None);
+        assert_eq!(pixels("1.5px"), None);
+        assert_eq!(pixels("１px"), None);
+    }
+
+    #[test]
+    fn test_markdown() {
+        assert_eq!(
+            markdown("**bold** and _italic_ or *also*\nsnake_case_name"),
+            "<strong>bold</strong> and <em>italic</em> or <em>also</em><br>snake_case_name"
+        );
+        assert_eq!(markdown("Hi <at>Ada</at>"), "Hi <strong>@Ada</strong>");
+    }
+}
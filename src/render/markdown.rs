@@ -0,0 +1,156 @@
+//! The Markdown subset allowed in text blocks and facts, shared by the renderers and
+//! exporters.
+//!
+//! Hosts support bold (`**text**`), italic (`_text_` or `*text*`), links (`[text](url)`)
+//! and Teams `<at>name</at>` mentions. [`parse`] splits text into [`Span`]s, which each
+//! output format writes in its own syntax.
+use std::sync::LazyLock;
+
+use regex::{Captures, Regex};
+
+/// A run of text and its formatting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Span<'a> {
+    /// Unformatted text, which may contain line breaks.
+    Text(&'a str),
+    /// `**text**`.
+    Bold(Vec<Span<'a>>),
+    /// `_text_` or `*text*`.
+    Italic(Vec<Span<'a>>),
+    /// `[text](url)`.
+    Link { text: Vec<Span<'a>>, url: &'a str },
+    /// A Teams mention of the named user.
+    Mention(&'a str),
+}
+
+/// Splits `text` into spans.
+pub(crate) fn parse(text: &str) -> Vec<Span<'_>> {
+    // Link URLs may contain one level of balanced parentheses, e.g. Wikipedia links.
+    static TOKEN: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(concat!(
+            r"\[([^\]]+)\]\(((?:[^()\s]|\([^()\s]*\))+)\)",
+            r"|<at>(.*?)</at>",
+            r"|\*\*(.+?)\*\*",
+            r"|_(\S(?:.*?\S)?)_",
+            r"|\*(\S(?:.*?\S)?)\*",
+        ))
+        .unwrap()
+    });
+
+    let mut spans = Vec::new();
+    let mut start = 0;
+    let mut at = 0;
+    while let Some(captures) = TOKEN.captures_at(text, at) {
+        let token = captures.get(0).expect("group 0 always matches");
+        let Some(span) = span(text, &captures) else {
+            // Skip an italic marker inside a word, such as the `_` of `snake_case`.
+            at = token.start()
+                + text[token.start()..]
+                    .chars()
+                    .next()
+                    .map_or(1, char::len_utf8);
+            continue;
+        };
+        if token.start() > start {
+            spans.push(Span::Text(&text[start..token.start()]));
+        }
+        spans.push(span);
+        start = token.end();
+        at = token.end();
+    }
+    if start < text.len() {
+        spans.push(Span::Text(&text[start..]));
+    }
+    spans
+}
+
+fn span<'a>(text: &'a str, captures: &Captures<'a>) -> Option<Span<'a>> {
+    if let (Some(label), Some(url)) = (captures.get(1), captures.get(2)) {
+        return Some(Span::Link {
+            text: parse(label.as_str()),
+            url: url.as_str(),
+        });
+    }
+    if let Some(name) = captures.get(3) {
+        return Some(Span::Mention(name.as_str()));
+    }
+    if let Some(bold) = captures.get(4) {
+        return Some(Span::Bold(parse(bold.as_str())));
+    }
+    let italic = captures.get(5).or_else(|| captures.get(6))?;
+    // Italic markers only count at word boundaries.
+    let token = captures.get(0).expect("group 0 always matches");
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let before = text[..token.start()].chars().next_back();
+    let after = text[token.end()..].chars().next();
+    if before.is_some_and(is_word) || after.is_some_and(is_word) {
+        return None;
+    }
+    Some(Span::Italic(parse(italic.as_str())))
+}
+
+/// Returns the text without formatting. Links are written as `text (url)` and mentions as
+/// `@name`.
+pub(crate) fn plain(text: &str) -> String {
+    fn write(spans: &[Span], out: &mut String) {
+        for span in spans {
+            match span {
+                Span::Text(text) => out.push_str(text),
+                Span::Bold(spans) | Span::Italic(spans) => write(spans, out),
+                Span::Link { text, url } => {
+                    write(text, out);
+                    out.push_str(" (");
+                    out.push_str(url);
+                    out.push(')');
+                }
+                Span::Mention(name) => {
+                    out.push('@');
+                    out.push_str(name);
+                }
+            }
+        }
+    }
+
+    let mut out = String::new();
+    write(&parse(text), &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("**bold _both_** and *it* see [docs](https://x.io/a_(b)) <at>Ada</at>"),
+            vec![
+                Span::Bold(vec![
+                    Span::Text("bold "),
+                    Span::Italic(vec![Span::Text("both")])
+                ]),
+                Span::Text(" and "),
+                Span::Italic(vec![Span::Text("it")]),
+                Span::Text(" see "),
+                Span::Link {
+                    text: vec![Span::Text("docs")],
+                    url: "https://x.io/a_(b)",
+                },
+                Span::Text(" "),
+                Span::Mention("Ada"),
+            ]
+        );
+        assert_eq!(
+            parse("snake_case_name and é_x_"),
+            vec![Span::Text("snake_case_name and é_x_")]
+        );
+    }
+
+    #[test]
+    fn test_plain() {
+        assert_eq!(
+            plain("**Build** _failed_, see [logs](https://ci.example.com) <at>Ada</at>"),
+            "Build failed, see logs (https://ci.example.com) @Ada"
+        );
+    }
+}
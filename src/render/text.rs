@@ -0,0 +1,567 @@
+//! Renders cards as plain text or Markdown.
+//!
+//! For channels that cannot show cards, such as SMS, logs, terminals and
+//! [`AdaptiveCard::fallback_text`], the renderer keeps the content and drops the layout:
+//!
+//! - headings (`TextBlockStyle::Heading` or large text) are underlined or become `#` headings,
+//! - fact sets and tables become aligned lines or Markdown tables,
+//! - choice sets become bullet lists,
+//! - `Action.OpenUrl` becomes a link and `Action.ShowCard` shows its card inline.
+//!
+//! Submit, execute and toggle actions cannot work without a card host and are left out, as
+//! are hidden elements.
+//!
+//! ```
+//! use adaptive_card_rs::card::AdaptiveCard;
+//! use adaptive_card_rs::render::text::{self, Format};
+//!
+//! let card = AdaptiveCard::builder()
+//!     .text_block("Deploy finished", |t| t.style(adaptive_card_rs::common::TextBlockStyle::Heading))
+//!     .fact_set(|f| f.fact("Env", "production"))
+//!     .open_url("https://ci.example.com/runs/42", |a| a.title("Logs"))
+//!     .build();
+//!
+//! assert_eq!(
+//!     text::render(&card, Format::Markdown),
+//!     "## Deploy finished\n\n|  |  |\n| --- | --- |\n| **Env** | production |\n\n[Logs](https://ci.example.com/runs/42)"
+//! );
+//! ```
+use crate::actions::Action;
+use crate::card::{AdaptiveCard, CardElement, FactSet, Inline, Table, TextSize, TextWeight};
+use crate::common::TextBlockStyle;
+use crate::inputs::InputChoiceSet;
+use crate::render::markdown::{self, Span};
+
+/// The output format of [`render`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// Text with Markdown syntax removed; links are written as `title (url)`.
+    #[default]
+    Plain,
+    /// GitHub-flavored Markdown.
+    Markdown,
+}
+
+/// Renders `card` as text in the given format.
+pub fn render(card: &AdaptiveCard, format: Format) -> String {
+    let mut renderer = Renderer {
+        format,
+        blocks: Vec::new(),
+    };
+    renderer.card(card);
+    renderer.blocks.join("\n\n")
+}
+
+impl AdaptiveCard {
+    /// Sets [`fallback_text`](AdaptiveCard::fallback_text) to the card rendered as plain text,
+    /// unless it is already set.
+    pub fn with_generated_fallback_text(mut self) -> Self {
+        if self.fallback_text.is_none() {
+            self.fallback_text = Some(render(&self, Format::Plain));
+        }
+        self
+    }
+}
+
+/// Collects the card's content as blocks, which are separated by blank lines.
+struct Renderer {
+    format: Format,
+    blocks: Vec<String>,
+}
+
+impl Renderer {
+    fn card(&mut self, card: &AdaptiveCard) {
+        self.elements(&card.body);
+        self.actions(card.actions.iter().flatten());
+    }
+
+    fn elements(&mut self, elements: &[CardElement]) {
+        for element in elements.iter().filter(|element| element.is_visible()) {
+            self.element(element);
+        }
+    }
+
+    fn element(&mut self, element: &CardElement) {
+        match element {
+            CardElement::TextBlock(text_block) => {
+                let heading = matches!(text_block.style, Some(TextBlockStyle::Heading));
+                let level = match text_block.size {
+                    Some(TextSize::ExtraLarge) => Some(1),
+                    Some(TextSize::Large) => Some(2),
+                    _ if heading => Some(2),
+                    _ => None,
+                };
+                let text = self.text(&text_block.text);
+                let block = match (level, self.format) {
+                    (Some(level), Format::Markdown) => format!("{} {text}", "#".repeat(level)),
+                    (Some(level), Format::Plain) => {
+                        let underline = if level == 1 { "=" } else { "-" };
+                        format!("{text}\n{}", underline.repeat(text.chars().count()))
+                    }
+                    (None, _) => text,
+                };
+                self.push(block);
+            }
+            CardElement::RichTextBlock(rich_text) => {
+                let text: String = rich_text
+                    .inlines
+                    .iter()
+                    .map(|inline| match inline {
+                        Inline::Text(text) => text.clone(),
+                        Inline::TextRun(run) if self.format == Format::Markdown => {
+                            let mut text = run.text.clone();
+                            if run.strikethrough == Some(true) {
+                                text = format!("~~{text}~~");
+                            }
+                            if run.italic == Some(true) {
+                                text = format!("_{text}_");
+                            }
+                            if matches!(run.weight, Some(TextWeight::Bolder)) {
+                                text = format!("**{text}**");
+                            }
+                            text
+                        }
+                        Inline::TextRun(run) => run.text.clone(),
+                    })
+                    .collect();
+                self.push(text);
+            }
+            CardElement::Container(container) => {
+                self.elements(&container.items);
+            }
+            CardElement::ColumnSet(column_set) => {
+                for column in &column_set.columns {
+                    if column.is_visible != Some(false) {
+                        self.elements(&column.items);
+                    }
+                }
+            }
+            CardElement::Image(image) => {
+                let alt_text = image.alt_text.as_deref().unwrap_or_default();
+                let block = match self.format {
+                    Format::Markdown => format!("![{alt_text}]({})", image.url),
+                    Format::Plain if !alt_text.is_empty() => format!("[Image: {alt_text}]"),
+                    Format::Plain => String::new(),
+                };
+                self.push(block);
+            }
+            CardElement::ImageSet(image_set) => {
+                for image in &image_set.images {
+                    self.element(&CardElement::Image(image.clone()));
+                }
+            }
+            CardElement::FactSet(fact_set) => self.fact_set(fact_set),
+            CardElement::Table(table) => self.table(table),
+            CardElement::Media(media) => {
+                if let Some(source) = media.sources.first() {
+                    let title = media.alt_text.as_deref().unwrap_or("Media");
+                    self.push(self.link(title, &source.url));
+                }
+            }
+            CardElement::ActionSet(action_set) => self.actions(&action_set.actions),
+            CardElement::InputText(input) => {
+                self.input(input.label.as_deref(), &input.id, input.value.clone())
+            }
+            CardElement::InputNumber(input) => self.input(
+                input.label.as_deref(),
+                &input.id,
+                input.value.map(|value| value.to_string()),
+            ),
+            CardElement::InputDate(input) => {
+                self.input(input.label.as_deref(), &input.id, input.value.clone())
+            }
+            CardElement::InputTime(input) => {
+                self.input(input.label.as_deref(), &input.id, input.value.clone())
+            }
+            CardElement::InputToggle(input) => {
+                let checked = input.value.is_some()
+                    && input.value.as_deref() == Some(input.value_on.as_deref().unwrap_or("true"));
+                let bullet = match self.format {
+                    Format::Markdown => "- ",
+                    Format::Plain => "",
+                };
+                let mark = if checked { "x" } else { " " };
+                let toggle = format!("{bullet}[{mark}] {}", input.title);
+                let block = match &input.label {
+                    Some(label) => format!("{label}\n{toggle}"),
+                    None => toggle,
+                };
+                self.push(block);
+            }
+            CardElement::InputChoiceSet(input) => self.choice_set(input),
+        }
+    }
+
+    fn fact_set(&mut self, fact_set: &FactSet) {
+        let block = match self.format {
+            Format::Markdown => {
+                let mut block = "|  |  |\n| --- | --- |".to_string();
+                for fact in &fact_set.facts {
+                    block.push_str(&format!(
+                        "\n| **{}** | {} |",
+                        cell(&fact.title),
+                        cell(&fact.value)
+                    ));
+                }
+                block
+            }
+            Format::Plain => {
+                let facts: Vec<_> = fact_set
+                    .facts
+                    .iter()
+                    .map(|fact| {
+                        (
+                            format!("{}:", self.text(&fact.title)),
+                            self.text(&fact.value),
+                        )
+                    })
+                    .collect();
+                let width = facts
+                    .iter()
+                    .map(|(title, _)| title.chars().count())
+                    .max()
+                    .unwrap_or_default();
+                facts
+                    .iter()
+                    .map(|(title, value)| format!("{title:width$} {value}"))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        };
+        self.push(block);
+    }
+
+    fn table(&mut self, table: &Table) {
+        let mut rows: Vec<Vec<String>> = table
+            .rows
+            .iter()
+            .flatten()
+            .map(|row| {
+                row.cells
+                    .iter()
+                    .map(|table_cell| {
+                        let mut renderer = Renderer {
+                            format: self.format,
+                            blocks: Vec::new(),
+                        };
+                        renderer.elements(&table_cell.items);
+                        renderer.blocks.join(" ")
+                    })
+                    .collect()
+            })
+            .collect();
+        let columns = rows.iter().map(Vec::len).max().unwrap_or_default();
+        if columns == 0 {
+            return;
+        }
+        for row in &mut rows {
+            row.resize(columns, String::new());
+        }
+
+        let block = match self.format {
+            Format::Markdown => {
+                let header = if table.first_row_as_headers != Some(false) {
+                    rows.remove(0)
+                } else {
+                    vec![String::new(); columns]
+                };
+                let line = |row: &[String]| {
+                    let cells: Vec<_> = row.iter().map(|text| cell(text)).collect();
+                    format!("| {} |", cells.join(" | "))
+                };
+                let mut lines = vec![line(&header), format!("|{}", " --- |".repeat(columns))];
+                lines.extend(rows.iter().map(|row| line(row)));
+                lines.join("\n")
+            }
+            Format::Plain => {
+                let widths: Vec<usize> = (0..columns)
+                    .map(|i| {
+                        rows.iter()
+                            .map(|row| row[i].chars().count())
+                            .max()
+                            .unwrap_or_default()
+                    })
+                    .collect();
+                rows.iter()
+                    .map(|row| {
+                        let cells: Vec<_> = row
+                            .iter()
+                            .zip(&widths)
+                            .map(|(text, width)| format!("{text:width$}"))
+                            .collect();
+                        cells.join(" | ").trim_end().to_string()
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        };
+        self.push(block);
+    }
+
+    fn choice_set(&mut self, input: &InputChoiceSet) {
+        let selected: Vec<&str> = input
+            .value
+            .as_deref()
+            .map(|value| value.split(',').collect())
+            .unwrap_or_default();
+        let mut lines: Vec<String> = input
+            .label
+            .iter()
+            .chain(input.placeholder.iter().filter(|_| input.label.is_none()))
+            .cloned()
+            .collect();
+        for choice in input.choices.iter().flatten() {
+            let mark = if selected.contains(&choice.value.as_str()) {
+                " (selected)"
+            } else {
+                ""
+            };
+            lines.push(format!("- {}{mark}", choice.title));
+        }
+        self.push(lines.join("\n"));
+    }
+
+    fn input(&mut self, label: Option<&str>, id: &str, value: Option<String>) {
+        let label = label.unwrap_or(id);
+        let block = match value {
+            Some(value) => format!("{label}: {value}"),
+            None => format!("{label}:"),
+        };
+        self.push(block);
+    }
+
+    fn actions<'a>(&mut self, actions: impl IntoIterator<Item = &'a Action>) {
+        let mut links = Vec::new();
+        for action in actions {
+            match action {
+                Action::OpenUrl(open_url) => {
+                    let title = open_url.title.as_deref().unwrap_or(&open_url.url);
+                    links.push(self.link(title, &open_url.url));
+                }
+                Action::ShowCard(show_card) => {
+                    self.push_links(&mut links);
+                    if let Some(title) = &show_card.title {
+                        let title = match self.format {
+                            Format::Markdown => format!("**{title}**"),
+                            Format::Plain => title.clone(),
+                        };
+                        self.push(title);
+                    }
+                    self.card(&show_card.card);
+                }
+                Action::Submit(_) | Action::Execute(_) | Action::ToggleVisibility(_) => {}
+            }
+        }
+        self.push_links(&mut links);
+    }
+
+    fn push_links(&mut self, links: &mut Vec<String>) {
+        if !links.is_empty() {
+            self.push(links.join("\n"));
+            links.clear();
+        }
+    }
+
+    fn link(&self, title: &str, url: &str) -> String {
+        match self.format {
+            Format::Markdown => format!("[{title}]({url})"),
+            Format::Plain if title == url => url.to_string(),
+            Format::Plain => format!("{title} ({url})"),
+        }
+    }
+
+    /// Returns text from a card, which may contain Markdown and Teams `<at>` mentions, in the
+    /// output format.
+    fn text(&self, text: &str) -> String {
+        fn write(spans: &[Span], out: &mut String) {
+            for span in spans {
+                match span {
+                    Span::Text(text) => out.push_str(text),
+                    Span::Bold(spans) => {
+                        out.push_str("**");
+                        write(spans, out);
+                        out.push_str("**");
+                    }
+                    Span::Italic(spans) => {
+                        out.push('_');
+                        write(spans, out);
+                        out.push('_');
+                    }
+                    Span::Link { text, url } => {
+                        out.push('[');
+                        write(text, out);
+                        out.push_str("](");
+                        out.push_str(url);
+                        out.push(')');
+                    }
+                    Span::Mention(name) => {
+                        out.push('@');
+                        out.push_str(name);
+                    }
+                }
+            }
+        }
+
+        match self.format {
+            Format::Markdown => {
+                let mut out = String::new();
+                write(&markdown::parse(text), &mut out);
+                out
+            }
+            Format::Plain => markdown::plain(text),
+        }
+    }
+
+    fn push(&mut self, block: String) {
+        if !block.trim().is_empty() {
+            self.blocks.push(block);
+        }
+    }
+}
+
+/// Escapes text for a Markdown table cell, which must fit on one line.
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::{TableCell, TableRow};
+    use expect_test::expect;
+
+    fn card() -> AdaptiveCard {
+        AdaptiveCard::builder()
+            .text_block("Deploy finished", |t| t.size(TextSize::ExtraLarge))
+            .text_block("Commit **abc123** by [Ada](https://example.com/ada)", |t| t)
+            .fact_set(|f| f.fact("Environment", "production").fact("Duration", "3m"))
+            .element(Table {
+                rows: Some(vec![
+                    TableRow {
+                        cells: vec![
+                            TableCell {
+                                items: vec![CardElement::TextBlock(
+                                    crate::card::TextBlock::builder("Step").build(),
+                                )],
+                                ..Default::default()
+                            },
+                            TableCell {
+                                items: vec![CardElement::TextBlock(
+                                    crate::card::TextBlock::builder("Status").build(),
+                                )],
+                                ..Default::default()
+                            },
+                        ],
+                        ..Default::default()
+                    },
+                    TableRow {
+                        cells: vec![
+                            TableCell {
+                                items: vec![CardElement::TextBlock(
+                                    crate::card::TextBlock::builder("build").build(),
+                                )],
+                                ..Default::default()
+                            },
+                            TableCell {
+                                items: vec![CardElement::TextBlock(
+                                    crate::card::TextBlock::builder("ok").build(),
+                                )],
+                                ..Default::default()
+                            },
+                        ],
+                        ..Default::default()
+                    },
+                ]),
+                ..Default::default()
+            })
+            .text_block("Hidden", |t| t.is_visible(false))
+            .input_choice_set("rating", |i| {
+                i.label("How did it go?")
+                    .choice("Good", "good")
+                    .choice("Bad", "bad")
+                    .value("good")
+            })
+            .input_toggle("notify", "Notify me", |i| i.value("true"))
+            .open_url("https://ci.example.com/runs/42", |a| a.title("Logs"))
+            .submit(|a| a.title("Send"))
+            .show_card(
+                AdaptiveCard::builder().text("Rollback steps").build(),
+                |a| a.title("Rollback"),
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_render_plain() {
+        let expected = expect![[r#"
+            Deploy finished
+            ===============
+
+            Commit abc123 by Ada (https://example.com/ada)
+
+            Environment: production
+            Duration:    3m
+
+            Step  | Status
+            build | ok
+
+            How did it go?
+            - Good (selected)
+            - Bad
+
+            [x] Notify me
+
+            Logs (https://ci.example.com/runs/42)
+
+            Rollback
+
+            Rollback steps"#]];
+        expected.assert_eq(&render(&card(), Format::Plain));
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let expected = expect![[r#"
+            # Deploy finished
+
+            Commit **abc123** by [Ada](https://example.com/ada)
+
+            |  |  |
+            | --- | --- |
+            | **Environment** | production |
+            | **Duration** | 3m |
+
+            | Step | Status |
+            | --- | --- |
+            | build | ok |
+
+            How did it go?
+            - Good (selected)
+            - Bad
+
+            - [x] Notify me
+
+            [Logs](https://ci.example.com/runs/42)
+
+            **Rollback**
+
+            Rollback steps"#]];
+        expected.assert_eq(&render(&card(), Format::Markdown));
+    }
+
+    #[test]
+    fn test_with_generated_fallback_text() {
+        let card = AdaptiveCard::builder()
+            .text("Hello **world**")
+            .build()
+            .with_generated_fallback_text();
+        assert_eq!(card.fallback_text.as_deref(), Some("Hello world"));
+
+        let card = AdaptiveCard::builder()
+            .fallback_text("Custom")
+            .text("Hello")
+            .build()
+            .with_generated_fallback_text();
+        assert_eq!(card.fallback_text.as_deref(), Some("Custom"));
+    }
+}
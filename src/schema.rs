@@ -0,0 +1,283 @@
+//! Offline validation of cards against a subset of the Adaptive Card JSON schema.
+//!
+//! The schema is bundled with the crate, so validation never touches the network. It is a
+//! subset of the 1.6 schema published at <http://adaptivecards.io/schemas/adaptive-card.json>:
+//! it keeps the definitions for the elements, inputs and actions this crate models and
+//! leaves out the rest, such as `Data.Query` and `Metadata`.
+//!
+//! Definitions and properties are annotated with the version that introduced them, and a
+//! card is validated against a view of the schema for its declared [`Version`]:
+//!
+//! - elements and actions introduced later are not accepted where an element or action is
+//!   expected,
+//! - properties introduced later are rejected wherever they appear.
+//!
+//! Values of enums, such as a `TextBlock` `style` of `heading`, are not gated.
+//!
+//! ```
+//! use adaptive_card_rs::card::{AdaptiveCard, CardElement, TextBlock, Version};
+//!
+//! let card = AdaptiveCard {
+//!     version: Version::V1_2,
+//!     body: vec![CardElement::TextBlock(TextBlock {
+//!         text: "Hello".to_string(),
+//!         ..Default::default()
+//!     })],
+//!     ..Default::default()
+//! };
+//! assert!(card.validate_schema().is_ok());
+//! ```
+use std::fmt;
+use std::sync::OnceLock;
+
+use jsonschema::{Draft, Validator};
+use serde_json::Value;
+
+use crate::card::{AdaptiveCard, Version};
+
+const SCHEMA: &str = include_str!("../schemas/adaptive-card.json");
+
+const VERSIONS: [Version; 7] = [
+    Version::V1_0,
+    Version::V1_1,
+    Version::V1_2,
+    Version::V1_3,
+    Version::V1_4,
+    Version::V1_5,
+    Version::V1_6,
+];
+
+/// A single schema violation found while validating a card.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    /// JSON pointer to the offending value in the serialized card, e.g. `/body/0/text`.
+    pub instance_path: String,
+    /// JSON pointer to the schema keyword that rejected the value.
+    pub schema_path: String,
+    /// Human readable description of the violation.
+    pub message: String,
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.instance_path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.instance_path, self.message)
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+impl AdaptiveCard {
+    /// Validates this card against the bundled schema for the card's declared version.
+    ///
+    /// Returns every violation found, each carrying a JSON pointer into the serialized card.
+    pub fn validate_schema(&self) -> Result<(), Vec<SchemaError>> {
+        let value = serde_json::to_value(self).map_err(|e| {
+            vec![SchemaError {
+                instance_path: String::new(),
+                schema_path: String::new(),
+                message: e.to_string(),
+            }]
+        })?;
+        validate_value(&value, self.version)
+    }
+}
+
+/// Validates an arbitrary JSON value against the bundled schema for the given version.
+pub fn validate_value(value: &Value, version: Version) -> Result<(), Vec<SchemaError>> {
+    let errors: Vec<SchemaError> = validator(version)
+        .iter_errors(value)
+        .map(|error| SchemaError {
+            instance_path: error.instance_path.to_string(),
+            schema_path: error.schema_path.to_string(),
+            message: error.to_string(),
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn validator(version: Version) -> &'static Validator {
+    static VALIDATORS: [OnceLock<Validator>; 7] = [const { OnceLock::new() }; 7];
+
+    let index = VERSIONS
+        .iter()
+        .position(|v| *v == version)
+        .expect("every version has a validator");
+    VALIDATORS[index].get_or_init(|| {
+        jsonschema::options()
+            .with_draft(Draft::Draft6)
+            .build(&schema_for(version))
+            .expect("bundled schema is valid")
+    })
+}
+
+/// Returns the bundled schema restricted to the elements, actions and properties available
+/// in `version`.
+fn schema_for(version: Version) -> Value {
+    let mut schema: Value = serde_json::from_str(SCHEMA).expect("bundled schema is valid JSON");
+    let definitions = schema["definitions"].clone();
+
+    let is_later = |annotated: &Value| {
+        annotated
+            .get("version")
+            .and_then(|v| serde_json::from_value::<Version>(v.clone()).ok())
+            .is_some_and(|introduced| introduced > version)
+    };
+    let introduced_after = |reference: &Value| {
+        let name = reference["$ref"]
+            .as_str()
+            .and_then(|r| r.strip_prefix("#/definitions/"))
+            .unwrap_or_default();
+        is_later(&definitions[name])
+    };
+
+    // The schema allows unknown properties, so a later property is replaced with the `false`
+    // schema, which no value matches.
+    if let Some(Value::Object(definitions)) = schema.get_mut("definitions") {
+        for definition in definitions.values_mut() {
+            if let Some(Value::Object(properties)) = definition.get_mut("properties") {
+                for property in properties.values_mut() {
+                    if is_later(property) {
+                        *property = Value::Bool(false);
+                    }
+                }
+            }
+        }
+    }
+
+    for name in [
+        "ImplementationsOf.Element",
+        "ImplementationsOf.Action",
+        "ImplementationsOf.ISelectAction",
+    ] {
+        if let Some(Value::Array(any_of)) = schema["definitions"][name].get_mut("anyOf") {
+            any_of.retain(|reference| !introduced_after(reference));
+        }
+    }
+
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{Action, ExecuteAction};
+    use crate::card::{CardElement, Column, ColumnSet, ColumnWidth, Image, Table, TextBlock};
+    use crate::inputs::{InputChoice, InputChoiceSet};
+
+    #[test]
+    fn test_valid_card_passes() {
+        let card = AdaptiveCard {
+            version: Version::V1_5,
+            body: vec![
+                CardElement::TextBlock(TextBlock {
+                    text: "Hello".to_string(),
+                    ..Default::default()
+                }),
+                CardElement::ColumnSet(ColumnSet {
+                    columns: vec![Column {
+                        width: Some(ColumnWidth::weight(1)),
+                        items: vec![CardElement::Image(Image {
+                            url: "https://example.com/image.png".to_string(),
+                            ..Default::default()
+                        })],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }),
+                CardElement::Table(Table::default()),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(card.validate_schema(), Ok(()));
+    }
+
+    #[test]
+    fn test_errors_carry_json_pointers() {
+        let card = AdaptiveCard {
+            version: Version::V1_3,
+            body: vec![CardElement::InputChoiceSet(InputChoiceSet {
+                id: "color".to_string(),
+                choices: Some(vec![InputChoice {
+                    title: "Red".to_string(),
+                    value: "red".to_string(),
+                }]),
+                is_multi_select: None,
+                style: None,
+                value: None,
+                placeholder: None,
+                wrap: None,
+                label: None,
+                is_required: None,
+                error_message: None,
+                separator: None,
+                spacing: None,
+                height: None,
+                is_visible: None,
+            })],
+            ..Default::default()
+        };
+        let mut value = serde_json::to_value(&card).unwrap();
+        value["body"][0]["choices"][0]
+            .as_object_mut()
+            .unwrap()
+            .remove("title");
+
+        let errors = validate_value(&value, card.version).unwrap_err();
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.instance_path.starts_with("/body/0")),
+            "{errors:?}"
+        );
+    }
+
+    #[test]
+    fn test_elements_are_gated_by_declared_version() {
+        let mut card = AdaptiveCard {
+            version: Version::V1_2,
+            actions: Some(vec![Action::Execute(ExecuteAction {
+                verb: Some("approve".to_string()),
+                ..Default::default()
+            })]),
+            ..Default::default()
+        };
+        let errors = card.validate_schema().unwrap_err();
+        assert!(errors.iter().any(|e| e.instance_path == "/actions/0"));
+
+        card.version = Version::V1_4;
+        assert_eq!(card.validate_schema(), Ok(()));
+    }
+
+    #[test]
+    fn test_properties_are_gated_by_declared_version() {
+        let mut card = AdaptiveCard {
+            version: Version::V1_4,
+            body: vec![CardElement::TextBlock(TextBlock {
+                text: "Title".to_string(),
+                style: Some(crate::common::TextBlockStyle::Heading),
+                ..Default::default()
+            })],
+            ..Default::default()
+        };
+        // The element no longer matches any element definition, so the error is reported
+        // for the element rather than the property.
+        let errors = card.validate_schema().unwrap_err();
+        assert!(
+            errors.iter().any(|e| e.instance_path == "/body/0"),
+            "{errors:?}"
+        );
+
+        card.version = Version::V1_5;
+        assert_eq!(card.validate_schema(), Ok(()));
+    }
+}
@@ -0,0 +1,1103 @@
+//! Typed parsing of the data sent back by `Action.Submit` and `Action.Execute`.
+//!
+//! Hosts submit a flat JSON object mapping input ids to strings, merged with the action's
+//! `data`. Parsing it against the card that was sent turns each input's string into a value
+//! of the right type, which can then be deserialized into your own struct.
+//!
+//! [`AdaptiveCard::validate_submission`] re-checks the constraints declared by the inputs,
+//! and [`AdaptiveCard::with_submission`] builds the card to send back when they fail.
+//!
+//! ```
+//! use adaptive_card_rs::card::AdaptiveCard;
+//! use serde::Deserialize;
+//! use serde_json::json;
+//!
+//! #[derive(Deserialize)]
+//! struct Order {
+//!     quantity: f64,
+//!     gift_wrap: bool,
+//!     toppings: Vec<String>,
+//! }
+//!
+//! let card = AdaptiveCard::builder()
+//!     .input_number("quantity", |i| i)
+//!     .input_toggle("gift_wrap", "Gift wrap", |i| i.value_on("yes").value_off("no"))
+//!     .input_choice_set("toppings", |i| i.is_multi_select(true))
+//!     .build();
+//!
+//! let order: Order = card
+//!     .parse_submission(&json!({
+//!         "quantity": "2",
+//!         "gift_wrap": "yes",
+//!         "toppings": "cheese,olives",
+//!     }))
+//!     .unwrap();
+//! assert_eq!(order.quantity, 2.0);
+//! assert!(order.gift_wrap);
+//! assert_eq!(order.toppings, ["cheese", "olives"]);
+//! ```
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+use crate::card::{AdaptiveCard, CardElement, Spacing, TextBlock, TextSize};
+use crate::common::Color;
+use crate::inputs::InputChoiceSet;
+use crate::visit::{Visitor, VisitorMut};
+
+/// An error parsing a submission.
+#[derive(Debug)]
+pub enum SubmissionError {
+    /// The payload is not a JSON object.
+    NotAnObject,
+    /// An input's value could not be parsed as the type the input produces.
+    InvalidValue {
+        /// The input id.
+        id: String,
+        /// The submitted value.
+        value: String,
+        /// What the value should have been, e.g. `"a number"`.
+        expected: &'static str,
+    },
+    /// The parsed values did not match the target type.
+    Deserialize(serde_json::Error),
+}
+
+impl fmt::Display for SubmissionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmissionError::NotAnObject => write!(f, "submission is not a JSON object"),
+            SubmissionError::InvalidValue {
+                id,
+                value,
+                expected,
+            } => write!(f, "input \"{id}\": expected {expected}, got \"{value}\""),
+            SubmissionError::Deserialize(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for SubmissionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SubmissionError::Deserialize(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// The typed value of a single input.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputValue {
+    /// The value of an `Input.Text` or a single-select `Input.ChoiceSet`.
+    Text(String),
+    /// The value of an `Input.Number`.
+    Number(f64),
+    /// The value of an `Input.Toggle`.
+    Bool(bool),
+    /// The values of a multi-select `Input.ChoiceSet`.
+    Choices(Vec<String>),
+    /// The value of an `Input.Date`.
+    Date(Date),
+    /// The value of an `Input.Time`.
+    Time(Time),
+}
+
+impl From<InputValue> for Value {
+    fn from(value: InputValue) -> Self {
+        match value {
+            InputValue::Text(text) => Value::String(text),
+            // Whole numbers become integers so they deserialize into integer fields.
+            InputValue::Number(number)
+                if number.fract() == 0.0 && number.abs() < i64::MAX as f64 =>
+            {
+                (number as i64).into()
+            }
+            InputValue::Number(number) => number.into(),
+            InputValue::Bool(b) => Value::Bool(b),
+            InputValue::Choices(choices) => choices.into(),
+            InputValue::Date(date) => Value::String(date.to_string()),
+            InputValue::Time(time) => Value::String(time.to_string()),
+        }
+    }
+}
+
+/// A submission parsed against the card it came from.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Submission {
+    /// The values of the card's inputs, keyed by input id. Inputs left empty are absent.
+    pub values: BTreeMap<String, InputValue>,
+    /// Everything else in the payload, usually the submitting action's `data`.
+    pub data: Map<String, Value>,
+}
+
+impl Submission {
+    /// Parses `payload` using the inputs declared in `card`.
+    pub fn parse(card: &AdaptiveCard, payload: &Value) -> Result<Self, SubmissionError> {
+        let Value::Object(payload) = payload else {
+            return Err(SubmissionError::NotAnObject);
+        };
+        let inputs: BTreeMap<&str, &CardElement> = inputs(card)
+            .into_iter()
+            .filter_map(|(_, input)| Some((input.id()?, input)))
+            .collect();
+
+        let mut submission = Submission::default();
+        for (key, value) in payload {
+            match inputs.get(key.as_str()) {
+                Some(input) => {
+                    if let Some(value) = parse_value(input, key, value)? {
+                        submission.values.insert(key.clone(), value);
+                    }
+                }
+                None => {
+                    submission.data.insert(key.clone(), value.clone());
+                }
+            }
+        }
+        Ok(submission)
+    }
+
+    /// Returns the value of the input with the given id.
+    pub fn get(&self, id: &str) -> Option<&InputValue> {
+        self.values.get(id)
+    }
+
+    /// Merges the input values and data back into one JSON object. Input values take
+    /// precedence over data with the same key.
+    pub fn to_value(&self) -> Value {
+        let mut object = self.data.clone();
+        for (id, value) in &self.values {
+            object.insert(id.clone(), value.clone().into());
+        }
+        Value::Object(object)
+    }
+
+    /// Deserializes the merged values into `T`.
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T, SubmissionError> {
+        serde_json::from_value(self.to_value()).map_err(SubmissionError::Deserialize)
+    }
+}
+
+impl AdaptiveCard {
+    /// Parses a submission of this card and deserializes it into `T`.
+    pub fn parse_submission<T: DeserializeOwned>(
+        &self,
+        payload: &Value,
+    ) -> Result<T, SubmissionError> {
+        Submission::parse(self, payload)?.deserialize()
+    }
+}
+
+/// Collects every input in a card, including those in show cards, and whether it is in a
+/// show card.
+#[derive(Default)]
+struct Inputs<'a> {
+    /// How many cards the walk is inside, 1 for the card being walked.
+    depth: usize,
+    inputs: Vec<(bool, &'a CardElement)>,
+}
+
+impl<'a> Visitor<'a> for Inputs<'a> {
+    fn visit_card(&mut self, _path: &str, _card: &'a AdaptiveCard) {
+        self.depth += 1;
+    }
+
+    fn leave_card(&mut self, _path: &str, _card: &'a AdaptiveCard) {
+        self.depth -= 1;
+    }
+
+    fn visit_element(&mut self, _path: &str, element: &'a CardElement) {
+        if element.is_input() {
+            self.inputs.push((self.depth > 1, element));
+        }
+    }
+}
+
+fn inputs(card: &AdaptiveCard) -> Vec<(bool, &CardElement)> {
+    let mut inputs = Inputs::default();
+    card.walk(&mut inputs);
+    inputs.inputs
+}
+
+fn parse_value(
+    input: &CardElement,
+    id: &str,
+    value: &Value,
+) -> Result<Option<InputValue>, SubmissionError> {
+    let text = match value {
+        Value::String(text) if text.is_empty() => return Ok(None),
+        Value::String(text) => text,
+        // Some hosts send numbers and booleans as JSON values rather than strings.
+        Value::Number(number) if matches!(input, CardElement::InputNumber(_)) => {
+            return Ok(number.as_f64().map(InputValue::Number));
+        }
+        Value::Bool(b) if matches!(input, CardElement::InputToggle(_)) => {
+            return Ok(Some(InputValue::Bool(*b)));
+        }
+        Value::Null => return Ok(None),
+        other => {
+            return Err(invalid(id, &other.to_string(), "a string"));
+        }
+    };
+
+    let value = match input {
+        CardElement::InputNumber(_) => InputValue::Number(
+            text.trim()
+                .parse()
+                .map_err(|_| invalid(id, text, "a number"))?,
+        ),
+        CardElement::InputToggle(toggle) => {
+            let on = toggle.value_on.as_deref().unwrap_or("true");
+            let off = toggle.value_off.as_deref().unwrap_or("false");
+            if text == on {
+                InputValue::Bool(true)
+            } else if text == off {
+                InputValue::Bool(false)
+            } else {
+                return Err(invalid(id, text, "the toggle's on or off value"));
+            }
+        }
+        CardElement::InputChoiceSet(choice_set) if choice_set.is_multi_select == Some(true) => {
+            InputValue::Choices(
+                text.split(',')
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            )
+        }
+        CardElement::InputDate(_) => {
+            InputValue::Date(text.parse().map_err(|_| invalid(id, text, "a date"))?)
+        }
+        CardElement::InputTime(_) => {
+            InputValue::Time(text.parse().map_err(|_| invalid(id, text, "a time"))?)
+        }
+        _ => InputValue::Text(text.clone()),
+    };
+    Ok(Some(value))
+}
+
+fn invalid(id: &str, value: &str, expected: &'static str) -> SubmissionError {
+    SubmissionError::InvalidValue {
+        id: id.to_string(),
+        value: value.to_string(),
+        expected,
+    }
+}
+
+/// Why a submitted value was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputErrorKind {
+    /// A required input was left empty.
+    Required,
+    /// The value could not be parsed, e.g. `"abc"` for an `Input.Number`.
+    Invalid,
+    /// An `Input.Text` value does not match the input's `regex`.
+    Pattern,
+    /// An `Input.Text` value is longer than the input's `max_length`.
+    TooLong,
+    /// A number, date or time is outside the input's `min` and `max`.
+    OutOfRange,
+    /// An `Input.ChoiceSet` value is not one of the input's choices.
+    NotAChoice,
+    /// The payload is not a JSON object, so no input could be checked. The error's id is
+    /// empty.
+    NotAnObject,
+}
+
+/// A submitted value that breaks a constraint declared by its input, or a payload that is
+/// not a JSON object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputError {
+    /// The input id.
+    pub id: String,
+    /// Which constraint was broken.
+    pub kind: InputErrorKind,
+    /// The input's `error_message`, or a description of the constraint if it has none.
+    pub message: String,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.id, self.message)
+    }
+}
+
+impl std::error::Error for InputError {}
+
+impl AdaptiveCard {
+    /// Re-checks a submission against the constraints declared by the card's inputs:
+    /// `is_required`, `regex`, `max_length`, `min`/`max` and choice membership.
+    ///
+    /// Hosts don't reliably enforce these, so a submission should be validated before it is
+    /// trusted. Inputs inside show cards are only checked when the payload includes them, as
+    /// only actions in the same show card submit them.
+    pub fn validate_submission(&self, payload: &Value) -> Result<(), Vec<InputError>> {
+        let Value::Object(payload) = payload else {
+            return Err(vec![InputError {
+                id: String::new(),
+                kind: InputErrorKind::NotAnObject,
+                message: SubmissionError::NotAnObject.to_string(),
+            }]);
+        };
+
+        let mut errors = Vec::new();
+        for (in_show_card, input) in inputs(self) {
+            let id = input.id().unwrap_or_default();
+            let value = match payload.get(id) {
+                Some(value) => match parse_value(input, id, value) {
+                    Ok(value) => value,
+                    Err(SubmissionError::InvalidValue { expected, .. }) => {
+                        errors.push(input_error(
+                            input,
+                            InputErrorKind::Invalid,
+                            format!("must be {expected}"),
+                        ));
+                        continue;
+                    }
+                    Err(error) => {
+                        errors.push(input_error(
+                            input,
+                            InputErrorKind::Invalid,
+                            error.to_string(),
+                        ));
+                        continue;
+                    }
+                },
+                None if in_show_card => continue,
+                None => None,
+            };
+            if let Some((kind, message)) = check(input, value.as_ref()) {
+                errors.push(input_error(input, kind, message));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Returns a copy of the card with the submitted values filled in and each error shown
+    /// below its input, ready to be sent back so the user can correct their submission.
+    ///
+    /// Error blocks added by an earlier call are replaced, so a card can be sent back as
+    /// many times as it takes.
+    pub fn with_submission(&self, payload: &Value, errors: &[InputError]) -> AdaptiveCard {
+        let empty = Map::new();
+        let payload = payload.as_object().unwrap_or(&empty);
+        let mut card = self.clone();
+        card.walk_mut(&mut Fill { payload, errors });
+        card
+    }
+}
+
+fn check(input: &CardElement, value: Option<&InputValue>) -> Option<(InputErrorKind, String)> {
+    let required = match input {
+        CardElement::InputText(input) => input.is_required,
+        CardElement::InputNumber(input) => input.is_required,
+        CardElement::InputDate(input) => input.is_required,
+        CardElement::InputTime(input) => input.is_required,
+        CardElement::InputToggle(input) => input.is_required,
+        CardElement::InputChoiceSet(input) => input.is_required,
+        _ => None,
+    } == Some(true);
+    let Some(value) = value else {
+        return required.then(|| (InputErrorKind::Required, "a value is required".to_string()));
+    };
+
+    match (input, value) {
+        (CardElement::InputText(input), InputValue::Text(value)) => {
+            if let Some(max) = input.max_length
+                && value.chars().count() > max as usize
+            {
+                return Some((
+                    InputErrorKind::TooLong,
+                    format!("must be at most {max} characters"),
+                ));
+            }
+            // An invalid pattern is a mistake in the card, not the submission.
+            if let Some(pattern) = &input.regex
+                && let Ok(regex) = regex::Regex::new(pattern)
+                && !regex.is_match(value)
+            {
+                return Some((
+                    InputErrorKind::Pattern,
+                    "is not in the expected format".to_string(),
+                ));
+            }
+            None
+        }
+        (CardElement::InputNumber(input), InputValue::Number(value)) => {
+            out_of_range(*value, input.min, input.max)
+        }
+        (CardElement::InputDate(input), InputValue::Date(value)) => out_of_range(
+            *value,
+            input.min.as_deref().and_then(|min| min.parse().ok()),
+            input.max.as_deref().and_then(|max| max.parse().ok()),
+        ),
+        (CardElement::InputTime(input), InputValue::Time(value)) => out_of_range(
+            *value,
+            input.min.as_deref().and_then(|min| min.parse().ok()),
+            input.max.as_deref().and_then(|max| max.parse().ok()),
+        ),
+        // A required toggle must be switched on.
+        (CardElement::InputToggle(_), InputValue::Bool(false)) if required => {
+            Some((InputErrorKind::Required, "must be selected".to_string()))
+        }
+        (CardElement::InputChoiceSet(input), InputValue::Text(value)) => {
+            not_a_choice(input, std::slice::from_ref(value))
+        }
+        (CardElement::InputChoiceSet(input), InputValue::Choices(values)) => {
+            not_a_choice(input, values)
+        }
+        _ => None,
+    }
+}
+
+fn out_of_range<T: PartialOrd + fmt::Display>(
+    value: T,
+    min: Option<T>,
+    max: Option<T>,
+) -> Option<(InputErrorKind, String)> {
+    if let Some(min) = min
+        && value < min
+    {
+        return Some((
+            InputErrorKind::OutOfRange,
+            format!("must be at least {min}"),
+        ));
+    }
+    if let Some(max) = max
+        && value > max
+    {
+        return Some((InputErrorKind::OutOfRange, format!("must be at most {max}")));
+    }
+    None
+}
+
+fn not_a_choice(input: &InputChoiceSet, values: &[String]) -> Option<(InputErrorKind, String)> {
+    // Choice sets without static choices are filled in by the host.
+    let choices = input
+        .choices
+        .as_deref()
+        .filter(|choices| !choices.is_empty())?;
+    let value = values
+        .iter()
+        .find(|value| !choices.iter().any(|choice| &choice.value == *value))?;
+    Some((
+        InputErrorKind::NotAChoice,
+        format!("\"{value}\" is not one of the choices"),
+    ))
+}
+
+fn input_error(input: &CardElement, kind: InputErrorKind, message: String) -> InputError {
+    let error_message = match input {
+        CardElement::InputText(input) => input.error_message.as_ref(),
+        CardElement::InputNumber(input) => input.error_message.as_ref(),
+        CardElement::InputDate(input) => input.error_message.as_ref(),
+        CardElement::InputTime(input) => input.error_message.as_ref(),
+        CardElement::InputToggle(input) => input.error_message.as_ref(),
+        CardElement::InputChoiceSet(input) => input.error_message.as_ref(),
+        _ => None,
+    };
+    InputError {
+        id: input.id().unwrap_or_default().to_string(),
+        kind,
+        message: error_message.cloned().unwrap_or(message),
+    }
+}
+
+/// Fills in submitted values and adds a text block below each input with an error, removing
+/// any added before.
+struct Fill<'a> {
+    payload: &'a Map<String, Value>,
+    errors: &'a [InputError],
+}
+
+impl VisitorMut for Fill<'_> {
+    fn visit_elements(&mut self, _path: &str, elements: &mut Vec<CardElement>) {
+        let error_ids: Vec<String> = elements
+            .iter()
+            .filter(|element| element.is_input())
+            .filter_map(|input| Some(error_id(input.id()?)))
+            .collect();
+        elements.retain(|element| {
+            !matches!(element, CardElement::TextBlock(TextBlock { id: Some(id), .. })
+                if error_ids.contains(id))
+        });
+
+        let mut i = 0;
+        while i < elements.len() {
+            let id = elements[i].is_input().then(|| elements[i].id()).flatten();
+            if let Some(error) = self
+                .errors
+                .iter()
+                .find(|error| Some(error.id.as_str()) == id)
+            {
+                i += 1;
+                elements.insert(
+                    i,
+                    CardElement::TextBlock(TextBlock {
+                        text: error.message.clone(),
+                        size: Some(TextSize::Small),
+                        wrap: Some(true),
+                        color: Some(Color::Attention),
+                        id: Some(error_id(&error.id)),
+                        spacing: Some(Spacing::Small),
+                        ..Default::default()
+                    }),
+                );
+            }
+            i += 1;
+        }
+    }
+
+    fn visit_element(&mut self, _path: &str, element: &mut CardElement) {
+        if element.is_input()
+            && let Some(value) = self.payload.get(element.id().unwrap_or_default())
+        {
+            fill_value(element, value);
+        }
+    }
+}
+
+/// The id of the text block showing an input's error.
+fn error_id(input_id: &str) -> String {
+    format!("{input_id}-error")
+}
+
+fn fill_value(input: &mut CardElement, value: &Value) {
+    let value = match value {
+        Value::Null => None,
+        Value::String(text) if text.is_empty() => None,
+        Value::String(text) => Some(text.clone()),
+        other => Some(other.to_string()),
+    };
+    match input {
+        CardElement::InputText(input) => input.value = value,
+        CardElement::InputNumber(input) => {
+            input.value = value.and_then(|value| value.trim().parse().ok())
+        }
+        CardElement::InputDate(input) => input.value = value,
+        CardElement::InputTime(input) => input.value = value,
+        CardElement::InputToggle(input) => input.value = value,
+        CardElement::InputChoiceSet(input) => input.value = value,
+        _ => {}
+    }
+}
+
+/// An error parsing a [`Date`] or [`Time`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDateTimeError;
+
+impl fmt::Display for ParseDateTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid date or time")
+    }
+}
+
+impl std::error::Error for ParseDateTimeError {}
+
+/// A calendar date, as submitted by `Input.Date` in `YYYY-MM-DD` format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    /// The year.
+    pub year: i32,
+    /// The month, from 1 to 12.
+    pub month: u8,
+    /// The day of the month, from 1.
+    pub day: u8,
+}
+
+impl FromStr for Date {
+    type Err = ParseDateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('-');
+        let (Some(year), Some(month), Some(day), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(ParseDateTimeError);
+        };
+        if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+            return Err(ParseDateTimeError);
+        }
+        let year: i32 = digits(year)?;
+        let month: u8 = digits(month)?;
+        let day: u8 = digits(day)?;
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(ParseDateTimeError);
+        }
+        Ok(Date { year, month, day })
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// A time of day, as submitted by `Input.Time` in `HH:mm` format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+    /// The hour, from 0 to 23.
+    pub hour: u8,
+    /// The minute, from 0 to 59.
+    pub minute: u8,
+    /// The second, from 0 to 59. Hosts usually omit seconds.
+    pub second: u8,
+}
+
+impl FromStr for Time {
+    type Err = ParseDateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(':').collect();
+        if !(2..=3).contains(&parts.len()) || parts.iter().any(|part| part.len() != 2) {
+            return Err(ParseDateTimeError);
+        }
+        let hour: u8 = digits(parts[0])?;
+        let minute: u8 = digits(parts[1])?;
+        let second: u8 = parts.get(2).map_or(Ok(0), |part| digits(part))?;
+        if hour > 23 || minute > 59 || second > 59 {
+            return Err(ParseDateTimeError);
+        }
+        Ok(Time {
+            hour,
+            minute,
+            second,
+        })
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)?;
+        if self.second != 0 {
+            write!(f, ":{:02}", self.second)?;
+        }
+        Ok(())
+    }
+}
+
+macro_rules! string_serde {
+    ($($ty:ty),*) => {
+        $(
+            impl Serialize for $ty {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $ty {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let s = String::deserialize(deserializer)?;
+                    s.parse().map_err(serde::de::Error::custom)
+                }
+            }
+        )*
+    };
+}
+
+string_serde!(Date, Time);
+
+#[cfg(feature = "chrono")]
+impl From<Date> for chrono::NaiveDate {
+    fn from(date: Date) -> Self {
+        chrono::NaiveDate::from_ymd_opt(date.year, date.month.into(), date.day.into())
+            .expect("dates are validated when parsed")
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<Time> for chrono::NaiveTime {
+    fn from(time: Time) -> Self {
+        chrono::NaiveTime::from_hms_opt(time.hour.into(), time.minute.into(), time.second.into())
+            .expect("times are validated when parsed")
+    }
+}
+
+fn digits<T: FromStr>(s: &str) -> Result<T, ParseDateTimeError> {
+    if !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ParseDateTimeError);
+    }
+    s.parse().map_err(|_| ParseDateTimeError)
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expect_test::expect;
+    use serde_json::json;
+
+    fn card() -> AdaptiveCard {
+        AdaptiveCard::builder()
+            .input_text("name", |i| i)
+            .input_number("age", |i| i)
+            .input_toggle("subscribe", "Subscribe", |i| i)
+            .input_choice_set("colour", |i| i.choice("Red", "red").choice("Blue", "blue"))
+            .input_choice_set("tags", |i| i.is_multi_select(true))
+            .input_date("birthday", |i| i)
+            .show_card(
+                AdaptiveCard::builder().input_time("alarm", |i| i).build(),
+                |a| a.title("More"),
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_parse_submission() {
+        let submission = Submission::parse(
+            &card(),
+            &json!({
+                "name": "Ada",
+                "age": "36",
+                "subscribe": "false",
+                "colour": "red",
+                "tags": "a,b",
+                "birthday": "1815-12-10",
+                "alarm": "07:30",
+                "verb": "save",
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(
+            submission.values,
+            BTreeMap::from([
+                ("age".to_string(), InputValue::Number(36.0)),
+                (
+                    "alarm".to_string(),
+                    InputValue::Time(Time {
+                        hour: 7,
+                        minute: 30,
+                        second: 0
+                    })
+                ),
+                (
+                    "birthday".to_string(),
+                    InputValue::Date(Date {
+                        year: 1815,
+                        month: 12,
+                        day: 10
+                    })
+                ),
+                ("colour".to_string(), InputValue::Text("red".to_string())),
+                ("name".to_string(), InputValue::Text("Ada".to_string())),
+                ("subscribe".to_string(), InputValue::Bool(false)),
+                (
+                    "tags".to_string(),
+                    InputValue::Choices(vec!["a".to_string(), "b".to_string()])
+                ),
+            ])
+        );
+        assert_eq!(
+            submission.data,
+            json!({"verb": "save"}).as_object().cloned().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_deserialize_submission() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Profile {
+            name: String,
+            age: Option<u32>,
+            birthday: Date,
+            tags: Vec<String>,
+            verb: String,
+        }
+
+        let profile: Profile = card()
+            .parse_submission(&json!({
+                "name": "Ada",
+                "age": "36",
+                "birthday": "1815-12-10",
+                "tags": "a",
+                "verb": "save",
+            }))
+            .unwrap();
+
+        assert_eq!(
+            profile,
+            Profile {
+                name: "Ada".to_string(),
+                age: Some(36),
+                birthday: "1815-12-10".parse().unwrap(),
+                tags: vec!["a".to_string()],
+                verb: "save".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        let error = |payload: Value| {
+            Submission::parse(&card(), &payload)
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            error(json!({"age": "old"})),
+            "input \"age\": expected a number, got \"old\""
+        );
+        assert_eq!(
+            error(json!({"subscribe": "maybe"})),
+            "input \"subscribe\": expected the toggle's on or off value, got \"maybe\""
+        );
+        assert_eq!(
+            error(json!({"birthday": "1815-02-30"})),
+            "input \"birthday\": expected a date, got \"1815-02-30\""
+        );
+        assert_eq!(
+            Submission::parse(&card(), &json!([]))
+                .unwrap_err()
+                .to_string(),
+            "submission is not a JSON object"
+        );
+    }
+
+    fn form() -> AdaptiveCard {
+        AdaptiveCard::builder()
+            .input_text("name", |i| {
+                i.required().error_message("Please enter your name")
+            })
+            .input_text("code", |i| i.regex("^[A-Z]{3}$").max_length(3u32))
+            .input_number("age", |i| i.min(18.0).max(120.0))
+            .input_date("start", |i| i.min("2024-01-01"))
+            .input_time("alarm", |i| i.max("12:00"))
+            .input_toggle("terms", "I agree", |i| i.required())
+            .input_choice_set("colour", |i| {
+                i.is_multi_select(true)
+                    .choice("Red", "red")
+                    .choice("Blue", "blue")
+            })
+            .container(|c| c.input_number("count", |i| i))
+            .show_card(
+                AdaptiveCard::builder()
+                    .input_text("comment", |i| i.required())
+                    .build(),
+                |a| a.title("Comment"),
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_validate_submission() {
+        let kinds = |payload: Value| {
+            form()
+                .validate_submission(&payload)
+                .unwrap_err()
+                .into_iter()
+                .map(|error| (error.id, error.kind, error.message))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            kinds(json!({
+                "name": "",
+                "code": "abcd",
+                "age": "12",
+                "start": "2023-12-31",
+                "alarm": "13:30",
+                "terms": "false",
+                "colour": "red,green",
+                "count": "many",
+            })),
+            [
+                (
+                    "name".to_string(),
+                    InputErrorKind::Required,
+                    "Please enter your name".to_string()
+                ),
+                (
+                    "code".to_string(),
+                    InputErrorKind::TooLong,
+                    "must be at most 3 characters".to_string()
+                ),
+                (
+                    "age".to_string(),
+                    InputErrorKind::OutOfRange,
+                    "must be at least 18".to_string()
+                ),
+                (
+                    "start".to_string(),
+                    InputErrorKind::OutOfRange,
+                    "must be at least 2024-01-01".to_string()
+                ),
+                (
+                    "alarm".to_string(),
+                    InputErrorKind::OutOfRange,
+                    "must be at most 12:00".to_string()
+                ),
+                (
+                    "terms".to_string(),
+                    InputErrorKind::Required,
+                    "must be selected".to_string()
+                ),
+                (
+                    "colour".to_string(),
+                    InputErrorKind::NotAChoice,
+                    "\"green\" is not one of the choices".to_string()
+                ),
+                (
+                    "count".to_string(),
+                    InputErrorKind::Invalid,
+                    "must be a number".to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            kinds(json!({"name": "Ada", "code": "abc", "terms": "true", "comment": ""})),
+            [
+                (
+                    "code".to_string(),
+                    InputErrorKind::Pattern,
+                    "is not in the expected format".to_string()
+                ),
+                (
+                    "comment".to_string(),
+                    InputErrorKind::Required,
+                    "a value is required".to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            form().validate_submission(&json!({"name": "Ada", "terms": "true"})),
+            Ok(())
+        );
+        assert_eq!(
+            kinds(json!(["Ada"])),
+            [(
+                String::new(),
+                InputErrorKind::NotAnObject,
+                "submission is not a JSON object".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_validate_inputs_after_show_card() {
+        // The show card's inputs are optional here, but the walk must return to the outer
+        // card for the input after it.
+        let card = AdaptiveCard::builder()
+            .action_set(|a| {
+                a.show_card(
+                    AdaptiveCard::builder()
+                        .input_text("comment", |i| i.required())
+                        .build(),
+                    |a| a.title("Comment"),
+                )
+            })
+            .input_text("name", |i| i.required())
+            .build();
+        let errors = card.validate_submission(&json!({})).unwrap_err();
+        assert_eq!(
+            errors
+                .iter()
+                .map(|error| (error.id.as_str(), error.kind))
+                .collect::<Vec<_>>(),
+            [("name", InputErrorKind::Required)]
+        );
+    }
+
+    #[test]
+    fn test_multi_select_values_are_trimmed() {
+        let submission = Submission::parse(&card(), &json!({"tags": " a, b ,"})).unwrap();
+        assert_eq!(
+            submission.get("tags"),
+            Some(&InputValue::Choices(vec!["a".to_string(), "b".to_string()]))
+        );
+    }
+
+    #[test]
+    fn test_with_submission() {
+        let card = AdaptiveCard::builder()
+            .input_text("name", |i| {
+                i.required().error_message("Please enter your name")
+            })
+            .container(|c| c.input_number("age", |i| i.min(18.0)))
+            .build();
+        let payload = json!({"name": "", "age": "12"});
+        let errors = card.validate_submission(&payload).unwrap_err();
+
+        let json = serde_json::to_string_pretty(&card.with_submission(&payload, &errors)).unwrap();
+        let expected = expect![[r#"
+            {
+              "type": "AdaptiveCard",
+              "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+              "version": "1.2",
+              "body": [
+                {
+                  "type": "Input.Text",
+                  "id": "name",
+                  "isRequired": true,
+                  "errorMessage": "Please enter your name"
+                },
+                {
+                  "type": "TextBlock",
+                  "text": "Please enter your name",
+                  "size": "small",
+                  "wrap": true,
+                  "color": "attention",
+                  "id": "name-error",
+                  "spacing": "small"
+                },
+                {
+                  "type": "Container",
+                  "items": [
+                    {
+                      "type": "Input.Number",
+                      "id": "age",
+                      "min": 18.0,
+                      "value": 12.0
+                    },
+                    {
+                      "type": "TextBlock",
+                      "text": "must be at least 18",
+                      "size": "small",
+                      "wrap": true,
+                      "color": "attention",
+                      "id": "age-error",
+                      "spacing": "small"
+                    }
+                  ]
+                }
+              ]
+            }"#]];
+        expected.assert_eq(&json);
+
+        // Sending the card back again replaces the error blocks rather than adding more.
+        let resent = card.with_submission(&payload, &errors);
+        assert_eq!(
+            serde_json::to_string_pretty(&resent.with_submission(&payload, &errors)).unwrap(),
+            json
+        );
+        let fixed = json!({"name": "Ada", "age": "12"});
+        let errors = resent.validate_submission(&fixed).unwrap_err();
+        let ids: Vec<_> = resent
+            .with_submission(&fixed, &errors)
+            .elements()
+            .filter_map(|element| element.id().map(str::to_string))
+            .collect();
+        assert_eq!(ids, ["name", "age", "age-error"]);
+    }
+}
@@ -0,0 +1,432 @@
+//! Microsoft Teams extensions: mentions and Teams-specific submit actions.
+//!
+//! Teams highlights `<at>Name</at>` in text only when the card's `msteams.entities` lists a
+//! matching mention. [`AdaptiveCard::mention_in`] keeps the two in sync:
+//!
+//! ```
+//! use adaptive_card_rs::card::AdaptiveCard;
+//! use adaptive_card_rs::teams::{Mentioned, UserId};
+//!
+//! let mut card = AdaptiveCard::builder()
+//!     .text_block("Review requested from", |t| t.id("reviewers"))
+//!     .build();
+//! let ada = Mentioned::user(UserId::Upn("ada@example.com".into()), "Ada");
+//! assert!(card.mention_in("reviewers", ada));
+//!
+//! let json = serde_json::to_value(&card).unwrap();
+//! assert_eq!(json["body"][0]["text"], "Review requested from <at>Ada</at>");
+//! assert_eq!(json["msteams"]["entities"][0]["mentioned"]["id"], "ada@example.com");
+//! ```
+//!
+//! `Action.Submit` buttons in Teams can behave like chat messages, task modules or sign-in
+//! prompts through the `msteams` property of their data, modelled by [`MsTeamsAction`]:
+//!
+//! ```
+//! use adaptive_card_rs::actions::SubmitAction;
+//! use adaptive_card_rs::teams::MsTeamsAction;
+//!
+//! let action = SubmitAction::builder()
+//!     .title("Open form")
+//!     .data(serde_json::json!({ "form": "feedback" }))
+//!     .msteams(MsTeamsAction::TaskFetch)
+//!     .build();
+//!
+//! assert_eq!(
+//!     action.data.unwrap(),
+//!     serde_json::json!({ "form": "feedback", "msteams": { "type": "task/fetch" } })
+//! );
+//! ```
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::actions::SubmitAction;
+use crate::builder::SubmitActionBuilder;
+use crate::card::{AdaptiveCard, CardElement, MsTeams};
+
+/// An entity in `msteams.entities`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Entity {
+    Mention(Mention),
+}
+
+/// Links the `<at>` text in a card to the user or tag it mentions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Mention {
+    /// The text of the mention, including the `<at>` tags, e.g. `<at>Ada</at>`.
+    pub text: String,
+    /// Who is mentioned.
+    pub mentioned: Mentioned,
+}
+
+impl Mention {
+    /// Creates a mention whose text is the mentioned name.
+    pub fn new(mentioned: Mentioned) -> Self {
+        Self {
+            text: mentioned.text(),
+            mentioned,
+        }
+    }
+}
+
+/// The user or tag a [`Mention`] refers to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "RawMentioned", into = "RawMentioned")]
+pub enum Mentioned {
+    /// A user, who is notified.
+    User { id: UserId, name: String },
+    /// A tag, whose members are notified.
+    Tag { id: String, name: String },
+}
+
+impl Mentioned {
+    /// Mentions a user.
+    pub fn user(id: UserId, name: impl Into<String>) -> Self {
+        Mentioned::User {
+            id,
+            name: name.into(),
+        }
+    }
+
+    /// Mentions a tag by its id.
+    pub fn tag(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Mentioned::Tag {
+            id: id.into(),
+            name: name.into(),
+        }
+    }
+
+    /// Returns the name shown in the card.
+    pub fn name(&self) -> &str {
+        match self {
+            Mentioned::User { name, .. } | Mentioned::Tag { name, .. } => name,
+        }
+    }
+
+    /// Returns the text that refers to this mention, e.g. `<at>Ada</at>`.
+    pub fn text(&self) -> String {
+        format!("<at>{}</at>", self.name())
+    }
+}
+
+/// The ways Teams can identify a mentioned user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum UserId {
+    /// A Teams user id, starting with `29:`.
+    Teams(String),
+    /// A Microsoft Entra ID (Azure AD) object id.
+    AadObjectId(String),
+    /// A user principal name, usually the user's email address.
+    Upn(String),
+}
+
+impl From<String> for UserId {
+    fn from(id: String) -> Self {
+        if id.starts_with("29:") {
+            UserId::Teams(id)
+        } else if id.contains('@') {
+            UserId::Upn(id)
+        } else {
+            UserId::AadObjectId(id)
+        }
+    }
+}
+
+impl From<UserId> for String {
+    fn from(id: UserId) -> Self {
+        match id {
+            UserId::Teams(id) | UserId::AadObjectId(id) | UserId::Upn(id) => id,
+        }
+    }
+}
+
+/// The JSON form of [`Mentioned`], where tags are marked by `"type": "tag"`.
+#[derive(Serialize, Deserialize)]
+struct RawMentioned {
+    id: String,
+    name: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    kind: Option<String>,
+}
+
+impl From<RawMentioned> for Mentioned {
+    fn from(raw: RawMentioned) -> Self {
+        match raw.kind.as_deref() {
+            Some("tag") => Mentioned::Tag {
+                id: raw.id,
+                name: raw.name,
+            },
+            _ => Mentioned::User {
+                id: raw.id.into(),
+                name: raw.name,
+            },
+        }
+    }
+}
+
+impl From<Mentioned> for RawMentioned {
+    fn from(mentioned: Mentioned) -> Self {
+        match mentioned {
+            Mentioned::User { id, name } => RawMentioned {
+                id: id.into(),
+                name,
+                kind: None,
+            },
+            Mentioned::Tag { id, name } => RawMentioned {
+                id,
+                name,
+                kind: Some("tag".to_string()),
+            },
+        }
+    }
+}
+
+impl AdaptiveCard {
+    /// Registers a mention in `msteams.entities` and returns the text to put in the card,
+    /// e.g. `<at>Ada</at>`. Registering the same mention twice adds one entity.
+    pub fn add_mention(&mut self, mentioned: Mentioned) -> String {
+        let mention = Mention::new(mentioned);
+        let text = mention.text.clone();
+        let entities = self
+            .msteams
+            .get_or_insert_with(MsTeams::default)
+            .entities
+            .get_or_insert_with(Vec::new);
+        let entity = Entity::Mention(mention);
+        if !entities.contains(&entity) {
+            entities.push(entity);
+        }
+        text
+    }
+
+    /// Appends a mention to the end of the text block with the given id and registers it.
+    ///
+    /// Returns `false`, leaving the card unchanged, if there is no such text block.
+    pub fn mention_in(&mut self, text_block_id: &str, mentioned: Mentioned) -> bool {
+        if !matches!(
+            self.find_by_id(text_block_id),
+            Some(CardElement::TextBlock(_))
+        ) {
+            return false;
+        }
+        let text = self.add_mention(mentioned);
+        if let Some(CardElement::TextBlock(text_block)) = self.find_by_id_mut(text_block_id) {
+            if !text_block.text.is_empty() && !text_block.text.ends_with(char::is_whitespace) {
+                text_block.text.push(' ');
+            }
+            text_block.text.push_str(&text);
+        }
+        true
+    }
+}
+
+/// The Teams behaviour of an `Action.Submit`, stored in `data.msteams`.
+///
+/// See <https://learn.microsoft.com/en-us/microsoftteams/platform/task-modules-and-cards/cards/cards-actions#adaptive-cards-actions>.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum MsTeamsAction {
+    /// Sends a message to the bot, optionally showing different text in the chat.
+    #[serde(rename = "messageBack", rename_all = "camelCase")]
+    MessageBack {
+        /// The text sent to the bot.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        text: Option<String>,
+        /// The text shown in the chat as the user's message.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        display_text: Option<String>,
+        /// Data sent to the bot, not shown to the user.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        value: Option<Value>,
+    },
+    /// Sends `value` as a chat message from the user.
+    #[serde(rename = "imBack")]
+    ImBack { value: String },
+    /// Sends an invoke activity with `value` to the bot.
+    #[serde(rename = "invoke")]
+    Invoke { value: Value },
+    /// Starts an OAuth flow at the URL in `value`.
+    #[serde(rename = "signin")]
+    Signin { value: String },
+    /// Asks the bot for a task module.
+    #[serde(rename = "task/fetch")]
+    TaskFetch,
+}
+
+impl SubmitAction {
+    /// Returns the Teams behaviour of this action, if it has one.
+    pub fn msteams(&self) -> Option<MsTeamsAction> {
+        let msteams = self.data.as_ref()?.get("msteams")?;
+        serde_json::from_value(msteams.clone()).ok()
+    }
+
+    /// Sets the Teams behaviour of this action, keeping the rest of `data`. Data that is not
+    /// an object is replaced, as Teams requires an object.
+    pub fn set_msteams(&mut self, action: MsTeamsAction) {
+        let msteams = serde_json::to_value(action).expect("MsTeamsAction serializes to JSON");
+        match &mut self.data {
+            Some(Value::Object(data)) => {
+                data.insert("msteams".to_string(), msteams);
+            }
+            data => *data = Some(serde_json::json!({ "msteams": msteams })),
+        }
+    }
+}
+
+impl SubmitActionBuilder {
+    /// Sets the Teams behaviour of the action in `data.msteams`. Call after `data`, which
+    /// replaces the whole of `data`.
+    pub fn msteams(mut self, action: MsTeamsAction) -> Self {
+        self.inner.set_msteams(action);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expect_test::expect;
+
+    #[test]
+    fn test_mentions() {
+        let mut card = AdaptiveCard::builder()
+            .text_block("Hi", |t| t.id("greeting"))
+            .build();
+        let ada = Mentioned::user(UserId::Teams("29:1abc".into()), "Ada");
+
+        assert!(card.mention_in("greeting", ada.clone()));
+        assert!(card.mention_in("greeting", Mentioned::tag("tag-1", "Reviewers")));
+        assert!(card.mention_in("greeting", ada));
+        assert!(!card.mention_in("missing", Mentioned::tag("tag-1", "Reviewers")));
+
+        let expected = expect![[r#"
+            {
+              "type": "AdaptiveCard",
+              "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+              "version": "1.2",
+              "body": [
+                {
+                  "type": "TextBlock",
+                  "text": "Hi <at>Ada</at> <at>Reviewers</at> <at>Ada</at>",
+                  "id": "greeting"
+                }
+              ],
+              "msteams": {
+                "entities": [
+                  {
+                    "type": "mention",
+                    "text": "<at>Ada</at>",
+                    "mentioned": {
+                      "id": "29:1abc",
+                      "name": "Ada"
+                    }
+                  },
+                  {
+                    "type": "mention",
+                    "text": "<at>Reviewers</at>",
+                    "mentioned": {
+                      "id": "tag-1",
+                      "name": "Reviewers",
+                      "type": "tag"
+                    }
+                  }
+                ]
+              }
+            }"#]];
+        expected.assert_eq(&serde_json::to_string_pretty(&card).unwrap());
+    }
+
+    #[test]
+    fn test_mentioned_round_trip() {
+        let entities: Vec<Entity> = serde_json::from_value(serde_json::json!([
+            {
+                "type": "mention",
+                "text": "<at>Ada</at>",
+                "mentioned": { "id": "ada@example.com", "name": "Ada" }
+            },
+            {
+                "type": "mention",
+                "text": "<at>Ops</at>",
+                "mentioned": { "id": "tag-2", "name": "Ops", "type": "tag" }
+            },
+            {
+                "type": "mention",
+                "text": "<at>Grace</at>",
+                "mentioned": { "id": "8c6c1a0e-44b2-4d2b-9a26-5f4a2b3c7d10", "name": "Grace" }
+            }
+        ]))
+        .unwrap();
+
+        let ids: Vec<_> = entities
+            .iter()
+            .map(|Entity::Mention(mention)| &mention.mentioned)
+            .collect();
+        assert!(matches!(
+            ids[0],
+            Mentioned::User {
+                id: UserId::Upn(_),
+                ..
+            }
+        ));
+        assert!(matches!(ids[1], Mentioned::Tag { id, .. } if id == "tag-2"));
+        assert!(matches!(
+            ids[2],
+            Mentioned::User {
+                id: UserId::AadObjectId(_),
+                ..
+            }
+        ));
+        assert_eq!(
+            serde_json::to_value(&entities).unwrap()[1]["mentioned"]["type"],
+            "tag"
+        );
+    }
+
+    #[test]
+    fn test_msteams_actions() {
+        let actions = [
+            MsTeamsAction::MessageBack {
+                text: Some("approve".into()),
+                display_text: Some("Approved".into()),
+                value: Some(serde_json::json!({ "id": 7 })),
+            },
+            MsTeamsAction::ImBack {
+                value: "yes".into(),
+            },
+            MsTeamsAction::Invoke {
+                value: serde_json::json!({ "verb": "refresh" }),
+            },
+            MsTeamsAction::Signin {
+                value: "https://login.example.com".into(),
+            },
+            MsTeamsAction::TaskFetch,
+        ];
+        let json: Vec<_> = actions
+            .iter()
+            .map(|action| serde_json::to_string(action).unwrap())
+            .collect();
+        let expected = expect![[r#"
+            [
+                "{\"type\":\"messageBack\",\"text\":\"approve\",\"displayText\":\"Approved\",\"value\":{\"id\":7}}",
+                "{\"type\":\"imBack\",\"value\":\"yes\"}",
+                "{\"type\":\"invoke\",\"value\":{\"verb\":\"refresh\"}}",
+                "{\"type\":\"signin\",\"value\":\"https://login.example.com\"}",
+                "{\"type\":\"task/fetch\"}",
+            ]
+        "#]];
+        expected.assert_debug_eq(&json);
+
+        let mut submit = SubmitAction::builder()
+            .data("not an object")
+            .msteams(MsTeamsAction::TaskFetch)
+            .build();
+        assert_eq!(submit.msteams(), Some(MsTeamsAction::TaskFetch));
+        submit.set_msteams(actions[1].clone());
+        assert_eq!(submit.msteams(), Some(actions[1].clone()));
+        assert_eq!(
+            submit.data,
+            Some(serde_json::json!({ "msteams": { "type": "imBack", "value": "yes" } }))
+        );
+    }
+}
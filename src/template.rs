@@ -0,0 +1,502 @@
+//! Expands Adaptive Card templates authored with `${...}` data bindings.
+//!
+//! This follows the semantics of the official templating SDK:
+//!
+//! - `${expression}` inside a string is replaced by the value of the expression. A string
+//!   that consists of a single binding keeps the type of the value, so `"${count}"` can
+//!   produce a number. Bindings that evaluate to `null` are left untouched.
+//! - `"$data"` on an object changes the data context for that object. When it evaluates to
+//!   an array, the object is repeated once per item and `$index` holds the item's position.
+//! - `"$when"` on an object drops the object unless the expression is truthy.
+//! - `$root` always refers to the data passed to [`Template::expand`].
+//!
+//! ```
+//! use adaptive_card_rs::card::CardElement;
+//! use adaptive_card_rs::template::Template;
+//! use serde_json::json;
+//!
+//! let template = Template::new(json!({
+//!     "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+//!     "type": "AdaptiveCard",
+//!     "version": "1.5",
+//!     "body": [
+//!         { "type": "TextBlock", "text": "Hello ${name}!" },
+//!         { "type": "TextBlock", "$data": "${tasks}", "text": "${$index}. ${title}" }
+//!     ]
+//! }));
+//!
+//! let card = template
+//!     .render(&json!({ "name": "Ada", "tasks": [{ "title": "Write" }, { "title": "Ship" }] }))
+//!     .unwrap();
+//! assert_eq!(card.body.len(), 3);
+//! match &card.body[2] {
+//!     CardElement::TextBlock(text_block) => assert_eq!(text_block.text, "1. Ship"),
+//!     other => panic!("unexpected element {other:?}"),
+//! }
+//! ```
+use std::fmt;
+use std::str::FromStr;
+
+use serde_json::{Map, Value};
+
+use crate::card::AdaptiveCard;
+use crate::expressions::{self, Evaluator, Expr, Scope};
+
+/// An error raised while expanding a template.
+#[derive(Debug)]
+pub enum TemplateError {
+    /// A binding could not be parsed or evaluated.
+    Expression {
+        /// JSON pointer to the string containing the binding.
+        path: String,
+        /// The expression between `${` and `}`.
+        expression: String,
+        /// Why the expression failed.
+        message: String,
+    },
+    /// The template root was dropped by `$when` or repeated by `$data`.
+    InvalidRoot {
+        /// The number of objects the root expanded to.
+        count: usize,
+    },
+    /// The expanded template is not a valid card.
+    Card(serde_json::Error),
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::Expression {
+                path,
+                expression,
+                message,
+            } => write!(f, "{path}: cannot evaluate ${{{expression}}}: {message}"),
+            TemplateError::InvalidRoot { count } => write!(
+                f,
+                "template root must expand to exactly one object, got {count}"
+            ),
+            TemplateError::Card(e) => write!(f, "expanded template is not a valid card: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TemplateError::Card(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// A card template.
+#[derive(Debug, Clone)]
+pub struct Template {
+    template: Value,
+    evaluator: Evaluator,
+}
+
+impl Template {
+    /// Creates a template from its JSON representation.
+    pub fn new(template: Value) -> Self {
+        Self {
+            template,
+            evaluator: Evaluator::new(),
+        }
+    }
+
+    /// Evaluates bindings with `evaluator`, e.g. to make custom functions available.
+    pub fn with_evaluator(mut self, evaluator: Evaluator) -> Self {
+        self.evaluator = evaluator;
+        self
+    }
+
+    /// Expands the template against `data`, returning the resulting JSON.
+    pub fn expand(&self, data: &Value) -> Result<Value, TemplateError> {
+        let mut expanded = self.expand_item(&self.template, &Scope::new(data), "")?;
+        match expanded.len() {
+            1 => Ok(expanded.remove(0)),
+            count => Err(TemplateError::InvalidRoot { count }),
+        }
+    }
+
+    /// Expands the template against `data` and parses the result as a card.
+    pub fn render(&self, data: &Value) -> Result<AdaptiveCard, TemplateError> {
+        serde_json::from_value(self.expand(data)?).map_err(TemplateError::Card)
+    }
+}
+
+/// Parses a template from JSON, e.g. a file embedded with `include_str!`.
+///
+/// ```
+/// # use adaptive_card_rs::card::CardElement;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use adaptive_card_rs::template::Template;
+///
+/// let template: Template = include_str!("../tests/fixtures/card.template.json").parse()?;
+/// let card = template.render(&serde_json::json!({ "name": "Ada" }))?;
+/// # match &card.body[0] {
+/// #     CardElement::TextBlock(text_block) => assert_eq!(text_block.text, "Hello Ada!"),
+/// #     other => panic!("unexpected element {other:?}"),
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+impl FromStr for Template {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(serde_json::from_str(s)?))
+    }
+}
+
+impl Template {
+    /// Expands a value that appears as an array item, which may repeat or drop it.
+    fn expand_item(
+        &self,
+        value: &Value,
+        scope: &Scope<'_>,
+        path: &str,
+    ) -> Result<Vec<Value>, TemplateError> {
+        let Value::Object(object) = value else {
+            return Ok(vec![self.expand_value(value, scope, path)?]);
+        };
+
+        let Some(data) = object.get("$data") else {
+            return Ok(self
+                .expand_object(object, scope, path)?
+                .into_iter()
+                .collect());
+        };
+
+        let data = self.expand_value(data, scope, &format!("{path}/$data"))?;
+        match &data {
+            Value::Array(items) => {
+                let mut expanded = Vec::new();
+                for (index, item) in items.iter().enumerate() {
+                    let scope = Scope {
+                        data: item,
+                        root: scope.root,
+                        index: Some(index),
+                    };
+                    expanded.extend(self.expand_object(object, &scope, path)?);
+                }
+                Ok(expanded)
+            }
+            data => {
+                let scope = Scope {
+                    data,
+                    root: scope.root,
+                    index: scope.index,
+                };
+                Ok(self
+                    .expand_object(object, &scope, path)?
+                    .into_iter()
+                    .collect())
+            }
+        }
+    }
+
+    /// Expands an object in its data context, returning `None` if `$when` drops it.
+    fn expand_object(
+        &self,
+        object: &Map<String, Value>,
+        scope: &Scope<'_>,
+        path: &str,
+    ) -> Result<Option<Value>, TemplateError> {
+        if let Some(when) = object.get("$when") {
+            let when = self.expand_value(when, scope, &format!("{path}/$when"))?;
+            // An unresolved binding is left as a string, which counts as false here.
+            if when.is_string() || !expressions::is_truthy(&when) {
+                return Ok(None);
+            }
+        }
+
+        let mut expanded = Map::new();
+        for (key, value) in object {
+            if key == "$data" || key == "$when" {
+                continue;
+            }
+            let path = format!("{path}/{key}");
+            match value {
+                Value::Object(_) => {
+                    let mut items = self.expand_item(value, scope, &path)?;
+                    match items.len() {
+                        0 => {}
+                        1 => {
+                            expanded.insert(key.clone(), items.remove(0));
+                        }
+                        _ => {
+                            expanded.insert(key.clone(), Value::Array(items));
+                        }
+                    }
+                }
+                value => {
+                    expanded.insert(key.clone(), self.expand_value(value, scope, &path)?);
+                }
+            }
+        }
+        Ok(Some(Value::Object(expanded)))
+    }
+
+    fn expand_value(
+        &self,
+        value: &Value,
+        scope: &Scope<'_>,
+        path: &str,
+    ) -> Result<Value, TemplateError> {
+        match value {
+            Value::String(s) => self.expand_string(s, scope, path),
+            Value::Array(items) => {
+                let mut expanded = Vec::new();
+                for (i, item) in items.iter().enumerate() {
+                    expanded.extend(self.expand_item(item, scope, &format!("{path}/{i}"))?);
+                }
+                Ok(Value::Array(expanded))
+            }
+            Value::Object(object) => Ok(self
+                .expand_object(object, scope, path)?
+                .unwrap_or(Value::Null)),
+            other => Ok(other.clone()),
+        }
+    }
+
+    fn expand_string(
+        &self,
+        s: &str,
+        scope: &Scope<'_>,
+        path: &str,
+    ) -> Result<Value, TemplateError> {
+        let bindings = find_bindings(s);
+        if bindings.is_empty() {
+            return Ok(Value::String(s.to_string()));
+        }
+
+        // A string made of a single binding keeps the type of the bound value.
+        if let [(0, end)] = bindings.as_slice()
+            && *end == s.len()
+        {
+            let value = self.evaluate_binding(&s[2..end - 1], scope, path)?;
+            return Ok(if value.is_null() {
+                Value::String(s.to_string())
+            } else {
+                value
+            });
+        }
+
+        let mut expanded = String::new();
+        let mut last = 0;
+        for (start, end) in bindings {
+            expanded.push_str(&s[last..start]);
+            let value = self.evaluate_binding(&s[start + 2..end - 1], scope, path)?;
+            if value.is_null() {
+                expanded.push_str(&s[start..end]);
+            } else {
+                expanded.push_str(&expressions::to_text(&value));
+            }
+            last = end;
+        }
+        expanded.push_str(&s[last..]);
+        Ok(Value::String(expanded))
+    }
+
+    fn evaluate_binding(
+        &self,
+        expression: &str,
+        scope: &Scope<'_>,
+        path: &str,
+    ) -> Result<Value, TemplateError> {
+        Expr::parse(expression)
+            .map_err(|e| e.to_string())
+            .and_then(|expr| {
+                self.evaluator
+                    .evaluate_in(&expr, scope)
+                    .map_err(|e| e.to_string())
+            })
+            .map_err(|message| TemplateError::Expression {
+                path: path.to_string(),
+                expression: expression.to_string(),
+                message,
+            })
+    }
+}
+
+/// Returns the byte ranges of every `${...}` binding in `s`, including the delimiters.
+fn find_bindings(s: &str) -> Vec<(usize, usize)> {
+    let bytes = s.as_bytes();
+    let mut bindings = Vec::new();
+    let mut i = 0;
+    while i + 1 < bytes.len() {
+        if bytes[i] != b'$' || bytes[i + 1] != b'{' {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        let mut depth = 0;
+        let mut quote = None;
+        let mut end = None;
+        for (j, &b) in bytes.iter().enumerate().skip(i + 1) {
+            match (quote, b) {
+                (Some(q), b) if b == q => quote = None,
+                (Some(_), _) => {}
+                (None, b'\'' | b'"') => quote = Some(b),
+                (None, b'{') => depth += 1,
+                (None, b'}') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = Some(j + 1);
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        match end {
+            Some(end) => {
+                bindings.push((start, end));
+                i = end;
+            }
+            None => break,
+        }
+    }
+    bindings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_bindings_keep_type_when_alone() {
+        let template = Template::new(json!({
+            "count": "${count}",
+            "label": "Count: ${count}",
+            "missing": "${nope}",
+            "partial": "Hi ${nope}",
+        }));
+
+        assert_eq!(
+            template.expand(&json!({ "count": 3 })).unwrap(),
+            json!({
+                "count": 3,
+                "label": "Count: 3",
+                "missing": "${nope}",
+                "partial": "Hi ${nope}",
+            })
+        );
+    }
+
+    #[test]
+    fn test_data_repetition_when_and_root() {
+        let template = Template::new(json!({
+            "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+            "type": "AdaptiveCard",
+            "version": "1.5",
+            "body": [
+                {
+                    "type": "FactSet",
+                    "facts": [
+                        {
+                            "$data": "${items}",
+                            "$when": "${price > 1}",
+                            "title": "${name} (${$root.currency})",
+                            "value": "${string(price)}"
+                        }
+                    ]
+                },
+                {
+                    "type": "TextBlock",
+                    "$when": "${count(items) == 0}",
+                    "text": "Nothing to show"
+                }
+            ]
+        }));
+
+        let data = json!({
+            "currency": "EUR",
+            "items": [
+                { "name": "Tea", "price": 2 },
+                { "name": "Water", "price": 1 },
+                { "name": "Cake", "price": 4 }
+            ]
+        });
+
+        assert_eq!(
+            template.expand(&data).unwrap(),
+            json!({
+                "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                "type": "AdaptiveCard",
+                "version": "1.5",
+                "body": [
+                    {
+                        "type": "FactSet",
+                        "facts": [
+                            { "title": "Tea (EUR)", "value": "2" },
+                            { "title": "Cake (EUR)", "value": "4" }
+                        ]
+                    }
+                ]
+            })
+        );
+        assert!(template.render(&data).is_ok());
+    }
+
+    #[test]
+    fn test_nested_data_context() {
+        let template = Template::new(json!({
+            "$data": "${order}",
+            "title": "Order ${id} for ${$root.customer}",
+        }));
+
+        assert_eq!(
+            template
+                .expand(&json!({ "customer": "Ada", "order": { "id": 7 } }))
+                .unwrap(),
+            json!({ "title": "Order 7 for Ada" })
+        );
+    }
+
+    #[test]
+    fn test_invalid_expression_reports_path() {
+        let template = Template::new(json!({ "body": [{ "text": "${1 +}" }] }));
+
+        match template.expand(&json!({})) {
+            Err(TemplateError::Expression {
+                path, expression, ..
+            }) => {
+                assert_eq!(path, "/body/0/text");
+                assert_eq!(expression, "1 +");
+            }
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_custom_functions() {
+        let template = Template::new(json!({ "text": "${shout(name)}" })).with_evaluator(
+            Evaluator::new().with_function("shout", |args| {
+                Ok(Value::String(
+                    expressions::to_text(&args[0]).to_uppercase() + "!",
+                ))
+            }),
+        );
+
+        assert_eq!(
+            template.expand(&json!({ "name": "ada" })).unwrap(),
+            json!({ "text": "ADA!" })
+        );
+    }
+
+    #[test]
+    fn test_root_must_expand_to_one_object() {
+        let template = Template::new(json!({ "$when": "${false}", "type": "AdaptiveCard" }));
+
+        assert!(matches!(
+            template.expand(&json!({})),
+            Err(TemplateError::InvalidRoot { count: 0 })
+        ));
+    }
+}
@@ -0,0 +1,427 @@
+//! Works out which schema [`Version`] a card actually needs.
+//!
+//! Every element, property and enum value that was added after 1.0 is listed in a table
+//! together with the version that introduced it. A card is serialized and each object in the
+//! resulting JSON is looked up in that table, so the reported paths are JSON pointers into
+//! the serialized card.
+//!
+//! ```
+//! use adaptive_card_rs::card::{AdaptiveCard, Version};
+//! use adaptive_card_rs::actions::{Action, ExecuteAction};
+//!
+//! let card = AdaptiveCard {
+//!     version: Version::V1_2,
+//!     actions: Some(vec![Action::Execute(ExecuteAction::default())]),
+//!     ..Default::default()
+//! };
+//! assert_eq!(card.minimum_version(), Version::V1_4);
+//! assert_eq!(card.check_version()[0].path, "/actions/0");
+//! ```
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+use crate::card::{AdaptiveCard, Version};
+use crate::lint::{Diagnostic, Rule, Severity};
+
+/// An element, property or value that requires a minimum schema version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRequirement {
+    /// JSON pointer to the element or property in the serialized card.
+    pub path: String,
+    /// The feature in use, e.g. `Input.Text.label` or `Action.Execute`.
+    pub feature: String,
+    /// The version that introduced the feature.
+    pub version: Version,
+}
+
+impl fmt::Display for VersionRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} requires version {}",
+            self.path, self.feature, self.version
+        )
+    }
+}
+
+impl AdaptiveCard {
+    /// Returns every feature used by the card that was introduced after version 1.0.
+    pub fn version_requirements(&self) -> Vec<VersionRequirement> {
+        let mut requirements = Vec::new();
+        if let Ok(value) = serde_json::to_value(self) {
+            collect(&value, Some("AdaptiveCard"), "", &mut requirements);
+        }
+        requirements
+    }
+
+    /// Returns the lowest version able to render every feature used by the card.
+    pub fn minimum_version(&self) -> Version {
+        self.version_requirements()
+            .into_iter()
+            .map(|requirement| requirement.version)
+            .max()
+            .unwrap_or(Version::V1_0)
+    }
+
+    /// Returns every feature used by the card that is newer than the card's declared version.
+    pub fn check_version(&self) -> Vec<VersionRequirement> {
+        self.version_requirements()
+            .into_iter()
+            .filter(|requirement| requirement.version > self.version)
+            .collect()
+    }
+}
+
+/// Reports features that are newer than the card's declared version.
+pub struct ExceedsDeclaredVersion;
+
+impl Rule for ExceedsDeclaredVersion {
+    fn check(&self, card: &AdaptiveCard, diagnostics: &mut Vec<Diagnostic>) {
+        for requirement in card.check_version() {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Error,
+                code: "exceeds-declared-version",
+                path: requirement.path,
+                message: format!(
+                    "{} requires version {} but the card declares {}",
+                    requirement.feature, requirement.version, card.version
+                ),
+            });
+        }
+    }
+}
+
+/// A single row of the version table.
+pub(crate) struct Feature {
+    /// The type the feature belongs to. `*` matches any element, `Input.*` any input and
+    /// `Action.*` any action.
+    owner: &'static str,
+    /// The property, or `None` when the row describes the type itself.
+    pub(crate) property: Option<&'static str>,
+    /// The property value, or `None` when any value of the property requires the version.
+    value: Option<&'static str>,
+    pub(crate) version: Version,
+}
+
+const fn element(owner: &'static str, version: Version) -> Feature {
+    Feature {
+        owner,
+        property: None,
+        value: None,
+        version,
+    }
+}
+
+const fn property(owner: &'static str, property: &'static str, version: Version) -> Feature {
+    Feature {
+        owner,
+        property: Some(property),
+        value: None,
+        version,
+    }
+}
+
+const fn value(
+    owner: &'static str,
+    property: &'static str,
+    value: &'static str,
+    version: Version,
+) -> Feature {
+    Feature {
+        owner,
+        property: Some(property),
+        value: Some(value),
+        version,
+    }
+}
+
+const FEATURES: &[Feature] = &[
+    // card.rs
+    property("AdaptiveCard", "selectAction", Version::V1_1),
+    property("AdaptiveCard", "verticalContentAlignment", Version::V1_1),
+    property("AdaptiveCard", "minHeight", Version::V1_2),
+    property("AdaptiveCard", "refresh", Version::V1_4),
+    property("AdaptiveCard", "authentication", Version::V1_4),
+    property("AdaptiveCard", "rtl", Version::V1_5),
+    property("*", "height", Version::V1_1),
+    property("*", "isVisible", Version::V1_2),
+    property("TextBlock", "fontType", Version::V1_2),
+    property("TextBlock", "style", Version::V1_5),
+    property("Image", "backgroundColor", Version::V1_1),
+    property("Image", "width", Version::V1_1),
+    property("Image", "selectAction", Version::V1_1),
+    property("Container", "selectAction", Version::V1_1),
+    property("Container", "verticalContentAlignment", Version::V1_1),
+    property("Container", "bleed", Version::V1_2),
+    property("Container", "minHeight", Version::V1_2),
+    property("Container", "backgroundImage", Version::V1_2),
+    property("ColumnSet", "selectAction", Version::V1_1),
+    property("ColumnSet", "style", Version::V1_2),
+    property("ColumnSet", "bleed", Version::V1_2),
+    property("ColumnSet", "minHeight", Version::V1_2),
+    property("Column", "selectAction", Version::V1_1),
+    property("Column", "verticalContentAlignment", Version::V1_1),
+    property("Column", "bleed", Version::V1_2),
+    property("Column", "minHeight", Version::V1_2),
+    property("Column", "backgroundImage", Version::V1_2),
+    property("Column", "isVisible", Version::V1_2),
+    property("Column", "rtl", Version::V1_5),
+    element("Media", Version::V1_1),
+    element("MediaSource", Version::V1_1),
+    property("Media", "captionSources", Version::V1_6),
+    element("CaptionSource", Version::V1_6),
+    element("BackgroundImage", Version::V1_2),
+    element("ActionSet", Version::V1_2),
+    element("RichTextBlock", Version::V1_2),
+    element("TextRun", Version::V1_2),
+    element("Table", Version::V1_5),
+    element("TableRow", Version::V1_5),
+    element("TableCell", Version::V1_5),
+    element("TableColumnDefinition", Version::V1_5),
+    element("Refresh", Version::V1_4),
+    property("Refresh", "expires", Version::V1_6),
+    element("Authentication", Version::V1_4),
+    // inputs.rs
+    property("Input.*", "label", Version::V1_3),
+    property("Input.*", "isRequired", Version::V1_3),
+    property("Input.*", "errorMessage", Version::V1_3),
+    property("Input.Text", "regex", Version::V1_3),
+    value("Input.Text", "style", "password", Version::V1_5),
+    property("Input.Toggle", "wrap", Version::V1_2),
+    property("Input.ChoiceSet", "wrap", Version::V1_2),
+    value("Input.ChoiceSet", "style", "filtered", Version::V1_5),
+    // actions.rs
+    property("Action.*", "iconUrl", Version::V1_1),
+    property("Action.*", "style", Version::V1_2),
+    property("Action.*", "tooltip", Version::V1_5),
+    property("Action.*", "isEnabled", Version::V1_5),
+    property("Action.*", "mode", Version::V1_5),
+    property("Action.Submit", "associatedInputs", Version::V1_3),
+    element("Action.ToggleVisibility", Version::V1_2),
+    element("Action.Execute", Version::V1_4),
+];
+
+const ELEMENTS: &[&str] = &[
+    "TextBlock",
+    "Container",
+    "ColumnSet",
+    "Image",
+    "ActionSet",
+    "FactSet",
+    "RichTextBlock",
+    "Table",
+    "Media",
+    "ImageSet",
+    "Input.Text",
+    "Input.Number",
+    "Input.Date",
+    "Input.Time",
+    "Input.Toggle",
+    "Input.ChoiceSet",
+];
+
+impl Feature {
+    fn applies_to(&self, type_name: &str) -> bool {
+        match self.owner {
+            "*" => ELEMENTS.contains(&type_name),
+            "Input.*" => type_name.starts_with("Input."),
+            "Action.*" => type_name.starts_with("Action."),
+            owner => owner == type_name,
+        }
+    }
+
+    pub(crate) fn name(&self, type_name: &str) -> String {
+        match (self.property, self.value) {
+            (None, _) => type_name.to_string(),
+            (Some(property), None) => format!("{type_name}.{property}"),
+            (Some(property), Some(value)) => format!("{type_name}.{property}: \"{value}\""),
+        }
+    }
+}
+
+/// Returns the type of objects that do not carry a `type` property, based on where they appear.
+pub(crate) fn implied_type(key: &str) -> Option<&'static str> {
+    match key {
+        "images" => Some("Image"),
+        "sources" => Some("MediaSource"),
+        "captionSources" => Some("CaptionSource"),
+        "backgroundImage" => Some("BackgroundImage"),
+        "refresh" => Some("Refresh"),
+        "authentication" => Some("Authentication"),
+        "card" => Some("AdaptiveCard"),
+        _ => None,
+    }
+}
+
+/// Returns true for properties whose contents are opaque to the schema, such as submit data
+/// and host-specific properties.
+pub(crate) fn is_opaque(key: &str) -> bool {
+    matches!(key, "data" | "msteams")
+}
+
+/// Returns the type of a serialized object, falling back to the type implied by its position.
+pub(crate) fn type_name<'a>(object: &'a Map<String, Value>, implied: Option<&'a str>) -> &'a str {
+    object
+        .get("type")
+        .and_then(Value::as_str)
+        .or(implied)
+        .unwrap_or_default()
+}
+
+/// Returns the rows of the version table used by a serialized object of the given type.
+pub(crate) fn features_used<'a>(
+    object: &'a Map<String, Value>,
+    type_name: &'a str,
+) -> impl Iterator<Item = &'static Feature> + 'a {
+    FEATURES.iter().filter(move |feature| {
+        feature.applies_to(type_name)
+            && match (feature.property, feature.value) {
+                (None, _) => true,
+                (Some(property), None) => object.contains_key(property),
+                (Some(property), Some(value)) => {
+                    object.get(property).and_then(Value::as_str) == Some(value)
+                }
+            }
+    })
+}
+
+fn collect(
+    value: &Value,
+    implied: Option<&str>,
+    path: &str,
+    requirements: &mut Vec<VersionRequirement>,
+) {
+    match value {
+        Value::Object(object) => {
+            let type_name = type_name(object, implied);
+            for feature in features_used(object, type_name) {
+                requirements.push(VersionRequirement {
+                    path: match feature.property {
+                        Some(property) => format!("{path}/{property}"),
+                        None => path.to_string(),
+                    },
+                    feature: feature.name(type_name),
+                    version: feature.version,
+                });
+            }
+
+            for (key, child) in object.iter().filter(|(key, _)| !is_opaque(key)) {
+                collect(
+                    child,
+                    implied_type(key),
+                    &format!("{path}/{key}"),
+                    requirements,
+                );
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                collect(item, implied, &format!("{path}/{i}"), requirements);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::{BackgroundImage, CardElement, Container, TextBlock};
+    use crate::common::ChoiceInputStyle;
+    use crate::inputs::InputChoiceSet;
+
+    fn choice_set(style: ChoiceInputStyle, label: Option<&str>) -> CardElement {
+        CardElement::InputChoiceSet(InputChoiceSet {
+            id: "choice".to_string(),
+            choices: None,
+            is_multi_select: None,
+            style: Some(style),
+            value: None,
+            placeholder: None,
+            wrap: None,
+            label: label.map(str::to_string),
+            is_required: None,
+            error_message: None,
+            separator: None,
+            spacing: None,
+            height: None,
+            is_visible: None,
+        })
+    }
+
+    #[test]
+    fn test_plain_card_requires_1_0() {
+        let card = AdaptiveCard {
+            body: vec![CardElement::TextBlock(TextBlock {
+                text: "Hello".to_string(),
+                ..Default::default()
+            })],
+            ..Default::default()
+        };
+
+        assert_eq!(card.minimum_version(), Version::V1_0);
+        assert_eq!(card.check_version(), vec![]);
+    }
+
+    #[test]
+    fn test_reports_every_feature_above_declared_version() {
+        let card = AdaptiveCard {
+            version: Version::V1_2,
+            body: vec![CardElement::Container(Container {
+                background_image: Some(BackgroundImage::Url(
+                    "https://example.com/bg.png".to_string(),
+                )),
+                items: vec![choice_set(ChoiceInputStyle::Filtered, Some("Pick one"))],
+                ..Default::default()
+            })],
+            ..Default::default()
+        };
+
+        let features: Vec<(String, String, Version)> = card
+            .check_version()
+            .into_iter()
+            .map(|r| (r.path, r.feature, r.version))
+            .collect();
+        assert_eq!(
+            features,
+            vec![
+                (
+                    "/body/0/items/0/label".to_string(),
+                    "Input.ChoiceSet.label".to_string(),
+                    Version::V1_3
+                ),
+                (
+                    "/body/0/items/0/style".to_string(),
+                    "Input.ChoiceSet.style: \"filtered\"".to_string(),
+                    Version::V1_5
+                ),
+            ]
+        );
+        assert_eq!(card.minimum_version(), Version::V1_5);
+    }
+
+    #[test]
+    fn test_enum_values_only_match_exact_value() {
+        let card = AdaptiveCard {
+            body: vec![choice_set(ChoiceInputStyle::Compact, None)],
+            ..Default::default()
+        };
+
+        assert_eq!(card.minimum_version(), Version::V1_0);
+    }
+
+    #[test]
+    fn test_lint_rule_reports_exceeded_version() {
+        let card = AdaptiveCard {
+            version: Version::V1_2,
+            body: vec![choice_set(ChoiceInputStyle::Compact, Some("Pick one"))],
+            ..Default::default()
+        };
+
+        let diagnostics = card.lint();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "exceeds-declared-version");
+        assert_eq!(diagnostics[0].path, "/body/0/label");
+    }
+}
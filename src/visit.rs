@@ -0,0 +1,502 @@
+//! Generic traversal of a card's element tree.
+//!
+//! Elements, actions and nested cards are reached through many different fields:
+//! `Container.items`, `Column.items`, `TableCell.items`, `ActionSet.actions`,
+//! `ShowCardAction.card` and the `select_action` of several elements. [`AdaptiveCard::walk`]
+//! and [`AdaptiveCard::walk_mut`] follow all of them and call a [`Visitor`] or
+//! [`VisitorMut`] with each node and its JSON pointer, e.g. `/body/0/items/1`.
+//!
+//! ```
+//! use adaptive_card_rs::card::{AdaptiveCard, CardElement};
+//! use adaptive_card_rs::visit::Visitor;
+//!
+//! #[derive(Default)]
+//! struct TextPaths(Vec<String>);
+//!
+//! impl Visitor<'_> for TextPaths {
+//!     fn visit_element(&mut self, path: &str, element: &CardElement) {
+//!         if let CardElement::TextBlock(_) = element {
+//!             self.0.push(path.to_string());
+//!         }
+//!     }
+//! }
+//!
+//! let card = AdaptiveCard::builder()
+//!     .text("Title")
+//!     .container(|c| c.text("Nested"))
+//!     .build();
+//! let mut paths = TextPaths::default();
+//! card.walk(&mut paths);
+//! assert_eq!(paths.0, ["/body/0", "/body/1/items/0"]);
+//! ```
+//!
+//! Nodes are visited before their children. A [`VisitorMut`] can change or replace a node,
+//! and its new children are the ones walked.
+use crate::actions::Action;
+use crate::card::{AdaptiveCard, CardElement, Column};
+
+/// Called for each node of a card by [`AdaptiveCard::walk`].
+///
+/// Every method does nothing by default, so implement only the ones you need.
+pub trait Visitor<'a> {
+    /// Called for the card being walked and for every card shown by an `Action.ShowCard`.
+    fn visit_card(&mut self, _path: &str, _card: &'a AdaptiveCard) {}
+
+    /// Called after everything in a card has been visited.
+    fn leave_card(&mut self, _path: &str, _card: &'a AdaptiveCard) {}
+
+    /// Called for each list of elements, before its elements are visited.
+    fn visit_elements(&mut self, _path: &str, _elements: &'a [CardElement]) {}
+
+    /// Called for every element.
+    fn visit_element(&mut self, _path: &str, _element: &'a CardElement) {}
+
+    /// Called for every column of a `ColumnSet`.
+    fn visit_column(&mut self, _path: &str, _column: &'a Column) {}
+
+    /// Called for every action, including select actions.
+    fn visit_action(&mut self, _path: &str, _action: &'a Action) {}
+
+    /// Called for the `select_action` of a card, element, column or table cell. Defaults to
+    /// [`visit_action`](Visitor::visit_action).
+    fn visit_select_action(&mut self, path: &str, action: &'a Action) {
+        self.visit_action(path, action);
+    }
+}
+
+/// Called for each node of a card by [`AdaptiveCard::walk_mut`].
+///
+/// Every method does nothing by default, so implement only the ones you need.
+pub trait VisitorMut {
+    /// Called for the card being walked and for every card shown by an `Action.ShowCard`.
+    fn visit_card(&mut self, _path: &str, _card: &mut AdaptiveCard) {}
+
+    /// Called after everything in a card has been visited.
+    fn leave_card(&mut self, _path: &str, _card: &mut AdaptiveCard) {}
+
+    /// Called for each list of elements, before its elements are visited. Elements can be
+    /// added or removed here.
+    fn visit_elements(&mut self, _path: &str, _elements: &mut Vec<CardElement>) {}
+
+    /// Called for every element.
+    fn visit_element(&mut self, _path: &str, _element: &mut CardElement) {}
+
+    /// Called for every column of a `ColumnSet`.
+    fn visit_column(&mut self, _path: &str, _column: &mut Column) {}
+
+    /// Called for every action, including select actions.
+    fn visit_action(&mut self, _path: &str, _action: &mut Action) {}
+
+    /// Called for the `select_action` of a card, element, column or table cell. Defaults to
+    /// [`visit_action`](VisitorMut::visit_action).
+    fn visit_select_action(&mut self, path: &str, action: &mut Action) {
+        self.visit_action(path, action);
+    }
+}
+
+impl AdaptiveCard {
+    /// Visits every element, action and nested card in this card.
+    pub fn walk<'a>(&'a self, visitor: &mut impl Visitor<'a>) {
+        walk_card(visitor, "", self);
+    }
+
+    /// Visits every element, action and nested card in this card, allowing them to be
+    /// changed.
+    pub fn walk_mut(&mut self, visitor: &mut impl VisitorMut) {
+        walk_card_mut(visitor, "", self);
+    }
+}
+
+/// Visits `card` and everything in it, using `path` as the card's JSON pointer.
+pub fn walk_card<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, path: &str, card: &'a AdaptiveCard) {
+    visitor.visit_card(path, card);
+    walk_elements(visitor, &format!("{path}/body"), &card.body);
+    if let Some(actions) = &card.actions {
+        walk_actions(visitor, &format!("{path}/actions"), actions);
+    }
+    walk_select_action(visitor, path, card.select_action.as_deref());
+    if let Some(refresh) = &card.refresh {
+        walk_action(visitor, &format!("{path}/refresh/action"), &refresh.action);
+    }
+    visitor.leave_card(path, card);
+}
+
+/// Visits a list of elements and everything in them.
+pub fn walk_elements<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V,
+    path: &str,
+    elements: &'a [CardElement],
+) {
+    visitor.visit_elements(path, elements);
+    for (i, element) in elements.iter().enumerate() {
+        walk_element(visitor, &format!("{path}/{i}"), element);
+    }
+}
+
+/// Visits `element` and everything in it.
+pub fn walk_element<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V,
+    path: &str,
+    element: &'a CardElement,
+) {
+    visitor.visit_element(path, element);
+    match element {
+        CardElement::Container(container) => {
+            walk_elements(visitor, &format!("{path}/items"), &container.items);
+            walk_select_action(visitor, path, container.select_action.as_deref());
+        }
+        CardElement::ColumnSet(column_set) => {
+            for (i, column) in column_set.columns.iter().enumerate() {
+                let path = format!("{path}/columns/{i}");
+                visitor.visit_column(&path, column);
+                walk_elements(visitor, &format!("{path}/items"), &column.items);
+                walk_select_action(visitor, &path, column.select_action.as_deref());
+            }
+            walk_select_action(visitor, path, column_set.select_action.as_deref());
+        }
+        CardElement::Image(image) => {
+            walk_select_action(visitor, path, image.select_action.as_deref());
+        }
+        CardElement::ImageSet(image_set) => {
+            for (i, image) in image_set.images.iter().enumerate() {
+                let path = format!("{path}/images/{i}");
+                walk_select_action(visitor, &path, image.select_action.as_deref());
+            }
+        }
+        CardElement::ActionSet(action_set) => {
+            walk_actions(visitor, &format!("{path}/actions"), &action_set.actions);
+        }
+        CardElement::Table(table) => {
+            for (i, row) in table.rows.iter().flatten().enumerate() {
+                for (j, cell) in row.cells.iter().enumerate() {
+                    let path = format!("{path}/rows/{i}/cells/{j}");
+                    walk_elements(visitor, &format!("{path}/items"), &cell.items);
+                    walk_select_action(visitor, &path, cell.select_action.as_deref());
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn walk_actions<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, path: &str, actions: &'a [Action]) {
+    for (i, action) in actions.iter().enumerate() {
+        walk_action(visitor, &format!("{path}/{i}"), action);
+    }
+}
+
+/// Visits `action` and, for `Action.ShowCard`, the card it shows.
+pub fn walk_action<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, path: &str, action: &'a Action) {
+    visitor.visit_action(path, action);
+    if let Action::ShowCard(show_card) = action {
+        walk_card(visitor, &format!("{path}/card"), &show_card.card);
+    }
+}
+
+fn walk_select_action<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V,
+    path: &str,
+    action: Option<&'a Action>,
+) {
+    if let Some(action) = action {
+        let path = format!("{path}/selectAction");
+        visitor.visit_select_action(&path, action);
+        if let Action::ShowCard(show_card) = action {
+            walk_card(visitor, &format!("{path}/card"), &show_card.card);
+        }
+    }
+}
+
+/// Visits `card` and everything in it mutably, using `path` as the card's JSON pointer.
+pub fn walk_card_mut<V: VisitorMut + ?Sized>(visitor: &mut V, path: &str, card: &mut AdaptiveCard) {
+    visitor.visit_card(path, card);
+    walk_elements_mut(visitor, &format!("{path}/body"), &mut card.body);
+    if let Some(actions) = &mut card.actions {
+        walk_actions_mut(visitor, &format!("{path}/actions"), actions);
+    }
+    walk_select_action_mut(visitor, path, card.select_action.as_deref_mut());
+    if let Some(refresh) = &mut card.refresh {
+        walk_action_mut(
+            visitor,
+            &format!("{path}/refresh/action"),
+            &mut refresh.action,
+        );
+    }
+    visitor.leave_card(path, card);
+}
+
+/// Visits a list of elements and everything in them mutably.
+pub fn walk_elements_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &str,
+    elements: &mut Vec<CardElement>,
+) {
+    visitor.visit_elements(path, elements);
+    for (i, element) in elements.iter_mut().enumerate() {
+        walk_element_mut(visitor, &format!("{path}/{i}"), element);
+    }
+}
+
+/// Visits `element` and everything in it mutably.
+pub fn walk_element_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &str,
+    element: &mut CardElement,
+) {
+    visitor.visit_element(path, element);
+    match element {
+        CardElement::Container(container) => {
+            walk_elements_mut(visitor, &format!("{path}/items"), &mut container.items);
+            walk_select_action_mut(visitor, path, container.select_action.as_deref_mut());
+        }
+        CardElement::ColumnSet(column_set) => {
+            for (i, column) in column_set.columns.iter_mut().enumerate() {
+                let path = format!("{path}/columns/{i}");
+                visitor.visit_column(&path, column);
+                walk_elements_mut(visitor, &format!("{path}/items"), &mut column.items);
+                walk_select_action_mut(visitor, &path, column.select_action.as_deref_mut());
+            }
+            walk_select_action_mut(visitor, path, column_set.select_action.as_deref_mut());
+        }
+        CardElement::Image(image) => {
+            walk_select_action_mut(visitor, path, image.select_action.as_deref_mut());
+        }
+        CardElement::ImageSet(image_set) => {
+            for (i, image) in image_set.images.iter_mut().enumerate() {
+                let path = format!("{path}/images/{i}");
+                walk_select_action_mut(visitor, &path, image.select_action.as_deref_mut());
+            }
+        }
+        CardElement::ActionSet(action_set) => {
+            walk_actions_mut(visitor, &format!("{path}/actions"), &mut action_set.actions);
+        }
+        CardElement::Table(table) => {
+            for (i, row) in table.rows.iter_mut().flatten().enumerate() {
+                for (j, cell) in row.cells.iter_mut().enumerate() {
+                    let path = format!("{path}/rows/{i}/cells/{j}");
+                    walk_elements_mut(visitor, &format!("{path}/items"), &mut cell.items);
+                    walk_select_action_mut(visitor, &path, cell.select_action.as_deref_mut());
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn walk_actions_mut<V: VisitorMut + ?Sized>(visitor: &mut V, path: &str, actions: &mut [Action]) {
+    for (i, action) in actions.iter_mut().enumerate() {
+        walk_action_mut(visitor, &format!("{path}/{i}"), action);
+    }
+}
+
+/// Visits `action` mutably and, for `Action.ShowCard`, the card it shows.
+pub fn walk_action_mut<V: VisitorMut + ?Sized>(visitor: &mut V, path: &str, action: &mut Action) {
+    visitor.visit_action(path, action);
+    if let Action::ShowCard(show_card) = action {
+        walk_card_mut(visitor, &format!("{path}/card"), &mut show_card.card);
+    }
+}
+
+fn walk_select_action_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    path: &str,
+    action: Option<&mut Action>,
+) {
+    if let Some(action) = action {
+        let path = format!("{path}/selectAction");
+        visitor.visit_select_action(&path, action);
+        if let Action::ShowCard(show_card) = action {
+            walk_card_mut(visitor, &format!("{path}/card"), &mut show_card.card);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{OpenUrlAction, ShowCardAction, SubmitAction};
+    use crate::card::{Refresh, TextBlock};
+
+    #[derive(Default)]
+    struct Paths(Vec<String>);
+
+    impl Visitor<'_> for Paths {
+        fn visit_card(&mut self, path: &str, _: &AdaptiveCard) {
+            self.0.push(format!("card {path}"));
+        }
+
+        fn visit_element(&mut self, path: &str, _: &CardElement) {
+            self.0.push(format!("element {path}"));
+        }
+
+        fn visit_column(&mut self, path: &str, _: &Column) {
+            self.0.push(format!("column {path}"));
+        }
+
+        fn visit_action(&mut self, path: &str, _: &Action) {
+            self.0.push(format!("action {path}"));
+        }
+    }
+
+    /// Records the same paths as [`Paths`] from [`AdaptiveCard::walk_mut`], and when cards
+    /// are left.
+    #[derive(Default)]
+    struct PathsMut(Vec<String>);
+
+    impl VisitorMut for PathsMut {
+        fn visit_card(&mut self, path: &str, _: &mut AdaptiveCard) {
+            self.0.push(format!("card {path}"));
+        }
+
+        fn leave_card(&mut self, path: &str, _: &mut AdaptiveCard) {
+            self.0.push(format!("leave {path}"));
+        }
+
+        fn visit_element(&mut self, path: &str, _: &mut CardElement) {
+            self.0.push(format!("element {path}"));
+        }
+
+        fn visit_column(&mut self, path: &str, _: &mut Column) {
+            self.0.push(format!("column {path}"));
+        }
+
+        fn visit_action(&mut self, path: &str, _: &mut Action) {
+            self.0.push(format!("action {path}"));
+        }
+    }
+
+    fn card() -> AdaptiveCard {
+        AdaptiveCard::builder()
+            .text("Title")
+            .column_set(|c| {
+                c.column(|c| {
+                    c.text("Left")
+                        .select_action(OpenUrlAction::builder("https://a"))
+                })
+            })
+            .action_set(|a| a.show_card(AdaptiveCard::builder().text("Shown").build(), |a| a))
+            .open_url("https://b", |a| a)
+            .build()
+    }
+
+    #[test]
+    fn test_walk_visits_every_node_with_its_path() {
+        let mut paths = Paths::default();
+        card().walk(&mut paths);
+
+        assert_eq!(
+            paths.0,
+            [
+                "card ",
+                "element /body/0",
+                "element /body/1",
+                "column /body/1/columns/0",
+                "element /body/1/columns/0/items/0",
+                "action /body/1/columns/0/selectAction",
+                "element /body/2",
+                "action /body/2/actions/0",
+                "card /body/2/actions/0/card",
+                "element /body/2/actions/0/card/body/0",
+                "action /actions/0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_walk_mut_transforms_nested_elements() {
+        struct Shout;
+
+        impl VisitorMut for Shout {
+            fn visit_elements(&mut self, _: &str, elements: &mut Vec<CardElement>) {
+                elements.retain(|element| !matches!(element, CardElement::ColumnSet(_)));
+            }
+
+            fn visit_element(&mut self, _: &str, element: &mut CardElement) {
+                if let CardElement::TextBlock(TextBlock { text, .. }) = element {
+                    *text = text.to_uppercase();
+                }
+            }
+        }
+
+        let mut card = card();
+        card.walk_mut(&mut Shout);
+
+        let mut paths = Paths::default();
+        card.walk(&mut paths);
+        assert_eq!(paths.0.len(), 7);
+        let CardElement::ActionSet(action_set) = &card.body[1] else {
+            panic!("expected an action set");
+        };
+        let Action::ShowCard(show_card) = &action_set.actions[0] else {
+            panic!("expected a show card action");
+        };
+        assert!(matches!(
+            &show_card.card.body[0],
+            CardElement::TextBlock(TextBlock { text, .. }) if text == "SHOWN"
+        ));
+    }
+
+    #[test]
+    fn test_walk_visits_tables_image_sets_and_refresh() {
+        let mut card = AdaptiveCard::builder()
+            .table(|t| {
+                t.row(|r| {
+                    r.cell(|c| c.text("Cell").select_action(SubmitAction::builder()))
+                        .cell(|c| c.text("Other"))
+                })
+            })
+            .image_set(|i| {
+                i.image("https://a.png", |i| i).image("https://b.png", |i| {
+                    i.select_action(OpenUrlAction::builder("https://b"))
+                })
+            })
+            .build();
+        card.refresh = Some(
+            Refresh::builder(ShowCardAction::builder(
+                AdaptiveCard::builder().text("Refreshed").build(),
+            ))
+            .build(),
+        );
+
+        let mut paths = Paths::default();
+        card.walk(&mut paths);
+        assert_eq!(
+            paths.0,
+            [
+                "card ",
+                "element /body/0",
+                "element /body/0/rows/0/cells/0/items/0",
+                "action /body/0/rows/0/cells/0/selectAction",
+                "element /body/0/rows/0/cells/1/items/0",
+                "element /body/1",
+                "action /body/1/images/1/selectAction",
+                "action /refresh/action",
+                "card /refresh/action/card",
+                "element /refresh/action/card/body/0",
+            ]
+        );
+
+        let mut paths_mut = PathsMut::default();
+        card.walk_mut(&mut paths_mut);
+        let left: Vec<_> = paths_mut
+            .0
+            .iter()
+            .filter(|path| path.starts_with("leave "))
+            .collect();
+        assert_eq!(left, ["leave /refresh/action/card", "leave "]);
+        paths_mut.0.retain(|path| !path.starts_with("leave "));
+        assert_eq!(paths_mut.0, paths.0);
+    }
+
+    #[test]
+    fn test_walk_mut_visits_the_same_nodes_as_walk() {
+        let mut card = card();
+        let mut paths = Paths::default();
+        card.walk(&mut paths);
+        let mut paths_mut = PathsMut::default();
+        card.walk_mut(&mut paths_mut);
+        assert_eq!(paths_mut.0[paths_mut.0.len() - 1..], ["leave ".to_string()]);
+        paths_mut.0.retain(|path| !path.starts_with("leave "));
+        assert_eq!(paths_mut.0, paths.0);
+    }
+}